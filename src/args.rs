@@ -0,0 +1,69 @@
+//! The errors and the parsing helpers of the filter arguments.
+/// The error of a filter argument with an unexpected value.
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+pub(crate) fn invalid_argument(argument: &'static str, cause: &'static str) -> liquid_core::Error {
+  liquid_core::Error::with_msg("Invalid argument")
+    .context("argument", argument)
+    .context("cause", cause)
+}
+
+/// The error of a filter argument naming an unknown case, style, ...: the
+/// error shows the value and lists the `expected` ones.
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+pub(crate) fn unknown_value(
+  argument: &'static str,
+  value: &str,
+  expected: &[&str],
+) -> liquid_core::Error {
+  liquid_core::Error::with_msg("Invalid argument")
+    .context("argument", argument)
+    .context("value", format!("{value:?}"))
+    .context("expected", one_of(expected))
+}
+
+/// The value of the argument `argument` spelled `value`, as parsed by
+/// `from_name`, one of the `expected` names.
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+pub(crate) fn named_value<T>(
+  argument: &'static str,
+  value: &str,
+  from_name: impl FnOnce(&str) -> Option<T>,
+  expected: &[&str],
+) -> liquid_core::Result<T> {
+  from_name(value).ok_or_else(|| unknown_value(argument, value, expected))
+}
+
+/// The value of an argument accepted both positionally and by keyword, as
+/// `| helm_name: 40` and `| helm_name: max_length: 40`. Passing it both ways
+/// is an error.
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+pub(crate) fn positional_or_keyword<T>(
+  argument: &'static str,
+  positional: Option<T>,
+  keyword: Option<T>,
+) -> liquid_core::Result<Option<T>> {
+  match (positional, keyword) {
+    (Some(_), Some(_)) => Err(invalid_argument(
+      argument,
+      "Passed both positionally and by keyword",
+    )),
+    (positional, keyword) => Ok(positional.or(keyword)),
+  }
+}
+
+/// The `expected` values as a sentence: `"a", "b" or "c"`.
+pub(crate) fn one_of(expected: &[&str]) -> String {
+  let mut sentence = String::new();
+  for (i, value) in expected.iter().enumerate() {
+    let separator = match i {
+      0 => "",
+      i if i + 1 == expected.len() => " or ",
+      _ => ", ",
+    };
+    sentence.push_str(separator);
+    sentence.push('"');
+    sentence.push_str(value);
+    sentence.push('"');
+  }
+  sentence
+}
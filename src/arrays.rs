@@ -0,0 +1,448 @@
+//! Filters over arrays of identifiers.
+#[cfg(feature = "icu")]
+use crate::args::invalid_argument;
+use crate::args::unknown_value;
+use crate::words::Words;
+use crate::Options;
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  Object, ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// The items of `input`: the values of an array, nothing for nil, or `input`
+/// itself.
+pub(crate) fn as_sequence<'a>(
+  input: &'a dyn ValueView,
+) -> Box<dyn Iterator<Item = &'a dyn ValueView> + 'a> {
+  if let Some(array) = input.as_array() {
+    array.values()
+  } else if input.is_nil() {
+    Box::new(std::iter::empty())
+  } else {
+    Box::new(std::iter::once(input))
+  }
+}
+
+/// A part of a sort key. Numbers sort before text.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum KeyPart {
+  /// A run of digits, as its length without the leading zeros and its
+  /// digits, so any number compares by value.
+  Number(usize, String),
+  Text(String),
+}
+
+/// The case-folded words of `s`, with the digit runs compared by value when
+/// `natural`.
+fn sort_key(s: &str, natural: bool) -> Vec<KeyPart> {
+  let mut key = Vec::new();
+  for word in Words::new(s, &Options::default()) {
+    let word = word.to_lowercase();
+    if !natural {
+      key.push(KeyPart::Text(word));
+      continue;
+    }
+    let mut rest = word.as_str();
+    while let Some(c) = rest.chars().next() {
+      let digit = c.is_ascii_digit();
+      let end = rest
+        .find(|c: char| c.is_ascii_digit() != digit)
+        .unwrap_or(rest.len());
+      let (part, tail) = rest.split_at(end);
+      key.push(if digit {
+        let digits = part.trim_start_matches('0');
+        KeyPart::Number(digits.len(), digits.to_string())
+      } else {
+        KeyPart::Text(part.to_string())
+      });
+      rest = tail;
+    }
+  }
+  key
+}
+
+/// Sort the items of `input` by their [`sort_key`], then by their spelling.
+fn sort(input: &dyn ValueView, natural: bool) -> Value {
+  let mut items: Vec<_> = as_sequence(input)
+    .map(|item| {
+      let s = item.to_kstr();
+      (
+        sort_key(s.as_str(), natural),
+        s.into_string(),
+        item.to_value(),
+      )
+    })
+    .collect();
+  items.sort_by(|(a_key, a, _), (b_key, b, _)| a_key.cmp(b_key).then_with(|| a.cmp(b)));
+  Value::Array(items.into_iter().map(|(_, _, item)| item).collect())
+}
+
+/// Sort an array of strings by their words, ignoring the case and the
+/// separators: `Zebra` sorts after `apple`.
+#[cfg(feature = "filter-sort-ci")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "sort_ci",
+  description = "Sort the array by the words of its items, ignoring their case.",
+  parsed(SortCiFilter)
+)]
+pub struct SortCi;
+
+#[cfg(feature = "filter-sort-ci")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "sort_ci"]
+struct SortCiFilter;
+
+#[cfg(feature = "filter-sort-ci")]
+impl Filter for SortCiFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, _runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    let output = sort(input, false);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "sort_ci: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+/// Sort an array of identifiers like [`SortCi`], comparing the numbers by
+/// value: `item2` sorts before `item10`.
+#[cfg(feature = "filter-sort-natural-ident")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "sort_natural_ident",
+  description = "Sort the array by the words of its items, ignoring their case and comparing the numbers by value.",
+  parsed(SortNaturalIdentFilter)
+)]
+pub struct SortNaturalIdent;
+
+#[cfg(feature = "filter-sort-natural-ident")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "sort_natural_ident"]
+struct SortNaturalIdentFilter;
+
+#[cfg(feature = "filter-sort-natural-ident")]
+impl Filter for SortNaturalIdentFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, _runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    let output = sort(input, true);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "sort_natural_ident: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+/// Group an array of identifiers by their first word, ignoring its case:
+/// `["user_id", "userName", "age"]` becomes
+/// `{"user": ["user_id", "userName"], "age": ["age"]}`.
+#[cfg(feature = "filter-group-by-prefix")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "group_by_prefix",
+  description = "Group the array items by their lowercased first word.",
+  parsed(GroupByPrefixFilter)
+)]
+pub struct GroupByPrefix;
+
+#[cfg(feature = "filter-group-by-prefix")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "group_by_prefix"]
+struct GroupByPrefixFilter;
+
+#[cfg(feature = "filter-group-by-prefix")]
+impl Filter for GroupByPrefixFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, _runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
+    let mut groups = Object::new();
+    for item in as_sequence(input) {
+      let s = item.to_kstr();
+      let prefix = Words::new(s.as_str(), &Options::default())
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+      match groups
+        .entry(prefix)
+        .or_insert_with(|| Value::Array(Vec::new()))
+      {
+        Value::Array(group) => group.push(item.to_value()),
+        _ => unreachable!("the groups are arrays"),
+      }
+    }
+    let output = Value::Object(groups);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "group_by_prefix: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+#[cfg(feature = "filter-dedupe-idents")]
+#[derive(Debug, FilterParameters)]
+struct DedupeIdentsArgs {
+  #[parameter(
+    description = "Which spelling of the duplicates to keep: \"first\" (default) or \"last\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  keep: Option<Expression>,
+}
+
+/// Remove the identifiers spelling the same words as an earlier one:
+/// `["userId", "user_id", "name"]` becomes `["userId", "name"]`. With
+/// `keep: "last"`, the last spelling is kept, at the place of the last one.
+#[cfg(feature = "filter-dedupe-idents")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "dedupe_idents",
+  description = "Remove the array items made of the same words as another item, whatever their case.",
+  parameters(DedupeIdentsArgs),
+  parsed(DedupeIdentsFilter)
+)]
+pub struct DedupeIdents;
+
+#[cfg(feature = "filter-dedupe-idents")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "dedupe_idents"]
+struct DedupeIdentsFilter {
+  #[parameters]
+  args: DedupeIdentsArgs,
+}
+
+#[cfg(feature = "filter-dedupe-idents")]
+impl Filter for DedupeIdentsFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let keep_last = match args.keep.as_deref() {
+      None | Some("first") => false,
+      Some("last") => true,
+      Some(keep) => return Err(unknown_value("keep", keep, &["first", "last"])),
+    };
+
+    let mut items: Vec<_> = as_sequence(input).collect();
+    if keep_last {
+      items.reverse();
+    }
+    let mut seen = std::collections::HashSet::new();
+    items.retain(|item| seen.insert(sort_key(item.to_kstr().as_str(), false)));
+    if keep_last {
+      items.reverse();
+    }
+    let output = Value::Array(items.into_iter().map(ValueView::to_value).collect());
+    #[cfg(feature = "log")]
+    log::debug!(
+      "dedupe_idents: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+#[cfg(feature = "icu")]
+#[derive(Debug, FilterParameters)]
+struct SortCollateArgs {
+  #[parameter(
+    description = "The locale whose collation rules order the items, as a BCP 47 tag. Defaults to the root collation.",
+    arg_type = "str"
+  )]
+  locale: Option<Expression>,
+}
+
+/// Sort an array of strings by the collation rules of a locale:
+/// `{{ glossary | sort_collate: "de" }}` sorts `Äpfel` next to `Apfel`
+/// instead of after `Zucker`, and `"sv"` after `Zucker`, as Swedish does.
+#[cfg(feature = "icu")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "sort_collate",
+  description = "Sort the array by the collation rules of a locale.",
+  parameters(SortCollateArgs),
+  parsed(SortCollateFilter)
+)]
+pub struct SortCollate;
+
+#[cfg(feature = "icu")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "sort_collate"]
+struct SortCollateFilter {
+  #[parameters]
+  args: SortCollateArgs,
+}
+
+#[cfg(feature = "icu")]
+impl Filter for SortCollateFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let locale = match args.locale.as_deref() {
+      Some(locale) => locale
+        .parse()
+        .map_err(|_| invalid_argument("locale", "BCP 47 language tag expected"))?,
+      None => Default::default(),
+    };
+    let collator = icu_collator::Collator::try_new(&locale, Default::default())
+      .map_err(|_| invalid_argument("locale", "No collation data for the locale"))?;
+
+    let mut items: Vec<_> = as_sequence(input)
+      .map(|item| (item.to_kstr().into_string(), item.to_value()))
+      .collect();
+    items.sort_by(|(a, _), (b, _)| collator.compare(a, b));
+    let output = Value::Array(items.into_iter().map(|(_, item)| item).collect());
+    #[cfg(feature = "log")]
+    log::debug!(
+      "sort_collate: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+  use std::cmp::Ordering;
+
+  #[test]
+  fn keys() {
+    assert_eq!(
+      sort_key("Item_010b", true),
+      [
+        KeyPart::Text("item".into()),
+        KeyPart::Number(2, "10".into()),
+        KeyPart::Text("b".into())
+      ]
+    );
+    assert_eq!(
+      sort_key("item2", true).cmp(&sort_key("item10", true)),
+      Ordering::Less
+    );
+    assert_eq!(
+      sort_key("item2", false).cmp(&sort_key("item10", false)),
+      Ordering::Greater
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-sort-ci")]
+  fn sort_ci() {
+    assert_eq!(
+      liquid_core::call_filter!(
+        SortCi,
+        liquid_core::value!(["Zebra", "apple", "user_id", "userAge"])
+      )
+      .unwrap(),
+      liquid_core::value!(["apple", "userAge", "user_id", "Zebra"])
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-sort-natural-ident")]
+  fn sort_natural_ident() {
+    assert_eq!(
+      liquid_core::call_filter!(
+        SortNaturalIdent,
+        liquid_core::value!(["item10", "Item2", "item1"])
+      )
+      .unwrap(),
+      liquid_core::value!(["item1", "Item2", "item10"])
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-group-by-prefix")]
+  fn group_by_prefix() {
+    assert_eq!(
+      liquid_core::call_filter!(
+        GroupByPrefix,
+        liquid_core::value!(["user_id", "age", "userName", "UserEmail"])
+      )
+      .unwrap(),
+      liquid_core::value!({
+        "user": ["user_id", "userName", "UserEmail"],
+        "age": ["age"],
+      })
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-dedupe-idents")]
+  fn dedupe_idents() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(DedupeIdents)
+      .build()
+      .unwrap();
+    let render = |template| {
+      let globals = liquid::object!({ "idents": ["userId", "name", "user_id", "Name", "age"] });
+      parser.parse(template)?.render(&globals)
+    };
+    assert_eq!(
+      render("{{ idents | dedupe_idents | join: ',' }}").unwrap(),
+      "userId,name,age"
+    );
+    assert_eq!(
+      render("{{ idents | dedupe_idents: keep: 'last' | join: ',' }}").unwrap(),
+      "user_id,Name,age"
+    );
+    assert!(render("{{ idents | dedupe_idents: keep: 'middle' }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "icu")]
+  fn sort_collate() {
+    let words = liquid_core::value!(["Zucker", "Äpfel", "apfel", "Birne"]);
+    assert_eq!(
+      liquid_core::call_filter!(SortCollate, words.clone(), "de").unwrap(),
+      liquid_core::value!(["apfel", "Äpfel", "Birne", "Zucker"])
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SortCollate, words.clone(), "sv").unwrap(),
+      liquid_core::value!(["apfel", "Birne", "Zucker", "Äpfel"])
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SortCollate, words.clone()).unwrap(),
+      liquid_core::value!(["apfel", "Äpfel", "Birne", "Zucker"])
+    );
+    assert!(liquid_core::call_filter!(SortCollate, words, "not a locale!").is_err());
+  }
+
+  #[test]
+  #[cfg(all(feature = "log", feature = "filter-sort-ci"))]
+  fn logs_conversions() {
+    let logs = crate::instrument::captured_logs(|| {
+      liquid_core::call_filter!(SortCi, liquid_core::value!(["b", "A"])).unwrap();
+    });
+    assert_eq!(
+      logs,
+      ["sort_ci: Array([Scalar(Str(\"b\")), Scalar(Str(\"A\"))]) \
+         -> Array([Scalar(Str(\"A\")), Scalar(Str(\"b\"))])"]
+    );
+  }
+}
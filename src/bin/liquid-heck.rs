@@ -0,0 +1,135 @@
+//! Command line conversions and template rendering with the heck filters.
+//!
+//! Only built with the `cli` feature.
+use liquid_core::parser::FilterArguments;
+use liquid_core::runtime::RuntimeBuilder;
+use liquid_core::{ParseFilter, Value, ValueView};
+use liquid_heck::{
+  liquid, register_all_filters_with_aliases, register_all_tags, FilterRegistry, HeckCase,
+  HECK_CONFIG_GLOBAL,
+};
+use std::io::{self, BufRead, Write};
+use std::{env, fs, process};
+
+const USAGE: &str = "\
+Usage:
+  liquid-heck convert <filter> [TEXT...]   Convert TEXT, or each line of stdin,
+                                           with a filter or a case, e.g. snake
+  liquid-heck render <template> [GLOBALS]  Render a template file, with globals
+                                           from a .json, .yaml or .yml file
+  liquid-heck filters                      List the filters
+
+Options:
+  -o, --option NAME=VALUE  Set a conversion option, e.g. acronyms=ID,URL or
+                           digit_boundary=true, as in the heck_config global";
+
+type Error = Box<dyn std::error::Error>;
+
+fn main() {
+  let (args, config) = match parse_options(env::args().skip(1)) {
+    Some(parsed) => parsed,
+    None => {
+      eprintln!("{USAGE}");
+      process::exit(2);
+    }
+  };
+  let result = match args.first().map(String::as_str) {
+    Some("convert") if args.len() >= 2 => convert(&args[1], &args[2..], &config),
+    Some("render") if (2..=3).contains(&args.len()) => render(&args[1], args.get(2), &config),
+    Some("filters") => filters(),
+    _ => {
+      eprintln!("{USAGE}");
+      process::exit(2);
+    }
+  };
+  if let Err(error) = result {
+    eprintln!("liquid-heck: {error}");
+    process::exit(1);
+  }
+}
+
+/// Split the `--option NAME=VALUE` flags from the other arguments. The
+/// options are returned as the fields of the `heck_config` global.
+fn parse_options(args: impl Iterator<Item = String>) -> Option<(Vec<String>, liquid::Object)> {
+  let mut args = args.peekable();
+  let mut rest = Vec::new();
+  let mut config = liquid::Object::new();
+  while let Some(arg) = args.next() {
+    if arg != "-o" && arg != "--option" {
+      rest.push(arg);
+      continue;
+    }
+    let option = args.next()?;
+    let (name, value) = option.split_once('=')?;
+    let value = match value {
+      "true" => Value::scalar(true),
+      "false" => Value::scalar(false),
+      value => Value::scalar(value.to_string()),
+    };
+    config.insert(name.to_string().into(), value);
+  }
+  Some((rest, config))
+}
+
+fn convert(name: &str, texts: &[String], config: &liquid::Object) -> Result<(), Error> {
+  let filter = FilterRegistry::get(name)
+    .or_else(|| FilterRegistry::get(name.parse::<HeckCase>().ok()?.filter_name()))
+    .ok_or_else(|| liquid_heck::UnknownFilter(name.to_string()))?;
+  let mut stdout = io::stdout().lock();
+  if texts.is_empty() {
+    for line in io::stdin().lock().lines() {
+      writeln!(stdout, "{}", apply(filter.as_ref(), &line?, config)?)?;
+    }
+  } else {
+    for text in texts {
+      writeln!(stdout, "{}", apply(filter.as_ref(), text, config)?)?;
+    }
+  }
+  Ok(())
+}
+
+fn apply(filter: &dyn ParseFilter, input: &str, config: &liquid::Object) -> Result<String, Error> {
+  let arguments = FilterArguments {
+    positional: Box::new(std::iter::empty()),
+    keyword: Box::new(std::iter::empty()),
+  };
+  let globals = liquid::object!({ HECK_CONFIG_GLOBAL: config });
+  let runtime = RuntimeBuilder::new().set_globals(&globals).build();
+  let output = filter
+    .parse(arguments)?
+    .evaluate(&Value::scalar(input.to_string()), &runtime)?;
+  Ok(output.to_kstr().into_string())
+}
+
+fn render(template: &str, globals: Option<&String>, config: &liquid::Object) -> Result<(), Error> {
+  let mut globals: liquid::Object = match globals {
+    None => liquid::Object::new(),
+    Some(path) if path.ends_with(".yaml") || path.ends_with(".yml") => {
+      serde_yaml::from_str(&fs::read_to_string(path)?)?
+    }
+    Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+  };
+  if !config.is_empty() {
+    let global = globals
+      .entry(HECK_CONFIG_GLOBAL)
+      .or_insert_with(|| Value::Object(liquid::Object::new()));
+    if let Value::Object(global) = global {
+      global.extend(config.clone());
+    }
+  }
+  let parser = register_all_tags(register_all_filters_with_aliases(
+    liquid::ParserBuilder::with_stdlib(),
+  ))
+  .build()?;
+  let template = parser.parse(&fs::read_to_string(template)?)?;
+  template.render_to(&mut io::stdout().lock(), &globals)?;
+  Ok(())
+}
+
+fn filters() -> Result<(), Error> {
+  let mut stdout = io::stdout().lock();
+  for doc in liquid_heck::filter_docs() {
+    writeln!(stdout, "{:<16} {}", doc.name, doc.description)?;
+  }
+  Ok(())
+}
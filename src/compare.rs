@@ -0,0 +1,194 @@
+//! Filters comparing identifiers regardless of their case.
+use crate::words::Words;
+use crate::Options;
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// The Unicode full case folding of `s`: `ß` and `ẞ` fold to `ss`, `ς` to
+/// `σ`, `ﬁ` to `fi`, where [`str::to_lowercase`] keeps them apart.
+fn case_fold(s: &str) -> impl Iterator<Item = char> + '_ {
+  // The lowercase first, as `ẞ` uppercases to itself but `ß` to `SS`.
+  s.chars()
+    .flat_map(char::to_lowercase)
+    .flat_map(char::to_uppercase)
+    .flat_map(char::to_lowercase)
+}
+
+/// Are `a` and `b` the same string after Unicode case folding? `Straße`
+/// matches `STRASSE` and `ΌΣΟΣ` matches `όσος`, unlike with a comparison of
+/// their lowercase. Nothing else is ignored: the separators, the whitespace
+/// and the accents must match.
+///
+/// ```
+/// assert!(liquid_heck::caseless_eq("Straße", "STRASSE"));
+/// assert!(!liquid_heck::caseless_eq("user_id", "UserId"));
+/// ```
+pub fn caseless_eq(a: &str, b: &str) -> bool {
+  case_fold(a).eq(case_fold(b))
+}
+
+/// Do `a` and `b` have the same words, ignoring their case and separators?
+pub(crate) fn same_words(a: &str, b: &str) -> bool {
+  let lowercase = |s| Words::new(s, &Options::default()).map(str::to_lowercase);
+  lowercase(a).eq(lowercase(b))
+}
+
+#[cfg(feature = "filter-case-eq")]
+#[derive(Debug, FilterParameters)]
+struct CaseEqArgs {
+  #[parameter(description = "The string to compare with.", arg_type = "str")]
+  other: Expression,
+}
+
+/// Compare two strings word by word, ignoring their case and separators:
+/// `{% assign same = "user_id" | case_eq: "UserID" %}` sets `same` to `true`.
+#[cfg(feature = "filter-case-eq")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "case_eq",
+  description = "Is the string made of the same words as the argument, whatever their case?",
+  parameters(CaseEqArgs),
+  parsed(CaseEqFilter)
+)]
+pub struct CaseEq;
+
+#[cfg(feature = "filter-case-eq")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "case_eq"]
+struct CaseEqFilter {
+  #[parameters]
+  args: CaseEqArgs,
+}
+
+#[cfg(feature = "filter-case-eq")]
+impl Filter for CaseEqFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let s = input.to_kstr();
+    let output = same_words(s.as_str(), args.other.as_str());
+    #[cfg(feature = "log")]
+    log::debug!(
+      "case_eq: {} == {} -> {output}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&args.other)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-caseless-eq")]
+#[derive(Debug, FilterParameters)]
+struct CaselessEqArgs {
+  #[parameter(description = "The string to compare with.", arg_type = "str")]
+  other: Expression,
+}
+
+/// Compare two strings after Unicode case folding, for matching user input
+/// against a canonical identifier:
+/// `{% if answer | caseless_eq: "STRASSE" %}` holds for `straße`.
+/// Unlike [`CaseEq`], the separators must match.
+#[cfg(feature = "filter-caseless-eq")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "caseless_eq",
+  description = "Is the string equal to the argument after Unicode case folding?",
+  parameters(CaselessEqArgs),
+  parsed(CaselessEqFilter)
+)]
+pub struct CaselessEq;
+
+#[cfg(feature = "filter-caseless-eq")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "caseless_eq"]
+struct CaselessEqFilter {
+  #[parameters]
+  args: CaselessEqArgs,
+}
+
+#[cfg(feature = "filter-caseless-eq")]
+impl Filter for CaselessEqFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let s = input.to_kstr();
+    let output = caseless_eq(s.as_str(), args.other.as_str());
+    #[cfg(feature = "log")]
+    log::debug!(
+      "caseless_eq: {} == {} -> {output}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&args.other)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn words() {
+    assert!(same_words("user_id", "UserID"));
+    assert!(same_words("user-id", "  userId "));
+    assert!(same_words("", "__"));
+    assert!(!same_words("user_id", "userid"));
+    assert!(!same_words("user_id", "user_id_2"));
+  }
+
+  #[test]
+  #[cfg(feature = "filter-case-eq")]
+  fn case_eq() {
+    assert_eq!(
+      liquid_core::call_filter!(CaseEq, "user_id", "UserID").unwrap(),
+      liquid_core::value!(true)
+    );
+    assert_eq!(
+      liquid_core::call_filter!(CaseEq, "user_id", "user_name").unwrap(),
+      liquid_core::value!(false)
+    );
+  }
+
+  #[test]
+  fn case_folding() {
+    assert!(caseless_eq("Straße", "STRASSE"));
+    assert!(caseless_eq("STRAẞE", "strasse"));
+    assert!(caseless_eq("ΌΣΟΣ", "όσος"));
+    assert!(caseless_eq("ﬁle", "FILE"));
+    assert!(caseless_eq("", ""));
+    assert!(!caseless_eq("Straße", "strase"));
+    assert!(!caseless_eq("user_id", "userId"));
+    assert!(!caseless_eq("café", "cafe"));
+  }
+
+  #[test]
+  #[cfg(feature = "filter-caseless-eq")]
+  fn caseless_eq_filter() {
+    assert_eq!(
+      liquid_core::call_filter!(CaselessEq, "straße", "STRASSE").unwrap(),
+      liquid_core::value!(true)
+    );
+    assert_eq!(
+      liquid_core::call_filter!(CaselessEq, "user_id", "USER-ID").unwrap(),
+      liquid_core::value!(false)
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(CaselessEq)
+      .build()
+      .unwrap()
+      .parse("{% assign ok = answer | caseless_eq: 'FLuß' %}{% if ok %}yes{% else %}no{% endif %}")
+      .unwrap();
+    let globals = liquid::object!({ "answer": "FLUSS" });
+    assert_eq!(template.render(&globals).unwrap(), "yes");
+  }
+}
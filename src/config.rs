@@ -0,0 +1,209 @@
+//! Filter registration driven by configuration.
+use crate::{ConfiguredCase, FilterRegistry, Options, UnknownFilter};
+use liquid_core::parser::{FilterArguments, ParameterReflection};
+use liquid_core::{Filter, FilterReflection, ParseFilter, Result};
+use std::fmt;
+
+/// Which filters to register, under which names and with which options.
+///
+/// With the `serde` feature, it can be loaded from any serde format:
+///
+/// ```toml
+/// [[filters]]
+/// name = "snakecase"
+///
+/// [[filters]]
+/// name = "uppercamelcase"
+/// alias = "type_name"
+/// options = { acronyms = ["ID", "URL"], digit_boundary = true }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Config {
+  /// The filters to register.
+  pub filters: Vec<FilterConfig>,
+}
+
+/// One filter of a [`Config`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct FilterConfig {
+  /// The filter name, as in [`FilterRegistry::names`].
+  pub name: String,
+  /// The name used in templates. Defaults to `name`.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub alias: Option<String>,
+  /// The conversion options. Only the case filters take them, the other
+  /// filters ignore them.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub options: Options,
+}
+
+/// A set of configured filters, ready to be registered.
+///
+/// ```
+/// # #[cfg(feature = "filter-snake")]
+/// # {
+/// use liquid_heck::{Config, FilterConfig, FilterSet};
+///
+/// let config = Config {
+///     filters: vec![FilterConfig {
+///         name: "snakecase".to_string(),
+///         alias: Some("column".to_string()),
+///         ..Default::default()
+///     }],
+/// };
+/// let mut builder = liquid::ParserBuilder::with_stdlib();
+/// for filter in FilterSet::from_config(&config).unwrap() {
+///     builder = builder.filter(filter);
+/// }
+/// let template = builder.build().unwrap().parse("{{ 'UserId' | column }}").unwrap();
+/// assert_eq!(template.render(&liquid::object!({})).unwrap(), "user_id");
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FilterSet {
+  filters: Vec<Box<dyn ParseFilter>>,
+}
+
+impl FilterSet {
+  /// Build the filters listed in `config`.
+  ///
+  /// Any filter known to [`FilterRegistry`] can be listed. Fails on the first
+  /// filter name unknown to it.
+  pub fn from_config(config: &Config) -> std::result::Result<FilterSet, UnknownFilter> {
+    let filters = config
+      .filters
+      .iter()
+      .map(|filter| {
+        let name = filter.alias.as_ref().unwrap_or(&filter.name);
+        if let Some(case) = FilterRegistry::case(&filter.name) {
+          let filter = ConfiguredCase::new(name.clone(), case, Some(filter.options.clone()));
+          return Ok(Box::new(filter) as Box<dyn ParseFilter>);
+        }
+        let known =
+          FilterRegistry::get(&filter.name).ok_or_else(|| UnknownFilter(filter.name.clone()))?;
+        Ok(match &filter.alias {
+          Some(alias) => Box::new(Renamed {
+            name: alias.clone(),
+            filter: known,
+          }),
+          None => known,
+        })
+      })
+      .collect::<std::result::Result<_, _>>()?;
+    Ok(FilterSet { filters })
+  }
+
+  /// The configured filters.
+  pub fn filters(&self) -> &[Box<dyn ParseFilter>] {
+    &self.filters
+  }
+}
+
+impl fmt::Debug for FilterSet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list()
+      .entries(self.filters.iter().map(|filter| filter.reflection().name()))
+      .finish()
+  }
+}
+
+impl IntoIterator for FilterSet {
+  type Item = Box<dyn ParseFilter>;
+  type IntoIter = std::vec::IntoIter<Box<dyn ParseFilter>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.filters.into_iter()
+  }
+}
+
+/// A filter registered under the alias of its configuration.
+#[derive(Clone)]
+struct Renamed {
+  name: String,
+  filter: Box<dyn ParseFilter>,
+}
+
+impl FilterReflection for Renamed {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn description(&self) -> &str {
+    self.filter.reflection().description()
+  }
+
+  fn positional_parameters(&self) -> &'static [ParameterReflection] {
+    self.filter.reflection().positional_parameters()
+  }
+
+  fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+    self.filter.reflection().keyword_parameters()
+  }
+}
+
+impl ParseFilter for Renamed {
+  fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+    self.filter.parse(arguments)
+  }
+
+  fn reflection(&self) -> &dyn FilterReflection {
+    self
+  }
+}
+
+#[cfg(all(test, feature = "serde", feature = "all-filters"))]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn from_config() {
+    let config: Config = serde_json::from_str(
+      r#"{ "filters": [
+        { "name": "snakecase" },
+        { "name": "uppercamelcase", "alias": "type_name", "options": { "acronyms": ["id"] } }
+      ] }"#,
+    )
+    .unwrap();
+    let set = FilterSet::from_config(&config).unwrap();
+    let names: Vec<_> = set
+      .filters()
+      .iter()
+      .map(|f| f.reflection().name())
+      .collect();
+    assert_eq!(names, ["snakecase", "type_name"]);
+    assert_eq!(
+      liquid_core::call_filter!(*set.filters()[1], "user_id").unwrap(),
+      liquid_core::value!("UserID")
+    );
+
+    let config: Config = serde_json::from_str(
+      r#"{ "filters": [{ "name": "acronymize" }, { "name": "i18n_key", "alias": "key" }] }"#,
+    )
+    .unwrap();
+    let set = FilterSet::from_config(&config).unwrap();
+    let names: Vec<_> = set
+      .filters()
+      .iter()
+      .map(|f| f.reflection().name())
+      .collect();
+    assert_eq!(names, ["acronymize", "key"]);
+    assert_eq!(
+      liquid_core::call_filter!(*set.filters()[1], "Sign In").unwrap(),
+      liquid_core::call_filter!(crate::targets::I18nKey, "Sign In").unwrap()
+    );
+
+    let config = Config {
+      filters: vec![FilterConfig {
+        name: "nocase".to_string(),
+        ..Default::default()
+      }],
+    };
+    assert_eq!(
+      FilterSet::from_config(&config).unwrap_err(),
+      UnknownFilter("nocase".to_string())
+    );
+  }
+}
@@ -0,0 +1,1333 @@
+//! Case conversion functions.
+//!
+//! These are the conversions behind the Liquid filters. They only need
+//! `core` and `alloc` and stay available without the `std` feature, except
+//! [`to_title_case_stream`].
+//!
+//! The words are split like Heck 0.4 does: only ASCII letters and digits
+//! make words, any other character separates them. With the `heck05`
+//! feature, they are split like Heck 0.5 does instead: any Unicode letter or
+//! digit makes words.
+//!
+//! ```
+//! use liquid_heck::conversion::to_snake_case;
+//!
+//! assert_eq!(to_snake_case("Some text to convert"), "some_text_to_convert");
+//! ```
+//!
+//! The `_with` variants, and [`convert`] to a [`HeckCase`] chosen at
+//! runtime, honor [`Options`]:
+//!
+//! ```
+//! use liquid_heck::{conversion::to_upper_camel_case_with, Options};
+//!
+//! let options = Options::builder().acronyms(["ID"]).build();
+//! assert_eq!(to_upper_camel_case_with("user_id", &options), "UserID");
+//! ```
+use crate::{
+  words::{is_cjk, is_word_separator, opaque_len, split_after_words, version_len, Words},
+  AcronymStyle, ArticleStyle, CjkStyle, Lang, Options, TokenStyle, UnitStyle,
+};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+use heck::{
+  ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase,
+  ToTrainCase, ToUpperCamelCase,
+};
+
+/// `s` as Heck 0.4 splits it into words, for Heck 0.5: the non-ASCII
+/// characters turned into separators. Heck 0.5 splits on the same
+/// characters as Heck 0.4 for ASCII input, and converts it the same way.
+#[cfg(not(feature = "heck05"))]
+fn heck_input(s: &str) -> Cow<'_, str> {
+  match s.is_ascii() {
+    true => Cow::Borrowed(s),
+    false => Cow::Owned(
+      s.chars()
+        .map(|c| if c.is_ascii() { c } else { ' ' })
+        .collect(),
+    ),
+  }
+}
+
+/// `s` as Heck 0.5 splits it into words.
+#[cfg(feature = "heck05")]
+fn heck_input(s: &str) -> Cow<'_, str> {
+  Cow::Borrowed(s)
+}
+
+/// Convert the string to UpperCamelCase.
+pub fn to_upper_camel_case(s: &str) -> String {
+  heck_input(s).to_upper_camel_case()
+}
+
+/// Convert the string to lowerCamelCase.
+pub fn to_lower_camel_case(s: &str) -> String {
+  heck_input(s).to_lower_camel_case()
+}
+
+/// Convert the string to snake_case.
+pub fn to_snake_case(s: &str) -> String {
+  heck_input(s).to_snake_case()
+}
+
+/// Convert the string to kebab-case.
+pub fn to_kebab_case(s: &str) -> String {
+  heck_input(s).to_kebab_case()
+}
+
+/// Convert the string to SHOUTY_SNAKE_CASE.
+pub fn to_shouty_snake_case(s: &str) -> String {
+  heck_input(s).to_shouty_snake_case()
+}
+
+/// Convert the string to Title Case.
+pub fn to_title_case(s: &str) -> String {
+  heck_input(s).to_title_case()
+}
+
+/// Convert the string to SHOUTY-KEBAB-CASE.
+pub fn to_shouty_kebab_case(s: &str) -> String {
+  heck_input(s).to_shouty_kebab_case()
+}
+
+/// Convert the string to Train-Case.
+pub fn to_train_case(s: &str) -> String {
+  heck_input(s).to_train_case()
+}
+
+/// Convert the string to UpperCamelCase, honoring `options`.
+pub fn to_upper_camel_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::UpperCamel, s, options)
+}
+
+/// Convert the string to lowerCamelCase, honoring `options`.
+pub fn to_lower_camel_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::LowerCamel, s, options)
+}
+
+/// Convert the string to snake_case, honoring `options`.
+pub fn to_snake_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::Snake, s, options)
+}
+
+/// Convert the string to kebab-case, honoring `options`.
+pub fn to_kebab_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::Kebab, s, options)
+}
+
+/// Convert the string to SHOUTY_SNAKE_CASE, honoring `options`.
+pub fn to_shouty_snake_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::ShoutySnake, s, options)
+}
+
+/// Convert the string to Title Case, honoring `options`.
+pub fn to_title_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::Title, s, options)
+}
+
+/// Convert the string to SHOUTY-KEBAB-CASE, honoring `options`.
+pub fn to_shouty_kebab_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::ShoutyKebab, s, options)
+}
+
+/// Convert the string to Train-Case, honoring `options`.
+pub fn to_train_case_with(s: &str, options: &Options) -> String {
+  convert(HeckCase::Train, s, options)
+}
+
+/// Convert a document read from `reader` to Title Case, honoring `options`,
+/// and write it to `writer`.
+///
+/// The document is converted chunk by chunk, so the memory used stays
+/// bounded whatever its length. The output is the one of
+/// [`to_title_case_with`].
+///
+/// ```
+/// use liquid_heck::{conversion::to_title_case_stream, Options};
+///
+/// let mut output = Vec::new();
+/// to_title_case_stream("the great gatsby".as_bytes(), &mut output, &Options::default()).unwrap();
+/// assert_eq!(output, b"The Great Gatsby");
+/// ```
+#[cfg(feature = "std")]
+pub fn to_title_case_stream(
+  reader: impl std::io::BufRead,
+  writer: impl std::io::Write,
+  options: &Options,
+) -> std::io::Result<()> {
+  convert_stream(HeckCase::Title, reader, writer, options, CHUNK_LENGTH)
+}
+
+/// A case the filters convert to.
+///
+/// Its [`FromStr`] implementation is the one source of
+/// the case names: the filter names and their aliases (`snakecase`,
+/// `snake_case`, `snake-case`), and the short style names (`snake`, `camel`,
+/// `pascal`, ...) of the tag and filter arguments. [`Display`](fmt::Display)
+/// and serde use the [`name`](Self::name).
+///
+/// ```
+/// use liquid_heck::HeckCase;
+///
+/// let case: HeckCase = "snake-case".parse().unwrap();
+/// assert_eq!(case, HeckCase::Snake);
+/// assert_eq!(case.to_string(), "snake");
+/// assert_eq!(case.filter_name(), "snakecase");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HeckCase {
+  /// `kebab-case`
+  Kebab,
+  /// `lowerCamelCase`
+  LowerCamel,
+  /// `SHOUTY-KEBAB-CASE`
+  ShoutyKebab,
+  /// `SHOUTY_SNAKE_CASE`
+  ShoutySnake,
+  /// `snake_case`
+  Snake,
+  /// `Title Case`
+  Title,
+  /// `Train-Case`
+  Train,
+  /// `UpperCamelCase`
+  UpperCamel,
+}
+
+/// How a word is cased in the output.
+#[derive(Clone, Copy, PartialEq)]
+enum WordCase {
+  Lower,
+  Upper,
+  Capitalized,
+}
+
+/// Error for a case name unknown to [`HeckCase`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownCase(pub String);
+
+impl fmt::Display for UnknownCase {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "unknown case `{}`, expected one of: ", self.0)?;
+    for (i, case) in HeckCase::ALL.iter().enumerate() {
+      let separator = if i == 0 { "" } else { ", " };
+      write!(f, "{separator}{case}")?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownCase {}
+
+impl HeckCase {
+  /// All the cases.
+  pub const ALL: [HeckCase; 8] = [
+    HeckCase::Kebab,
+    HeckCase::LowerCamel,
+    HeckCase::ShoutyKebab,
+    HeckCase::ShoutySnake,
+    HeckCase::Snake,
+    HeckCase::Title,
+    HeckCase::Train,
+    HeckCase::UpperCamel,
+  ];
+
+  /// The canonical short name of the case, e.g. `snake` or `upper_camel`.
+  pub fn name(self) -> &'static str {
+    match self {
+      HeckCase::Kebab => "kebab",
+      HeckCase::LowerCamel => "lower_camel",
+      HeckCase::ShoutyKebab => "shouty_kebab",
+      HeckCase::ShoutySnake => "shouty_snake",
+      HeckCase::Snake => "snake",
+      HeckCase::Title => "title",
+      HeckCase::Train => "train",
+      HeckCase::UpperCamel => "upper_camel",
+    }
+  }
+
+  /// The name of the filter converting to this case, e.g. `snakecase`.
+  pub fn filter_name(self) -> &'static str {
+    match self {
+      HeckCase::Kebab => "kebabcase",
+      HeckCase::LowerCamel => "lowercamelcase",
+      HeckCase::ShoutyKebab => "shoutykebabcase",
+      HeckCase::ShoutySnake => "shoutysnakecase",
+      HeckCase::Snake => "snakecase",
+      HeckCase::Title => "titlecase",
+      HeckCase::Train => "traincase",
+      HeckCase::UpperCamel => "uppercamelcase",
+    }
+  }
+
+  /// The underscore and hyphen spellings of the filter name, e.g.
+  /// `snake_case` and `snake-case`.
+  pub fn aliases(self) -> [&'static str; 2] {
+    match self {
+      HeckCase::Kebab => ["kebab_case", "kebab-case"],
+      HeckCase::LowerCamel => ["lower_camel_case", "lower-camel-case"],
+      HeckCase::ShoutyKebab => ["shouty_kebab_case", "shouty-kebab-case"],
+      HeckCase::ShoutySnake => ["shouty_snake_case", "shouty-snake-case"],
+      HeckCase::Snake => ["snake_case", "snake-case"],
+      HeckCase::Title => ["title_case", "title-case"],
+      HeckCase::Train => ["train_case", "train-case"],
+      HeckCase::UpperCamel => ["upper_camel_case", "upper-camel-case"],
+    }
+  }
+
+  /// The case of the filter registered as `name`, or one of its aliases.
+  pub(crate) fn from_filter_name(name: &str) -> Option<HeckCase> {
+    HeckCase::ALL
+      .into_iter()
+      .find(|case| case.filter_name() == name || case.aliases().contains(&name))
+  }
+
+  /// The case named by its short style name, e.g. `camel` or `snake`.
+  fn from_style_name(name: &str) -> Option<HeckCase> {
+    Some(match name {
+      "camel" => HeckCase::LowerCamel,
+      "screaming_snake" => HeckCase::ShoutySnake,
+      "pascal" => HeckCase::UpperCamel,
+      name => return HeckCase::ALL.into_iter().find(|case| case.name() == name),
+    })
+  }
+
+  /// The description of the filter converting to this case.
+  #[cfg(feature = "std")]
+  pub(crate) fn description(self) -> &'static str {
+    match self {
+      HeckCase::Kebab => "Convert the string to kebab-case.",
+      HeckCase::LowerCamel => "Convert the string to lowerCamelCase.",
+      HeckCase::ShoutyKebab => "Convert the string to SHOUTY-KEBAB-CASE.",
+      HeckCase::ShoutySnake => "Convert the string to SHOUTY_SNAKE_CASE.",
+      HeckCase::Snake => "Convert the string to snake-case.",
+      HeckCase::Title => "Convert the string to title case.",
+      HeckCase::Train => "Convert the string to Train-Case.",
+      HeckCase::UpperCamel => "Convert the string to UpperCamelCase.",
+    }
+  }
+
+  fn separator(self) -> &'static str {
+    match self {
+      HeckCase::Kebab | HeckCase::ShoutyKebab | HeckCase::Train => "-",
+      HeckCase::Snake | HeckCase::ShoutySnake => "_",
+      HeckCase::Title => " ",
+      HeckCase::LowerCamel | HeckCase::UpperCamel => "",
+    }
+  }
+
+  fn word_case(self, first: bool) -> WordCase {
+    match self {
+      HeckCase::Kebab | HeckCase::Snake => WordCase::Lower,
+      HeckCase::LowerCamel if first => WordCase::Lower,
+      HeckCase::ShoutyKebab | HeckCase::ShoutySnake => WordCase::Upper,
+      HeckCase::LowerCamel | HeckCase::Title | HeckCase::Train | HeckCase::UpperCamel => {
+        WordCase::Capitalized
+      }
+    }
+  }
+}
+
+impl FromStr for HeckCase {
+  type Err = UnknownCase;
+
+  fn from_str(s: &str) -> Result<HeckCase, UnknownCase> {
+    HeckCase::from_filter_name(s)
+      .or_else(|| HeckCase::from_style_name(s))
+      .ok_or_else(|| UnknownCase(String::from(s)))
+  }
+}
+
+impl fmt::Display for HeckCase {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+/// Convert `s` to `case`, honoring `options`: the very conversion of the
+/// filter of `case` in templates, for a case chosen at runtime.
+///
+/// ```
+/// use liquid_heck::{convert, HeckCase, Options};
+///
+/// let case: HeckCase = "kebab".parse().unwrap();
+/// let options = Options::builder().digit_boundary(true).build();
+/// assert_eq!(convert(case, "userID2Name", &options), "user-id-2-name");
+/// ```
+pub fn convert(case: HeckCase, s: &str, options: &Options) -> String {
+  convert_prepared(case, &options.preprocess(s), options)
+}
+
+/// [`convert`] for an `s` already preprocessed by [`Options::preprocess`],
+/// as the filters get it from `prepare_input`.
+pub(crate) fn convert_prepared(case: HeckCase, s: &str, options: &Options) -> String {
+  if case == HeckCase::Title && options.leading_article != ArticleStyle::Keep {
+    return convert_article(s, options);
+  }
+  if s.len() > CHUNKED_THRESHOLD {
+    return convert_chunked(case, s, options, CHUNK_LENGTH);
+  }
+  if options.is_heck() {
+    return match case {
+      HeckCase::Kebab => to_kebab_case(s),
+      HeckCase::LowerCamel => to_lower_camel_case(s),
+      HeckCase::ShoutyKebab => to_shouty_kebab_case(s),
+      HeckCase::ShoutySnake => to_shouty_snake_case(s),
+      HeckCase::Snake => to_snake_case(s),
+      HeckCase::Title => to_title_case(s),
+      HeckCase::Train => to_train_case(s),
+      HeckCase::UpperCamel => to_upper_camel_case(s),
+    };
+  }
+  convert_words(case, s, options)
+}
+
+/// Title Case dropping or moving the leading article of `s`, as set by
+/// [`Options::leading_article`]. A lone article is kept.
+fn convert_article(s: &str, options: &Options) -> String {
+  let keep = Options {
+    leading_article: ArticleStyle::Keep,
+    ..options.clone()
+  };
+  let mut words = Words::new(s, options);
+  let is_article = words.next().is_some_and(|word| {
+    ["the", "a", "an"]
+      .iter()
+      .any(|a| word.eq_ignore_ascii_case(a))
+  });
+  if !is_article || words.next().is_none() {
+    return convert_prepared(HeckCase::Title, s, &keep);
+  }
+  let (article, rest) = split_after_words(s, 1, options);
+  let mut output = convert_prepared(HeckCase::Title, rest, &keep);
+  if options.leading_article == ArticleStyle::Move {
+    output.push_str(", ");
+    output.push_str(&convert_prepared(HeckCase::Title, article, &keep));
+  }
+  output
+}
+
+/// Inputs longer than this are converted chunk by chunk.
+const CHUNKED_THRESHOLD: usize = 64 * 1024;
+
+/// The minimum length of the chunks.
+const CHUNK_LENGTH: usize = 16 * 1024;
+
+/// Converts a string chunk by chunk. The chunks end where a run of
+/// separators starts, so no word nor run of underscores spans two chunks.
+struct Chunker<'a> {
+  case: HeckCase,
+  options: &'a Options,
+  /// The options of the words, whose underscores are not at the ends.
+  words: Options,
+  first: bool,
+}
+
+impl<'a> Chunker<'a> {
+  fn new(case: HeckCase, options: &'a Options) -> Self {
+    Chunker {
+      case,
+      options,
+      words: Options {
+        preserve_underscores: false,
+        ..options.clone()
+      },
+      first: true,
+    }
+  }
+
+  /// Push the conversion of the next chunk, the last one when `last`.
+  fn push(&mut self, output: &mut String, mut chunk: &str, last: bool) {
+    let preserve = self.options.preserve_underscores;
+    if preserve && self.first {
+      let trimmed = chunk.trim_start_matches('_');
+      output.push_str(&chunk[..chunk.len() - trimmed.len()]);
+      chunk = trimmed;
+    }
+    let mut suffix = "";
+    if preserve && last {
+      let trimmed = chunk.trim_end_matches('_');
+      suffix = &chunk[trimmed.len()..];
+      chunk = trimmed;
+    }
+    let words = convert_words_from(self.case, chunk, &self.words, self.first);
+    if !self.first && !words.is_empty() {
+      output.push_str(self.case.separator());
+    }
+    output.push_str(&words);
+    output.push_str(suffix);
+    self.first = false;
+  }
+}
+
+/// The end of the first chunk of `s` at least `min` bytes long: the start of
+/// the first run of separators following a word.
+fn chunk_end(s: &str, min: usize, options: &Options) -> Option<usize> {
+  let mut start = min.min(s.len());
+  while !s.is_char_boundary(start) {
+    start -= 1;
+  }
+  let mut after_word = s[..start]
+    .chars()
+    .next_back()
+    .is_some_and(|c| !is_word_separator(c, options));
+  for (i, c) in s[start..].char_indices() {
+    let separator = is_word_separator(c, options);
+    // The elisions keep their apostrophe, the version strings and the opaque
+    // tokens their punctuation.
+    let verbatim =
+      options.version_strings || options.opaque_tokens || options.units == UnitStyle::Keep;
+    if separator && after_word && !is_apostrophe(c) && (!verbatim || c.is_whitespace()) {
+      return Some(start + i);
+    }
+    after_word = !separator;
+  }
+  None
+}
+
+fn convert_chunked(case: HeckCase, s: &str, options: &Options, chunk_length: usize) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut chunker = Chunker::new(case, options);
+  let mut rest = s;
+  while let Some(end) = chunk_end(rest, chunk_length, options) {
+    let (chunk, tail) = rest.split_at(end);
+    chunker.push(&mut output, chunk, false);
+    rest = tail;
+  }
+  chunker.push(&mut output, rest, true);
+  output
+}
+
+#[cfg(feature = "std")]
+fn convert_stream(
+  case: HeckCase,
+  mut reader: impl std::io::BufRead,
+  mut writer: impl std::io::Write,
+  options: &Options,
+  chunk_length: usize,
+) -> std::io::Result<()> {
+  use std::io::{Error, ErrorKind};
+
+  let mut chunker = Chunker::new(case, options);
+  let mut pending = alloc::vec::Vec::new();
+  let mut output = String::new();
+  loop {
+    let buffer = reader.fill_buf()?;
+    if buffer.is_empty() {
+      break;
+    }
+    pending.extend_from_slice(buffer);
+    let read = buffer.len();
+    reader.consume(read);
+    // A character may be cut at the end of the buffer.
+    let valid = match core::str::from_utf8(&pending) {
+      Ok(text) => text.len(),
+      Err(error) if error.error_len().is_none() => error.valid_up_to(),
+      Err(error) => return Err(Error::new(ErrorKind::InvalidData, error)),
+    };
+    let mut rest = core::str::from_utf8(&pending[..valid]).expect("valid UTF-8");
+    while let Some(end) = chunk_end(rest, chunk_length, options) {
+      let (chunk, tail) = rest.split_at(end);
+      chunker.push(&mut output, chunk, false);
+      writer.write_all(output.as_bytes())?;
+      output.clear();
+      rest = tail;
+    }
+    let converted = valid - rest.len();
+    pending.drain(..converted);
+  }
+  let rest =
+    core::str::from_utf8(&pending).map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+  chunker.push(&mut output, rest, true);
+  writer.write_all(output.as_bytes())?;
+  writer.flush()
+}
+
+fn convert_words(case: HeckCase, s: &str, options: &Options) -> String {
+  convert_words_from(case, s, options, true)
+}
+
+/// Convert the words of `s`, the first word of the output when
+/// `first_word`.
+fn convert_words_from(case: HeckCase, s: &str, options: &Options, first_word: bool) -> String {
+  let mut output = String::with_capacity(s.len());
+  let (prefix, s, suffix) = if options.preserve_underscores {
+    let trimmed = s.trim_start_matches('_');
+    let prefix = &s[..s.len() - trimmed.len()];
+    let inner = trimmed.trim_end_matches('_');
+    (prefix, inner, &trimmed[inner.len()..])
+  } else {
+    ("", s, "")
+  };
+  output.push_str(prefix);
+  let lang = match case {
+    HeckCase::Title => options.lang,
+    _ => Lang::None,
+  };
+  let mut elided = false;
+  for (n, word) in Words::new(s, options).enumerate() {
+    let first = first_word && n == 0;
+    if n > 0 && !elided {
+      output.push_str(case.separator());
+    }
+    elided = false;
+    if lang != Lang::None {
+      let end = word.as_ptr() as usize - s.as_ptr() as usize + word.len();
+      let mut next = s[end..].chars();
+      if let (Some(apostrophe), Some(letter)) = (next.next(), next.next()) {
+        if is_apostrophe(apostrophe) && letter.is_alphanumeric() && lang.is_elision(word) {
+          elided = true;
+          match first {
+            true => push_capitalized(&mut output, word),
+            false => push_lowercase(&mut output, word),
+          }
+          output.push(apostrophe);
+          continue;
+        }
+      }
+      if !first && lang.is_particle(word) {
+        push_lowercase(&mut output, word);
+        continue;
+      }
+      // The German nouns keep their capital, the other words their lowercase.
+      if !first && lang == Lang::De {
+        output.push_str(word);
+        continue;
+      }
+    }
+    if options.cjk == CjkStyle::Preserve && word.starts_with(|c| is_cjk(c, true))
+      || options.version_strings && version_len(word) == Some(word.len())
+      || options.opaque_tokens && opaque_len(word) == Some(word.len())
+    {
+      output.push_str(word);
+      continue;
+    }
+    let word_case = case.word_case(first);
+    let camel = word_case == WordCase::Capitalized || case == HeckCase::LowerCamel;
+    let token_style = match word_case {
+      WordCase::Capitalized if is_roman_numeral(word) => options.roman_numerals,
+      WordCase::Capitalized if is_version_token(word) => options.version_tokens,
+      _ => TokenStyle::Convert,
+    };
+    match word_case {
+      _ if token_style == TokenStyle::Preserve => output.push_str(word),
+      _ if token_style == TokenStyle::Upper => push_uppercase_with(&mut output, word, options),
+      _ if camel && options.acronym_style == AcronymStyle::Preserve && is_uppercase(word) => {
+        output.push_str(word)
+      }
+      WordCase::Capitalized
+        if options.acronym_style == AcronymStyle::Upper && options.is_acronym(word) =>
+      {
+        push_uppercase_with(&mut output, word, options)
+      }
+      WordCase::Capitalized => push_capitalized(&mut output, word),
+      WordCase::Lower => push_lowercase(&mut output, word),
+      WordCase::Upper => push_uppercase_with(&mut output, word, options),
+    }
+  }
+  output.push_str(suffix);
+  output
+}
+
+fn is_apostrophe(c: char) -> bool {
+  matches!(c, '\'' | '’')
+}
+
+impl Lang {
+  /// Is `word` a particle, kept lowercase in Title Case?
+  fn is_particle(self, word: &str) -> bool {
+    let particles: &[&str] = match self {
+      Lang::None | Lang::De => &[],
+      Lang::Fr => &[
+        "à", "au", "aux", "avec", "dans", "de", "des", "du", "en", "et", "la", "le", "les", "ou",
+        "par", "pour", "sur", "un", "une",
+      ],
+      Lang::Es => &[
+        "a", "al", "con", "de", "del", "e", "el", "en", "la", "las", "los", "o", "para", "por",
+        "sin", "u", "un", "una", "y",
+      ],
+    };
+    particles
+      .iter()
+      .any(|particle| eq_lowercase(word, particle))
+  }
+
+  /// Is `word` elided before an apostrophe, like `l` in `l'été`?
+  fn is_elision(self, word: &str) -> bool {
+    let elisions: &[&str] = match self {
+      Lang::Fr => &["c", "d", "j", "l", "m", "n", "qu", "s", "t"],
+      Lang::None | Lang::Es | Lang::De => &[],
+    };
+    elisions.iter().any(|elision| eq_lowercase(word, elision))
+  }
+}
+
+/// Is `word` equal to `lowercase` ignoring its case?
+fn eq_lowercase(word: &str, lowercase: &str) -> bool {
+  word
+    .chars()
+    .flat_map(char::to_lowercase)
+    .eq(lowercase.chars())
+}
+
+/// Is `word` written in uppercase, like an acronym? Single letters are not.
+fn is_uppercase(word: &str) -> bool {
+  word.chars().filter(|c| c.is_uppercase()).nth(1).is_some() && !word.chars().any(char::is_lowercase)
+}
+
+/// Is `word` a Roman numeral, from 1 (`i`) to 3999 (`mmmcmxcix`), in any
+/// case?
+fn is_roman_numeral(word: &str) -> bool {
+  fn strip_ones<'a>(mut rest: &'a str, one: &str) -> &'a str {
+    for _ in 0..3 {
+      match rest.strip_prefix(one) {
+        Some(tail) => rest = tail,
+        None => break,
+      }
+    }
+    rest
+  }
+
+  let word = word.to_ascii_lowercase();
+  let mut rest = strip_ones(&word, "m");
+  // Each power of ten is written with its one, five and ten numerals.
+  for (one, five, ten) in [("c", "d", "m"), ("x", "l", "c"), ("i", "v", "x")] {
+    rest = if let Some(tail) = rest.strip_prefix(one).and_then(|r| r.strip_prefix(ten)) {
+      tail
+    } else if let Some(tail) = rest.strip_prefix(one).and_then(|r| r.strip_prefix(five)) {
+      tail
+    } else {
+      strip_ones(rest.strip_prefix(five).unwrap_or(rest), one)
+    };
+  }
+  !word.is_empty() && rest.is_empty()
+}
+
+/// Is `word` a version token, a `v` followed by digits: `v2`, `V10`?
+fn is_version_token(word: &str) -> bool {
+  word
+    .strip_prefix(['v', 'V'])
+    .is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn push_lowercase(output: &mut String, word: &str) {
+  let mut chars = word.chars().peekable();
+  while let Some(c) = chars.next() {
+    // Final sigma, like Heck.
+    if c == 'Σ' && chars.peek().is_none() {
+      output.push('ς');
+    } else {
+      output.extend(c.to_lowercase());
+    }
+  }
+}
+
+fn push_uppercase(output: &mut String, word: &str) {
+  output.extend(word.chars().flat_map(char::to_uppercase));
+}
+
+/// Uppercase `word`, the German `ß` as `ẞ` with [`Lang::De`] unless
+/// [`Options::eszett_as_ss`].
+fn push_uppercase_with(output: &mut String, word: &str, options: &Options) {
+  if options.lang != Lang::De || options.eszett_as_ss {
+    return push_uppercase(output, word);
+  }
+  for c in word.chars() {
+    match c {
+      'ß' => output.push('ẞ'),
+      c => output.extend(c.to_uppercase()),
+    }
+  }
+}
+
+fn push_capitalized(output: &mut String, word: &str) {
+  let mut chars = word.chars();
+  if let Some(c) = chars.next() {
+    output.extend(c.to_uppercase());
+    push_lowercase(output, chars.as_str());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::{string::ToString, vec};
+  use pretty_assertions::assert_eq;
+
+  const CASES: [HeckCase; 8] = HeckCase::ALL;
+
+  #[test]
+  fn words_match_heck() {
+    let inputs = [
+      "",
+      "abc",
+      "hello world 21",
+      "hello_world_21",
+      "HelloWorld21",
+      "XMLHttpRequest",
+      "  --leading and trailing__ ",
+      "ΣΑΣ σας",
+      "ǅungla café",
+      "ABC123def456GHI",
+    ];
+    for case in CASES {
+      for input in inputs {
+        assert_eq!(
+          convert_words(case, input, &Options::default()),
+          convert(case, input, &Options::default()),
+          "{case:?} {input:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn options() {
+    let options = Options {
+      acronyms: vec!["id".to_string(), "URL".to_string()],
+      digit_boundary: true,
+      ..Options::default()
+    };
+    assert_eq!(convert(HeckCase::UpperCamel, "user_id", &options), "UserID");
+    assert_eq!(convert(HeckCase::LowerCamel, "id_url", &options), "idURL");
+    assert_eq!(convert(HeckCase::Snake, "userID2", &options), "user_id_2");
+    assert_eq!(convert(HeckCase::Title, "base url", &options), "Base URL");
+
+    let options = Options {
+      acronyms: vec!["xml".to_string(), "http".to_string()],
+      ..Options::default()
+    };
+    assert_eq!(
+      convert(HeckCase::LowerCamel, "XMLHttpRequest", &options),
+      "xmlHTTPRequest"
+    );
+    let options = Options {
+      acronym_style: AcronymStyle::Lower,
+      ..options
+    };
+    assert_eq!(
+      convert(HeckCase::LowerCamel, "XMLHttpRequest", &options),
+      "xmlHttpRequest"
+    );
+    let options = Options {
+      acronym_style: AcronymStyle::Preserve,
+      ..Options::default()
+    };
+    assert_eq!(
+      convert(HeckCase::LowerCamel, "XMLHttpRequest", &options),
+      "XMLHttpRequest"
+    );
+    assert_eq!(
+      convert(HeckCase::UpperCamel, "get_URL_v2", &options),
+      "GetURLV2"
+    );
+    assert_eq!(
+      convert(HeckCase::Snake, "XMLHttpRequest", &options),
+      "xml_http_request"
+    );
+
+    let options = Options {
+      roman_numerals: TokenStyle::Upper,
+      version_tokens: TokenStyle::Preserve,
+      ..Options::default()
+    };
+    assert_eq!(
+      convert(HeckCase::Title, "chapter iv: the return", &options),
+      "Chapter IV The Return"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, "api v2 overview", &options),
+      "Api v2 Overview"
+    );
+    assert_eq!(
+      convert(HeckCase::Snake, "chapter IV", &options),
+      "chapter_iv"
+    );
+  }
+
+  #[test]
+  fn special_words() {
+    for numeral in ["i", "IV", "ix", "xlii", "MCMXCIV", "mmmcmxcix"] {
+      assert!(is_roman_numeral(numeral), "{numeral}");
+    }
+    for word in ["", "iiii", "vx", "im", "ivi", "chapter"] {
+      assert!(!is_roman_numeral(word), "{word}");
+    }
+    assert!(is_version_token("v2"));
+    assert!(is_version_token("V10"));
+    assert!(!is_version_token("v"));
+    assert!(!is_version_token("v2a"));
+
+    let options = Options {
+      preserve_underscores: true,
+      ..Options::default()
+    };
+    assert_eq!(
+      convert(HeckCase::LowerCamel, "__private_field_", &options),
+      "__privateField_"
+    );
+    assert_eq!(convert(HeckCase::Snake, "___", &options), "___");
+    let options = Options {
+      ascii_mode: true,
+      ..Options::default()
+    };
+    assert_eq!(
+      convert(HeckCase::Snake, "café au lait", &options),
+      "caf_au_lait"
+    );
+  }
+
+  /// Expected outputs, in the order of `CASES`. Changing any of them changes
+  /// the rendered output of downstream templates.
+  const GOLDEN: &[(&str, [&str; 8])] = &[
+    ("", ["", "", "", "", "", "", "", ""]),
+    (
+      "abc",
+      ["abc", "abc", "ABC", "ABC", "abc", "Abc", "Abc", "Abc"],
+    ),
+    (
+      "hello world 21",
+      [
+        "hello-world-21",
+        "helloWorld21",
+        "HELLO-WORLD-21",
+        "HELLO_WORLD_21",
+        "hello_world_21",
+        "Hello World 21",
+        "Hello-World-21",
+        "HelloWorld21",
+      ],
+    ),
+    (
+      "hello_world_21",
+      [
+        "hello-world-21",
+        "helloWorld21",
+        "HELLO-WORLD-21",
+        "HELLO_WORLD_21",
+        "hello_world_21",
+        "Hello World 21",
+        "Hello-World-21",
+        "HelloWorld21",
+      ],
+    ),
+    (
+      "HelloWorld21",
+      [
+        "hello-world21",
+        "helloWorld21",
+        "HELLO-WORLD21",
+        "HELLO_WORLD21",
+        "hello_world21",
+        "Hello World21",
+        "Hello-World21",
+        "HelloWorld21",
+      ],
+    ),
+    (
+      "XMLHttpRequest",
+      [
+        "xml-http-request",
+        "xmlHttpRequest",
+        "XML-HTTP-REQUEST",
+        "XML_HTTP_REQUEST",
+        "xml_http_request",
+        "Xml Http Request",
+        "Xml-Http-Request",
+        "XmlHttpRequest",
+      ],
+    ),
+    (
+      "ABC123def456GHI",
+      [
+        "abc123def456-ghi",
+        "abc123def456Ghi",
+        "ABC123DEF456-GHI",
+        "ABC123DEF456_GHI",
+        "abc123def456_ghi",
+        "Abc123def456 Ghi",
+        "Abc123def456-Ghi",
+        "Abc123def456Ghi",
+      ],
+    ),
+    (
+      "  --leading and trailing__ ",
+      [
+        "leading-and-trailing",
+        "leadingAndTrailing",
+        "LEADING-AND-TRAILING",
+        "LEADING_AND_TRAILING",
+        "leading_and_trailing",
+        "Leading And Trailing",
+        "Leading-And-Trailing",
+        "LeadingAndTrailing",
+      ],
+    ),
+    (
+      "user.id/v2",
+      [
+        "user-id-v2",
+        "userIdV2",
+        "USER-ID-V2",
+        "USER_ID_V2",
+        "user_id_v2",
+        "User Id V2",
+        "User-Id-V2",
+        "UserIdV2",
+      ],
+    ),
+  ];
+
+  /// Outputs depending on the Heck major version.
+  #[cfg(feature = "heck05")]
+  const GOLDEN_UNICODE: &[(&str, [&str; 8])] = &[
+    (
+      "ΣΑΣ σας",
+      [
+        "σας-σας",
+        "σαςΣας",
+        "ΣΑΣ-ΣΑΣ",
+        "ΣΑΣ_ΣΑΣ",
+        "σας_σας",
+        "Σας Σας",
+        "Σας-Σας",
+        "ΣαςΣας",
+      ],
+    ),
+    (
+      "café au lait",
+      [
+        "café-au-lait",
+        "caféAuLait",
+        "CAFÉ-AU-LAIT",
+        "CAFÉ_AU_LAIT",
+        "café_au_lait",
+        "Café Au Lait",
+        "Café-Au-Lait",
+        "CaféAuLait",
+      ],
+    ),
+    (
+      "ǅungla",
+      [
+        "ǆungla", "ǆungla", "ǄUNGLA", "ǄUNGLA", "ǆungla", "Ǆungla", "Ǆungla", "Ǆungla",
+      ],
+    ),
+    (
+      "日本語テキスト",
+      [
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+        "日本語テキスト",
+      ],
+    ),
+  ];
+  #[cfg(not(feature = "heck05"))]
+  const GOLDEN_UNICODE: &[(&str, [&str; 8])] = &[
+    ("ΣΑΣ σας", ["", "", "", "", "", "", "", ""]),
+    (
+      "café au lait",
+      [
+        "caf-au-lait",
+        "cafAuLait",
+        "CAF-AU-LAIT",
+        "CAF_AU_LAIT",
+        "caf_au_lait",
+        "Caf Au Lait",
+        "Caf-Au-Lait",
+        "CafAuLait",
+      ],
+    ),
+    (
+      "ǅungla",
+      [
+        "ungla", "ungla", "UNGLA", "UNGLA", "ungla", "Ungla", "Ungla", "Ungla",
+      ],
+    ),
+    ("日本語テキスト", ["", "", "", "", "", "", "", ""]),
+  ];
+
+  #[test]
+  fn golden() {
+    for (input, outputs) in GOLDEN.iter().chain(GOLDEN_UNICODE) {
+      for (case, output) in CASES.iter().zip(outputs) {
+        let options = Options::default();
+        assert_eq!(
+          convert(*case, input, &options),
+          *output,
+          "{case:?} {input:?}"
+        );
+        assert_eq!(
+          convert_words(*case, input, &options),
+          *output,
+          "{case:?} {input:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn chunked() {
+    let inputs = [
+      "",
+      "the great  gatsby: chapter iv",
+      "__private_field_name__",
+      "_a_b__",
+      "XMLHttpRequest user_id2 ΣΑΣ σας",
+      "  --leading and trailing__ ",
+    ];
+    let preserve = Options {
+      preserve_underscores: true,
+      digit_boundary: true,
+      ..Options::default()
+    };
+    for options in [Options::default(), preserve] {
+      for case in CASES {
+        for input in inputs {
+          for chunk_length in 0..8 {
+            assert_eq!(
+              convert_chunked(case, input, &options, chunk_length),
+              convert_words(case, input, &options),
+              "{case:?} {input:?} {chunk_length}"
+            );
+          }
+        }
+      }
+    }
+    let long = "the quick brown fox ".repeat(CHUNKED_THRESHOLD / 10);
+    assert_eq!(
+      convert(HeckCase::Title, &long, &Options::default()),
+      to_title_case(&long)
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn stream() {
+    let input = "__the café  au lait_ ΣΑΣ__";
+    let options = Options {
+      preserve_underscores: true,
+      ..Options::default()
+    };
+    for chunk_length in 0..8 {
+      // Read a byte at a time, cutting the characters.
+      let reader = std::io::BufReader::with_capacity(1, input.as_bytes());
+      let mut output = Vec::new();
+      convert_stream(
+        HeckCase::LowerCamel,
+        reader,
+        &mut output,
+        &options,
+        chunk_length,
+      )
+      .unwrap();
+      assert_eq!(
+        String::from_utf8(output).unwrap(),
+        convert_words(HeckCase::LowerCamel, input, &options)
+      );
+    }
+    let mut output = Vec::new();
+    let error = to_title_case_stream(&b"caf\xe9 au lait"[..], &mut output, &options).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+  }
+
+  #[test]
+  fn leading_article() {
+    let moved = Options::builder()
+      .leading_article(ArticleStyle::Move)
+      .build();
+    assert_eq!(
+      convert(HeckCase::Title, "the great gatsby", &moved),
+      "Great Gatsby, The"
+    );
+    assert_eq!(convert(HeckCase::Title, "An_Ode", &moved), "Ode, An");
+    assert_eq!(
+      convert(HeckCase::Title, "theory of a", &moved),
+      "Theory Of A"
+    );
+    assert_eq!(convert(HeckCase::Title, "the", &moved), "The");
+    assert_eq!(convert(HeckCase::Snake, "the end", &moved), "the_end");
+    let dropped = Options::builder()
+      .leading_article(ArticleStyle::Drop)
+      .build();
+    assert_eq!(
+      convert(HeckCase::Title, "a tale of two cities", &dropped),
+      "Tale Of Two Cities"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "heck05")]
+  fn lang() {
+    let fr = Options::builder().lang(Lang::Fr).build();
+    assert_eq!(
+      convert(HeckCase::Title, "la vie de l'été", &fr),
+      "La Vie de l'Été"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, "l’ÉCUME DES JOURS", &fr),
+      "L’Écume des Jours"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, "qu'importe le flacon", &fr),
+      "Qu'Importe le Flacon"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, "rock 'n' roll", &fr),
+      "Rock N Roll"
+    );
+    assert_eq!(
+      convert(HeckCase::Snake, "la vie de l'été", &fr),
+      "la_vie_de_l_été"
+    );
+    let es = Options::builder().lang(Lang::Es).build();
+    assert_eq!(
+      convert(HeckCase::Title, "el señor de los anillos", &es),
+      "El Señor de los Anillos"
+    );
+    assert_eq!(convert(HeckCase::Title, "l'été", &es), "L Été");
+    for chunk_length in 0..8 {
+      assert_eq!(
+        convert_chunked(HeckCase::Title, "de l'été à l'hiver", &fr, chunk_length),
+        "De l'Été à l'Hiver"
+      );
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "heck05")]
+  fn german() {
+    let de = Options::builder().lang(Lang::De).build();
+    assert_eq!(
+      convert(HeckCase::ShoutySnake, "große straße", &de),
+      "GROẞE_STRAẞE"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, "die große Straße von Berlin", &de),
+      "Die große Straße von Berlin"
+    );
+    assert_eq!(
+      convert(HeckCase::Snake, "Große Straße", &de),
+      "große_straße"
+    );
+    let ss = Options {
+      eszett_as_ss: true,
+      ..de
+    };
+    assert_eq!(
+      convert(HeckCase::ShoutyKebab, "große straße", &ss),
+      "GROSSE-STRASSE"
+    );
+    assert_eq!(
+      convert(HeckCase::ShoutySnake, "straße", &Options::default()),
+      "STRASSE"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "heck05")]
+  fn cjk() {
+    let word = Options::builder().cjk(CjkStyle::Word).build();
+    assert_eq!(
+      convert(HeckCase::Snake, "hello世界World", &word),
+      "hello_世界_world"
+    );
+    assert_eq!(
+      convert(HeckCase::UpperCamel, "東京tower", &word),
+      "東京Tower"
+    );
+    let preserve = Options::builder().cjk(CjkStyle::Preserve).build();
+    assert_eq!(
+      convert(HeckCase::Kebab, "userName東京、ＴＯＫＹＯ", &preserve),
+      "user-name-東京、ＴＯＫＹＯ"
+    );
+    assert_eq!(
+      convert(HeckCase::Kebab, "東京、ＴＯＫＹＯ", &Options::default()),
+      "東京-ｔｏｋｙｏ"
+    );
+  }
+
+  #[test]
+  fn version_strings() {
+    let options = Options::builder().version_strings(true).build();
+    let input = "Release v1.2.3-RC.1 notes";
+    assert_eq!(
+      convert(HeckCase::Kebab, input, &options),
+      "release-v1.2.3-RC.1-notes"
+    );
+    assert_eq!(
+      convert(HeckCase::Title, input, &options),
+      "Release v1.2.3-RC.1 Notes"
+    );
+    assert_eq!(
+      convert(HeckCase::Kebab, input, &Options::default()),
+      "release-v1-2-3-rc-1-notes"
+    );
+    for chunk_length in 0..8 {
+      assert_eq!(
+        convert_chunked(HeckCase::Snake, input, &options, chunk_length),
+        "release_v1.2.3-RC.1_notes"
+      );
+    }
+  }
+
+  #[test]
+  fn opaque_tokens() {
+    let options = Options::builder().opaque_tokens(true).build();
+    let input = "Deploy 9FCEB02D0AE5 with key dGhpcyBpcyBhIHRlc3Q9Cg==";
+    assert_eq!(
+      convert(HeckCase::Snake, input, &options),
+      "deploy_9FCEB02D0AE5_with_key_dGhpcyBpcyBhIHRlc3Q9Cg=="
+    );
+    for chunk_length in 0..8 {
+      assert_eq!(
+        convert_chunked(HeckCase::Kebab, input, &options, chunk_length),
+        "deploy-9FCEB02D0AE5-with-key-dGhpcyBpcyBhIHRlc3Q9Cg=="
+      );
+    }
+  }
+
+  #[test]
+  fn heck_case_names() {
+    for case in HeckCase::ALL {
+      assert_eq!(case.name().parse(), Ok(case));
+      assert_eq!(case.filter_name().parse(), Ok(case));
+      for alias in case.aliases() {
+        assert_eq!(alias.parse(), Ok(case));
+      }
+      assert_eq!(case.to_string(), case.name());
+    }
+    assert_eq!("camel".parse(), Ok(HeckCase::LowerCamel));
+    assert_eq!("pascal".parse(), Ok(HeckCase::UpperCamel));
+    assert_eq!("screaming_snake".parse(), Ok(HeckCase::ShoutySnake));
+    assert_eq!(
+      "Snake".parse::<HeckCase>().unwrap_err().to_string(),
+      "unknown case `Snake`, expected one of: kebab, lower_camel, shouty_kebab, \
+       shouty_snake, snake, title, train, upper_camel"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn heck_case_serde() {
+    assert_eq!(
+      serde_json::to_string(&HeckCase::UpperCamel).unwrap(),
+      "\"upper_camel\""
+    );
+    assert_eq!(
+      serde_json::from_str::<HeckCase>("\"shouty_kebab\"").unwrap(),
+      HeckCase::ShoutyKebab
+    );
+  }
+}
@@ -0,0 +1,188 @@
+//! Machine-readable documentation of the filters.
+use crate::filters::evaluate_filter;
+use crate::FilterRegistry;
+use liquid_core::parser::ParameterReflection;
+use liquid_core::{Object, ParseFilter, Value, ValueView};
+
+/// The inputs used to build the examples of [`FilterDoc`].
+const EXAMPLE_INPUTS: [&str; 3] = ["hello world", "XMLHttpRequest", "user_id_2"];
+
+/// Documentation of a filter, for help pages and editor autocomplete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FilterDoc {
+  /// The name used in templates.
+  pub name: String,
+  /// What the filter does.
+  pub description: String,
+  /// The arguments the filter accepts.
+  pub arguments: Vec<ArgumentDoc>,
+  /// Sample conversions.
+  pub examples: Vec<ExampleDoc>,
+}
+
+/// Documentation of a filter argument.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArgumentDoc {
+  /// The argument name.
+  pub name: String,
+  /// What the argument does.
+  pub description: String,
+  /// Is the argument passed by keyword (`name: value`) rather than by position?
+  pub keyword: bool,
+  /// May the argument be omitted?
+  pub optional: bool,
+}
+
+/// A sample conversion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExampleDoc {
+  /// The template, e.g. `{{ "hello world" | snakecase }}`.
+  pub template: String,
+  /// The rendered output.
+  pub output: String,
+}
+
+/// Documentation of all the filters known to [`FilterRegistry`].
+///
+/// The examples are rendered by the filters themselves, so they always match
+/// the actual behavior.
+///
+/// ```
+/// # #[cfg(feature = "filter-snake")]
+/// # {
+/// let docs = liquid_heck::filter_docs();
+/// let snake = docs.iter().find(|doc| doc.name == "snakecase").unwrap();
+/// assert_eq!(snake.examples[0].template, r#"{{ "hello world" | snakecase }}"#);
+/// assert_eq!(snake.examples[0].output, "hello_world");
+/// # }
+/// ```
+pub fn filter_docs() -> Vec<FilterDoc> {
+  FilterRegistry::names()
+    .iter()
+    .filter_map(|name| FilterRegistry::get(name))
+    .map(|filter| filter_doc(filter.as_ref()))
+    .collect()
+}
+
+/// Documentation of `filter`.
+pub fn filter_doc(filter: &dyn ParseFilter) -> FilterDoc {
+  let reflection = filter.reflection();
+  let arguments = |parameters: &[ParameterReflection], keyword| {
+    parameters
+      .iter()
+      .map(|parameter| ArgumentDoc {
+        name: parameter.name.to_string(),
+        description: parameter.description.to_string(),
+        keyword,
+        optional: parameter.is_optional,
+      })
+      .collect::<Vec<_>>()
+  };
+  let mut all_arguments = arguments(reflection.positional_parameters(), false);
+  all_arguments.extend(arguments(reflection.keyword_parameters(), true));
+
+  let name = reflection.name();
+  let examples = EXAMPLE_INPUTS
+    .iter()
+    .filter_map(|input| {
+      let output = evaluate_filter(filter, input).ok()?.to_kstr().into_string();
+      Some(ExampleDoc {
+        template: format!("{{{{ {input:?} | {name} }}}}"),
+        output,
+      })
+    })
+    .collect();
+
+  FilterDoc {
+    name: name.to_string(),
+    description: reflection.description().to_string(),
+    arguments: all_arguments,
+    examples,
+  }
+}
+
+/// The global holding the filter documentation, see [`insert_filter_docs`].
+pub const HECK_FILTERS_GLOBAL: &str = "heck_filters";
+
+/// Insert the documentation of all the filters in `globals`, as the
+/// `heck_filters` array, so templates can list the available conversions.
+///
+/// Each item has a `name`, a `description`, an `example` template and its
+/// `output`. `example` and `output` are nil for the filters requiring
+/// arguments.
+///
+/// ```
+/// # #[cfg(feature = "filter-snake")]
+/// # {
+/// let mut globals = liquid::object!({});
+/// liquid_heck::insert_filter_docs(&mut globals);
+///
+/// let template = liquid::ParserBuilder::with_stdlib()
+///     .build()
+///     .unwrap()
+///     .parse("{% for f in heck_filters %}{{ f.name }}: {{ f.output }}\n{% endfor %}")
+///     .unwrap();
+/// let output = template.render(&globals).unwrap();
+/// assert!(output.contains("snakecase: hello_world\n"));
+/// # }
+/// ```
+pub fn insert_filter_docs(globals: &mut Object) {
+  let docs = filter_docs()
+    .into_iter()
+    .map(|doc| {
+      let mut item = Object::new();
+      item.insert("name".into(), Value::scalar(doc.name));
+      item.insert("description".into(), Value::scalar(doc.description));
+      let (example, output) = match doc.examples.into_iter().next() {
+        Some(example) => (
+          Value::scalar(example.template),
+          Value::scalar(example.output),
+        ),
+        None => (Value::Nil, Value::Nil),
+      };
+      item.insert("example".into(), example);
+      item.insert("output".into(), output);
+      Value::Object(item)
+    })
+    .collect();
+  globals.insert(HECK_FILTERS_GLOBAL.into(), Value::Array(docs));
+}
+
+#[cfg(all(test, feature = "all-filters"))]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn docs() {
+    let docs = filter_docs();
+    assert_eq!(docs.len(), FilterRegistry::names().len());
+    let camel = docs
+      .iter()
+      .find(|doc| doc.name == "uppercamelcase")
+      .unwrap();
+    assert_eq!(camel.description, "Convert the string to UpperCamelCase.");
+    assert_eq!(camel.arguments[0].name, "each_line");
+    assert!(camel.arguments[0].keyword);
+    let outputs: Vec<_> = camel
+      .examples
+      .iter()
+      .map(|example| example.output.as_str())
+      .collect();
+    assert_eq!(outputs, ["HelloWorld", "XmlHttpRequest", "UserId2"]);
+  }
+
+  #[test]
+  fn globals() {
+    let mut globals = Object::new();
+    insert_filter_docs(&mut globals);
+    let filters = globals.get("heck_filters").unwrap().as_array().unwrap();
+    assert_eq!(filters.size(), FilterRegistry::names().len() as i64);
+    let kebab = filters.first().unwrap().as_object().unwrap();
+    assert_eq!(kebab.get("name").unwrap().to_kstr(), "kebabcase");
+    assert_eq!(kebab.get("output").unwrap().to_kstr(), "hello-world");
+  }
+}
@@ -0,0 +1,1431 @@
+//! The Liquid filters. Only available with the `std` feature.
+#[cfg(feature = "filter-case")]
+use crate::args::positional_or_keyword;
+use crate::args::{invalid_argument, named_value, one_of};
+use crate::conversion::{self, HeckCase};
+use liquid_core::model::{ObjectView, ScalarCow, State, ValueCow};
+use liquid_core::parser::{FilterArguments, FilterParametersReflection, ParameterReflection};
+use liquid_core::runtime::RuntimeBuilder;
+#[cfg(feature = "filter-case")]
+use liquid_core::{Display_filter, FromFilterParameters};
+use liquid_core::{
+  Expression, Filter, FilterParameters, FilterReflection, ParseFilter, Result, Runtime, Value,
+  ValueView,
+};
+
+use crate::limit::{limit_argument, shorten, Strategy};
+use crate::modifiers::string_list;
+#[cfg(feature = "filter-case")]
+use crate::objects::case_argument;
+use crate::options::is_control;
+use crate::text::convert_markdown;
+use crate::words::{is_dropped_punctuation, split_after_words};
+use crate::{
+  AcronymStyle, ArticleStyle, CjkStyle, ControlStyle, Lang, Options, PunctuationStyle, TokenStyle,
+  UnitStyle,
+};
+use std::borrow::Cow;
+use std::fmt;
+
+#[cfg(feature = "filter-upper-camel")]
+crate::define_case_filter! {
+  pub struct UpperCamelCase;
+  name = "uppercamelcase",
+  description = "Convert the string to UpperCamelCase.",
+  convert = conversion::to_upper_camel_case,
+  case = HeckCase::UpperCamel,
+}
+
+#[cfg(feature = "filter-lower-camel")]
+crate::define_case_filter! {
+  pub struct LowerCamelCase;
+  name = "lowercamelcase",
+  description = "Convert the string to lowerCamelCase.",
+  convert = conversion::to_lower_camel_case,
+  case = HeckCase::LowerCamel,
+}
+
+#[cfg(feature = "filter-snake")]
+crate::define_case_filter! {
+  pub struct SnakeCase;
+  name = "snakecase",
+  description = "Convert the string to snake-case.",
+  convert = conversion::to_snake_case,
+  case = HeckCase::Snake,
+}
+
+#[cfg(feature = "filter-kebab")]
+crate::define_case_filter! {
+  pub struct KebabCase;
+  name = "kebabcase",
+  description = "Convert the string to kebab-case.",
+  convert = conversion::to_kebab_case,
+  case = HeckCase::Kebab,
+}
+
+#[cfg(feature = "filter-shouty-snake")]
+crate::define_case_filter! {
+  pub struct ShoutySnakeCase;
+  name = "shoutysnakecase",
+  description = "Convert the string to SHOUTY_SNAKE_CASE.",
+  convert = conversion::to_shouty_snake_case,
+  case = HeckCase::ShoutySnake,
+}
+
+#[cfg(feature = "filter-title")]
+crate::define_case_filter! {
+  pub struct TitleCase;
+  name = "titlecase",
+  description = "Convert the string to title case.",
+  convert = conversion::to_title_case,
+  case = HeckCase::Title,
+}
+
+#[cfg(feature = "filter-shouty-kebab")]
+crate::define_case_filter! {
+  pub struct ShoutyKebabCase;
+  name = "shoutykebabcase",
+  description = "Convert the string to SHOUTY-KEBAB-CASE.",
+  convert = conversion::to_shouty_kebab_case,
+  case = HeckCase::ShoutyKebab,
+}
+
+#[cfg(feature = "filter-train")]
+crate::define_case_filter! {
+  pub struct TrainCase;
+  name = "traincase",
+  description = "Convert the string to Train-Case.",
+  convert = conversion::to_train_case,
+  case = HeckCase::Train,
+}
+
+#[cfg(feature = "filter-case")]
+#[derive(Debug, FilterParameters)]
+struct ToCaseArgs {
+  #[parameter(
+    description = "The case to convert to, e.g. \"snake\" or \"kebabcase\".",
+    arg_type = "str"
+  )]
+  to: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `to`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "to"
+  )]
+  to_keyword: Option<Expression>,
+  #[parameter(
+    description = "The case of the input, checked before the conversion.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  from: Option<Expression>,
+}
+
+/// Convert the string to a case named at render time, positionally or by
+/// keyword: `{{ name | case: "snake" }}`, `{{ name | case: to: "snake" }}`.
+/// `from:` names the case of the input, which is checked:
+/// `{{ name | case: to: "snake", from: "camel" }}` fails on `user_id`,
+/// catching the identifiers a generator does not expect.
+#[cfg(feature = "filter-case")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "case",
+  description = "Convert the string to the given case.",
+  parameters(ToCaseArgs),
+  parsed(ToCaseFilter)
+)]
+pub struct ToCase;
+
+#[cfg(feature = "filter-case")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "case"]
+struct ToCaseFilter {
+  #[parameters]
+  args: ToCaseArgs,
+}
+
+#[cfg(feature = "filter-case")]
+impl Filter for ToCaseFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let to = positional_or_keyword("to", args.to, args.to_keyword)?
+      .ok_or_else(|| invalid_argument("to", "Case name expected"))?;
+    let to = case_argument("to", &to)?;
+    let from = args
+      .from
+      .map(|from| case_argument("from", &from))
+      .transpose()?;
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let options = runtime_options(runtime)?;
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    if let Some(from) = from {
+      if conversion::convert_prepared(from, s, &options) != s {
+        return Err(
+          liquid_core::Error::with_msg("Input not in the `from` case").context("from", from.name()),
+        );
+      }
+    }
+    let output = conversion::convert_prepared(to, s, &options);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "case: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+/// The arguments of all the case conversion filters. `lang` comes last:
+/// the filters converting with a plain function leave it out.
+#[derive(Debug, FilterParameters)]
+pub(crate) struct CaseArgs {
+  #[parameter(
+    description = "Convert each line on its own, keeping the line breaks.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  each_line: Option<Expression>,
+  #[parameter(
+    description = "Only convert the first words, leaving the rest of the string as is.",
+    arg_type = "integer",
+    mode = "keyword"
+  )]
+  words: Option<Expression>,
+  #[parameter(
+    description = "Only convert the prose of a Markdown document, each line on its own.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  markdown: Option<Expression>,
+  #[parameter(
+    description = "The maximum length in bytes, or a preset: \"postgres\" and \"k8s\" (63), \"oracle\" (30), \"s3\" (63).",
+    arg_type = "any",
+    mode = "keyword"
+  )]
+  limit: Option<Expression>,
+  #[parameter(
+    description = "How a longer output is shortened to the `limit`: \"truncate\" (the default), \"abbrev\" cutting all the words, \"hash\" cutting after a word and appending a hash.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  limit_strategy: Option<Expression>,
+  #[parameter(
+    description = "Follow the casing rules of a language: \"fr\", \"es\" or \"de\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  lang: Option<Expression>,
+}
+
+impl CaseArgs {
+  /// Convert `s` with `convert` and `options`, as requested by the
+  /// arguments. `lang:` overrides [`Options::lang`], `limit:` shortens the
+  /// output.
+  fn apply(
+    &self,
+    s: &str,
+    runtime: &dyn Runtime,
+    options: &Options,
+    convert: impl Fn(&str, &Options) -> String,
+  ) -> Result<String> {
+    let args = self.evaluate(runtime)?;
+    let options = match args.lang {
+      Some(lang) => Cow::Owned(Options {
+        lang: named_value("lang", lang.as_str(), Lang::from_name, Lang::NAMES)?,
+        ..options.clone()
+      }),
+      None => Cow::Borrowed(options),
+    };
+    let words = args
+      .words
+      .map(|words| {
+        usize::try_from(words).map_err(|_| invalid_argument("words", "Positive number expected"))
+      })
+      .transpose()?;
+    let convert = |s: &str| match words {
+      Some(words) => {
+        let (head, tail) = split_after_words(s, words, &options);
+        convert(head, &options) + tail
+      }
+      None => convert(s, &options),
+    };
+    let limit = args
+      .limit
+      .as_ref()
+      .map(|limit| limit_argument(limit))
+      .transpose()?;
+    let strategy = args
+      .limit_strategy
+      .map(|strategy| {
+        named_value(
+          "limit_strategy",
+          strategy.as_str(),
+          Strategy::from_name,
+          Strategy::NAMES,
+        )
+      })
+      .transpose()?
+      .unwrap_or_default();
+    let output = if args.markdown.unwrap_or(false) {
+      convert_markdown(s, convert)
+    } else if !args.each_line.unwrap_or(false) {
+      convert(s)
+    } else {
+      let mut output = String::with_capacity(s.len());
+      for line in s.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        output.push_str(&convert(content));
+        output.push_str(&line[content.len()..]);
+      }
+      output
+    };
+    Ok(match limit {
+      Some(limit) => shorten(&output, limit, strategy),
+      None => output,
+    })
+  }
+}
+
+/// The global configuring all the filters for a render.
+///
+/// Its fields, all optional, are the fields of [`Options`](crate::Options)
+/// (`acronyms` as an array or a comma-separated string, `acronym_style:
+/// "preserve"`, `digit_boundary: true`, ...), `ascii` as a short name of
+/// `ascii_mode`, and `nil_policy`, which is what the string filters return
+/// for a nil input: `"empty"` (the default) for an empty string, `"nil"` for
+/// nil or `"error"` to fail the render.
+///
+/// ```
+/// # #[cfg(feature = "filter-upper-camel")]
+/// # {
+/// let template = liquid::ParserBuilder::with_stdlib()
+///     .filter(liquid_heck::UpperCamelCase)
+///     .build()
+///     .unwrap()
+///     .parse("{{ field | uppercamelcase }}")
+///     .unwrap();
+/// let globals = liquid::object!({
+///     "field": "user_id",
+///     "heck_config": { "acronyms": ["ID"] },
+/// });
+/// assert_eq!(template.render(&globals).unwrap(), "UserID");
+/// # }
+/// ```
+pub const HECK_CONFIG_GLOBAL: &str = "heck_config";
+
+/// What the string filters return for a nil input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum NilPolicy {
+  #[default]
+  Empty,
+  Nil,
+  Error,
+}
+
+/// The register holding the options set by the `heck_defaults` tag for the
+/// rest of the render.
+#[derive(Debug, Default)]
+pub(crate) struct HeckDefaults(pub(crate) Option<Options>);
+
+/// The field `name` of the [`HECK_CONFIG_GLOBAL`] global.
+fn config_field<'r>(runtime: &'r dyn Runtime, name: &'static str) -> Option<ValueCow<'r>> {
+  runtime.try_get(&[ScalarCow::new(HECK_CONFIG_GLOBAL), ScalarCow::new(name)])
+}
+
+/// Update `options` with the fields of `fields` named after the options.
+/// The other fields are ignored.
+pub(crate) fn update_options(options: &mut Options, fields: &dyn ObjectView) -> Result<()> {
+  fn invalid(option: &'static str, cause: &'static str) -> liquid_core::Error {
+    liquid_core::Error::with_msg("Invalid option")
+      .context("option", option)
+      .context("cause", cause)
+  }
+  /// The value of `option` spelled `value`, one of `expected`.
+  fn named<T>(
+    option: &'static str,
+    value: &dyn ValueView,
+    from_name: impl FnOnce(&str) -> Option<T>,
+    expected: &[&str],
+  ) -> Result<T> {
+    let value = value.to_kstr();
+    from_name(value.as_str()).ok_or_else(|| {
+      liquid_core::Error::with_msg("Invalid option")
+        .context("option", option)
+        .context("value", format!("{:?}", value.as_str()))
+        .context("expected", one_of(expected))
+    })
+  }
+  let token_style =
+    |option, value: &dyn ValueView| named(option, value, TokenStyle::from_name, TokenStyle::NAMES);
+  for (name, value) in fields.iter() {
+    let is_set = || value.query_state(State::Truthy);
+    match name.as_str() {
+      "acronyms" => options.acronyms = string_list(value),
+      "acronym_style" => {
+        options.acronym_style = named(
+          "acronym_style",
+          value,
+          AcronymStyle::from_name,
+          AcronymStyle::NAMES,
+        )?
+      }
+      "roman_numerals" => options.roman_numerals = token_style("roman_numerals", value)?,
+      "version_tokens" => options.version_tokens = token_style("version_tokens", value)?,
+      "version_strings" => options.version_strings = is_set(),
+      "opaque_tokens" => options.opaque_tokens = is_set(),
+      "digit_boundary" => options.digit_boundary = is_set(),
+      "script_boundary" => options.script_boundary = is_set(),
+      "preserve_underscores" => options.preserve_underscores = is_set(),
+      "eszett_as_ss" => options.eszett_as_ss = is_set(),
+      "ascii_mode" | "ascii" => options.ascii_mode = is_set(),
+      "cjk" => options.cjk = named("cjk", value, CjkStyle::from_name, CjkStyle::NAMES)?,
+      "lang" => options.lang = named("lang", value, Lang::from_name, Lang::NAMES)?,
+      "leading_article" => {
+        options.leading_article = named(
+          "leading_article",
+          value,
+          ArticleStyle::from_name,
+          ArticleStyle::NAMES,
+        )?
+      }
+      "trim" => options.trim = is_set(),
+      "collapse_ws" => options.collapse_ws = is_set(),
+      "strip_soft_hyphens" => options.strip_soft_hyphens = is_set(),
+      "strip_invisible" => options.strip_invisible = is_set(),
+      "control_chars" => {
+        options.control_chars = named(
+          "control_chars",
+          value,
+          ControlStyle::from_name,
+          ControlStyle::NAMES,
+        )?
+      }
+      "keep_punctuation" => options.keep_punctuation = value.to_kstr().into_string(),
+      "punctuation" => {
+        options.punctuation = named(
+          "punctuation",
+          value,
+          PunctuationStyle::from_name,
+          PunctuationStyle::NAMES,
+        )?
+      }
+      "spell_symbols" => options.spell_symbols = is_set(),
+      "units" => options.units = named("units", value, UnitStyle::from_name, UnitStyle::NAMES)?,
+      #[cfg(feature = "number-words")]
+      "spell_numbers" => {
+        options.spell_numbers = named(
+          "spell_numbers",
+          value,
+          crate::NumberStyle::from_name,
+          crate::NumberStyle::NAMES,
+        )?
+      }
+      #[cfg(feature = "diacritics")]
+      "strip_diacritics" => options.strip_diacritics = is_set(),
+      #[cfg(feature = "homoglyphs")]
+      "normalize_homoglyphs" => options.normalize_homoglyphs = is_set(),
+      "max_input_length" => {
+        options.max_input_length = value
+          .as_scalar()
+          .and_then(|length| length.to_integer())
+          .and_then(|length| usize::try_from(length).ok())
+          .ok_or_else(|| invalid("max_input_length", "Positive integer expected"))?
+      }
+      _ => {}
+    }
+  }
+  Ok(())
+}
+
+/// The options set by `{% heck_defaults %}`, else by the `heck_config`
+/// global, else the default options.
+pub(crate) fn runtime_options(runtime: &dyn Runtime) -> Result<Options> {
+  if let Some(options) = &runtime.registers().get_mut::<HeckDefaults>().0 {
+    return Ok(options.clone());
+  }
+  let mut options = Options::default();
+  let config = runtime.try_get(&[ScalarCow::new(HECK_CONFIG_GLOBAL)]);
+  if let Some(config) = config.as_ref().and_then(|config| config.as_object()) {
+    update_options(&mut options, config)?;
+  }
+  Ok(options)
+}
+
+/// Fail when `s` is longer than the [`Options::max_input_length`] of
+/// `options`.
+pub(crate) fn check_input_length(s: &str, options: &Options) -> Result<()> {
+  if s.len() <= options.max_input_length {
+    return Ok(());
+  }
+  Err(
+    liquid_core::Error::with_msg("Input too long")
+      .context("length", s.len().to_string())
+      .context("max_input_length", options.max_input_length.to_string()),
+  )
+}
+
+/// `s` checked against [`Options::max_input_length`] and
+/// [`Options::control_chars`], preprocessed by [`Options::preprocess`], then
+/// checked against [`Options::punctuation`].
+pub(crate) fn prepare_input<'s>(s: &'s str, options: &Options) -> Result<Cow<'s, str>> {
+  check_input_length(s, options)?;
+  if options.control_chars == ControlStyle::Error {
+    if let Some((offset, c)) = s.char_indices().find(|&(_, c)| is_control(c)) {
+      return Err(
+        liquid_core::Error::with_msg("Control character in input")
+          .context("character", format!("U+{:04X}", u32::from(c)))
+          .context("offset", offset.to_string())
+          .context("control_chars", "error"),
+      );
+    }
+  }
+  let s = options.preprocess(s);
+  if options.punctuation == PunctuationStyle::Error {
+    if let Some(c) = s.chars().find(|&c| is_dropped_punctuation(c, options)) {
+      return Err(
+        liquid_core::Error::with_msg("Punctuation in input")
+          .context("character", format!("{c:?}"))
+          .context("punctuation", "error"),
+      );
+    }
+  }
+  Ok(s)
+}
+
+/// The output of a string filter for `input` when it is nil, as set by the
+/// `nil_policy` of the `heck_config` global. `None` when `input` is
+/// converted like an empty string.
+pub(crate) fn nil_output(input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Option<Value>> {
+  if !input.is_nil() {
+    return Ok(None);
+  }
+  let policy = match config_field(runtime, "nil_policy") {
+    None => NilPolicy::default(),
+    Some(policy) => match policy.to_kstr().as_str() {
+      "empty" => NilPolicy::Empty,
+      "nil" => NilPolicy::Nil,
+      "error" => NilPolicy::Error,
+      _ => {
+        return Err(
+          liquid_core::Error::with_msg("Invalid heck_config")
+            .context("field", "nil_policy")
+            .context("cause", "\"empty\", \"nil\" or \"error\" expected"),
+        )
+      }
+    },
+  };
+  match policy {
+    NilPolicy::Empty => Ok(None),
+    NilPolicy::Nil => Ok(Some(Value::Nil)),
+    NilPolicy::Error => Err(liquid_core::Error::with_msg("Nil input").context("nil_policy", "error")),
+  }
+}
+
+/// The parsed filter of [`define_case_filter!`], converting with a plain
+/// function.
+#[doc(hidden)]
+pub struct FnFilter {
+  name: &'static str,
+  convert: fn(&str) -> String,
+  case: Option<HeckCase>,
+  args: CaseArgs,
+}
+
+impl FnFilter {
+  /// The filter converting with `convert`, or with `case` and the options
+  /// of the render when given. `lang:` needs `case`.
+  pub fn parse(
+    name: &'static str,
+    convert: fn(&str) -> String,
+    case: Option<HeckCase>,
+    arguments: FilterArguments,
+  ) -> Result<Self> {
+    let args = CaseArgs::from_args(arguments)?;
+    if case.is_none() && args.lang.is_some() {
+      return Err(liquid_core::Error::with_msg(
+        "Unexpected named argument `lang`",
+      ));
+    }
+    Ok(FnFilter {
+      name,
+      convert,
+      case,
+      args,
+    })
+  }
+
+  /// The keyword arguments of the filters converting with `case`, or with
+  /// a plain function, which leave `lang:` out.
+  pub fn keyword_parameters(case: Option<HeckCase>) -> &'static [ParameterReflection] {
+    let parameters = CaseArgs::keyword_parameters();
+    match case {
+      Some(_) => parameters,
+      None => &parameters[..parameters.len() - 1],
+    }
+  }
+}
+
+impl fmt::Debug for FnFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("FnFilter")
+      .field("name", &self.name)
+      .finish()
+  }
+}
+
+impl fmt::Display for FnFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name)
+  }
+}
+
+impl Filter for FnFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let s = input.to_kstr();
+    let s = s.as_str();
+    #[cfg(feature = "log")]
+    log::trace!(
+      "{}: input={}",
+      self.name,
+      crate::instrument::Redacted::Str(s)
+    );
+    let options = runtime_options(runtime)?;
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    let output = match self.case {
+      Some(case) => self.args.apply(s, runtime, &options, |s, options| {
+        conversion::convert_prepared(case, s, options)
+      })?,
+      None => self
+        .args
+        .apply(s, runtime, &options, |s, _| (self.convert)(s))?,
+    };
+    #[cfg(feature = "log")]
+    log::debug!(
+      "{}: {} -> {}",
+      self.name,
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+/// A case conversion filter registered under a custom name and with custom
+/// [`Options`].
+///
+/// The options of the filter win over the options of the render, set by
+/// `{% heck_defaults %}`, else by the `heck_config` global. Only the fields
+/// they change from the default count: the other fields keep the options
+/// of the render.
+#[derive(Clone, Debug)]
+pub struct ConfiguredCase {
+  name: String,
+  case: HeckCase,
+  options: Option<Options>,
+}
+
+impl ConfiguredCase {
+  pub(crate) fn new(name: impl Into<String>, case: HeckCase, options: Option<Options>) -> Self {
+    ConfiguredCase {
+      name: name.into(),
+      case,
+      options,
+    }
+  }
+
+  /// The same filter, registered as `name` in templates.
+  ///
+  /// ```
+  /// # #[cfg(feature = "filter-upper-camel")]
+  /// # {
+  /// use liquid_heck::{Options, UpperCamelCase};
+  ///
+  /// let type_name = UpperCamelCase::with_options(Options {
+  ///     acronyms: vec!["ID".to_string()],
+  ///     ..Options::default()
+  /// })
+  /// .named("type_name");
+  /// let template = liquid::ParserBuilder::with_stdlib()
+  ///     .filter(UpperCamelCase)
+  ///     .filter(type_name)
+  ///     .build()
+  ///     .unwrap()
+  ///     .parse("{{ 'user_id' | uppercamelcase }} {{ 'user_id' | type_name }}")
+  ///     .unwrap();
+  /// assert_eq!(template.render(&liquid::object!({})).unwrap(), "UserId UserID");
+  /// # }
+  /// ```
+  pub fn named(self, name: impl Into<String>) -> Self {
+    ConfiguredCase {
+      name: name.into(),
+      ..self
+    }
+  }
+}
+
+impl HeckCase {
+  /// The filter of this case, registered under its
+  /// [filter name](Self::filter_name) and converting with `options`.
+  ///
+  /// ```
+  /// use liquid_heck::{HeckCase, Options};
+  ///
+  /// let options = Options::builder().acronyms(["ID"]).build();
+  /// let mut builder = liquid::ParserBuilder::with_stdlib();
+  /// for case in [HeckCase::Snake, HeckCase::UpperCamel] {
+  ///     builder = builder.filter(case.with_options(options.clone()));
+  /// }
+  /// let template = builder.build().unwrap().parse("{{ 'user id' | uppercamelcase }}").unwrap();
+  /// assert_eq!(template.render(&liquid::object!({})).unwrap(), "UserID");
+  /// ```
+  pub fn with_options(self, options: Options) -> ConfiguredCase {
+    ConfiguredCase::new(self.filter_name(), self, Some(options))
+  }
+}
+
+/// The filter of the case, registered under its filter name, with the
+/// options of the render.
+impl From<HeckCase> for ConfiguredCase {
+  fn from(case: HeckCase) -> Self {
+    ConfiguredCase::new(case.filter_name(), case, None)
+  }
+}
+
+/// The filter of the case, for the configuration-driven registrations:
+/// `builder.filter(Box::<dyn ParseFilter>::from(case))`.
+impl From<HeckCase> for Box<dyn ParseFilter> {
+  fn from(case: HeckCase) -> Self {
+    Box::new(ConfiguredCase::from(case))
+  }
+}
+
+impl FilterReflection for ConfiguredCase {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn description(&self) -> &str {
+    self.case.description()
+  }
+
+  fn positional_parameters(&self) -> &'static [ParameterReflection] {
+    &[]
+  }
+
+  fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+    CaseArgs::keyword_parameters()
+  }
+}
+
+impl ParseFilter for ConfiguredCase {
+  fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+    Ok(Box::new(ConfiguredCaseFilter(
+      self.clone(),
+      CaseArgs::from_args(arguments)?,
+    )))
+  }
+
+  fn reflection(&self) -> &dyn FilterReflection {
+    self
+  }
+}
+
+#[derive(Debug)]
+struct ConfiguredCaseFilter(ConfiguredCase, CaseArgs);
+
+impl fmt::Display for ConfiguredCaseFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0.name)
+  }
+}
+
+impl Filter for ConfiguredCaseFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let s = input.to_kstr();
+    let s = s.as_str();
+    #[cfg(feature = "log")]
+    log::trace!(
+      "{}: input={}",
+      self.0.name,
+      crate::instrument::Redacted::Str(s)
+    );
+    let options = match &self.0.options {
+      Some(options) => options.over(&runtime_options(runtime)?),
+      None => runtime_options(runtime)?,
+    };
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    let output = self.1.apply(s, runtime, &options, |s, options| {
+      conversion::convert_prepared(self.0.case, s, options)
+    })?;
+    #[cfg(feature = "log")]
+    log::debug!(
+      "{}: {} -> {}",
+      self.0.name,
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+/// Evaluate `filter`, without arguments, on `input`.
+pub(crate) fn evaluate_filter(filter: &dyn ParseFilter, input: &str) -> Result<Value> {
+  let arguments = FilterArguments {
+    positional: Box::new(std::iter::empty()),
+    keyword: Box::new(std::iter::empty()),
+  };
+  let runtime = RuntimeBuilder::new().build();
+  filter
+    .parse(arguments)?
+    .evaluate(&Value::scalar(input.to_string()), &runtime)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  #[cfg(feature = "filter-upper-camel")]
+  fn upper_camel_case() {
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "abc").unwrap(),
+      liquid_core::value!("Abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "hello world 21").unwrap(),
+      liquid_core::value!("HelloWorld21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "hello world 21").unwrap(),
+      liquid_core::value!("HelloWorld21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "hello_world_21").unwrap(),
+      liquid_core::value!("HelloWorld21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("HelloWorld21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-lower-camel")]
+  fn lower_camel_case() {
+    assert_eq!(
+      liquid_core::call_filter!(LowerCamelCase, "abc").unwrap(),
+      liquid_core::value!("abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LowerCamelCase, "hello world 21").unwrap(),
+      liquid_core::value!("helloWorld21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LowerCamelCase, "hello_world_21").unwrap(),
+      liquid_core::value!("helloWorld21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LowerCamelCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("helloWorld21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-snake")]
+  fn snake_case() {
+    assert_eq!(
+      liquid_core::call_filter!(SnakeCase, "abc").unwrap(),
+      liquid_core::value!("abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SnakeCase, "hello world 21").unwrap(),
+      liquid_core::value!("hello_world_21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SnakeCase, "hello_world_21").unwrap(),
+      liquid_core::value!("hello_world_21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SnakeCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("hello_world21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-kebab")]
+  fn kebab_case() {
+    assert_eq!(
+      liquid_core::call_filter!(KebabCase, "abc").unwrap(),
+      liquid_core::value!("abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(KebabCase, "hello world 21").unwrap(),
+      liquid_core::value!("hello-world-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(KebabCase, "hello_world_21").unwrap(),
+      liquid_core::value!("hello-world-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(KebabCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("hello-world21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-shouty-snake")]
+  fn shouty_snake_case() {
+    assert_eq!(
+      liquid_core::call_filter!(ShoutySnakeCase, "abc").unwrap(),
+      liquid_core::value!("ABC")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutySnakeCase, "hello world 21").unwrap(),
+      liquid_core::value!("HELLO_WORLD_21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutySnakeCase, "hello_world_21").unwrap(),
+      liquid_core::value!("HELLO_WORLD_21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutySnakeCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("HELLO_WORLD21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-title")]
+  fn title_case() {
+    assert_eq!(
+      liquid_core::call_filter!(TitleCase, "abc").unwrap(),
+      liquid_core::value!("Abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TitleCase, "hello world 21").unwrap(),
+      liquid_core::value!("Hello World 21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TitleCase, "hello_world_21").unwrap(),
+      liquid_core::value!("Hello World 21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TitleCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("Hello World21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-shouty-kebab")]
+  fn shouty_kebab_case() {
+    assert_eq!(
+      liquid_core::call_filter!(ShoutyKebabCase, "abc").unwrap(),
+      liquid_core::value!("ABC")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutyKebabCase, "hello world 21").unwrap(),
+      liquid_core::value!("HELLO-WORLD-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutyKebabCase, "hello_world_21").unwrap(),
+      liquid_core::value!("HELLO-WORLD-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ShoutyKebabCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("HELLO-WORLD21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-train")]
+  fn train_case() {
+    assert_eq!(
+      liquid_core::call_filter!(TrainCase, "abc").unwrap(),
+      liquid_core::value!("Abc")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TrainCase, "hello world 21").unwrap(),
+      liquid_core::value!("Hello-World-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TrainCase, "hello_world_21").unwrap(),
+      liquid_core::value!("Hello-World-21")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TrainCase, "HelloWorld21").unwrap(),
+      liquid_core::value!("Hello-World21")
+    );
+  }
+
+  #[test]
+  fn configured_case() {
+    let options = Options {
+      digit_boundary: true,
+      ..Options::default()
+    };
+    let filter = ConfiguredCase::new("snake", HeckCase::Snake, Some(options));
+    assert_eq!(filter.name(), "snake");
+    assert_eq!(
+      liquid_core::call_filter!(filter, "HelloWorld21").unwrap(),
+      liquid_core::value!("hello_world_21")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-snake")]
+  fn each_line() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .build()
+      .unwrap()
+      .parse("{{ text | snakecase: each_line: true }}|{{ text | snakecase }}")
+      .unwrap();
+    let globals = liquid::object!({ "text": "First Name\r\nLast Name\n\nAge\n" });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "first_name\r\nlast_name\n\nage\n|first_name_last_name_age"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-title")]
+  fn words() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(TitleCase)
+      .build()
+      .unwrap()
+      .parse("{{ text | titlecase: words: 1 }}|{{ text | titlecase: words: 2, each_line: true }}")
+      .unwrap();
+    let globals = liquid::object!({ "text": "the quick_fox\njumps over it" });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "The quick_fox\njumps over it|The Quick_fox\nJumps Over it"
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(TitleCase)
+      .build()
+      .unwrap()
+      .parse("{{ text | titlecase: words: -1 }}")
+      .unwrap();
+    assert!(template.render(&globals).is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-title")]
+  fn markdown() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(TitleCase)
+      .build()
+      .unwrap()
+      .parse(
+        "{% capture doc %}{% raw %}## getting started\n\
+         \n\
+         > install the [command line](https://example.com/cli_setup) with `cargo install`:\n\
+         \n\
+         ```sh\n\
+         cargo install liquid-heck\n\
+         ```\n\
+         \n\
+         - **read** the <https://example.com/read_me> | then write\n\
+         1. first step\n\
+         ---\n\
+         [cli]: https://example.com/some_page\n{% endraw %}{% endcapture %}\
+         {{ doc | titlecase: markdown: true }}",
+      )
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "## Getting Started\n\
+       \n\
+       > Install The [Command Line](https://example.com/cli_setup) With `cargo install`:\n\
+       \n\
+       ```sh\n\
+       cargo install liquid-heck\n\
+       ```\n\
+       \n\
+       - **Read** The <https://example.com/read_me> | Then Write\n\
+       1. First Step\n\
+       ---\n\
+       [cli]: https://example.com/some_page\n"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-title")]
+  fn lang() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(TitleCase)
+      .build()
+      .unwrap();
+    let template = parser
+      .parse("{{ text | titlecase: lang: 'fr' }}|{{ text | titlecase }}")
+      .unwrap();
+    let globals = liquid::object!({ "text": "le rouge et le noir d'antan" });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "Le Rouge et le Noir d'Antan|Le Rouge Et Le Noir D Antan"
+    );
+    let template = parser.parse("{{ text | titlecase: lang: 'xx' }}").unwrap();
+    assert!(template.render(&globals).is_err());
+    assert!(TitleCase
+      .keyword_parameters()
+      .iter()
+      .any(|parameter| parameter.name == "lang"));
+  }
+
+  #[test]
+  #[cfg(feature = "filter-snake")]
+  fn heck_config() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .build()
+      .unwrap();
+    let render = |config| {
+      let globals = liquid::object!({ "field": "item2", "missing": nil, "heck_config": config });
+      parser
+        .parse(
+          "{{ field | snakecase }} {% assign s = missing | snakecase %}\
+           {% if s == nil %}nil{% else %}'{{ s }}'{% endif %}",
+        )?
+        .render(&globals)
+    };
+    assert_eq!(render(liquid_core::value!({})).unwrap(), "item2 ''");
+    assert_eq!(
+      render(liquid_core::value!({ "digit_boundary": true, "nil_policy": "nil" })).unwrap(),
+      "item_2 nil"
+    );
+    assert!(render(liquid_core::value!({ "nil_policy": "error" })).is_err());
+    assert!(render(liquid_core::value!({ "nil_policy": "skip" })).is_err());
+    let error = render(liquid_core::value!({ "leading_article": "front" }))
+      .unwrap_err()
+      .to_string();
+    assert!(error.contains("option=leading_article"), "{error}");
+    assert!(
+      error.contains("expected=\"keep\", \"drop\" or \"move\""),
+      "{error}"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-upper-camel")]
+  fn with_options() {
+    let options = Options {
+      acronyms: vec!["ID".into()],
+      ..Options::default()
+    };
+    let filter = UpperCamelCase::with_options(options);
+    assert_eq!(filter.name(), "uppercamelcase");
+    assert_eq!(
+      liquid_core::call_filter!(filter, "user_id").unwrap(),
+      liquid_core::value!("UserID")
+    );
+    let filter = filter.named("type_name");
+    assert_eq!(filter.name(), "type_name");
+    assert_eq!(
+      liquid_core::call_filter!(UpperCamelCase, "user_id").unwrap(),
+      liquid_core::value!("UserId")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-title")]
+  fn max_input_length() {
+    let long = "word ".repeat(1000);
+    assert!(liquid_core::call_filter!(TitleCase, long.as_str()).is_ok());
+    let filter = TitleCase::with_options(Options::builder().max_input_length(100).build());
+    let error = liquid_core::call_filter!(filter, long.as_str()).unwrap_err();
+    assert!(error.to_string().contains("Input too long"));
+
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(TitleCase)
+      .build()
+      .unwrap();
+    let template = parser.parse("{{ text | titlecase }}").unwrap();
+    let globals = liquid::object!({ "text": long, "heck_config": { "max_input_length": 10 } });
+    assert!(template.render(&globals).is_err());
+  }
+
+  #[test]
+  #[cfg(all(feature = "filter-snake", feature = "filter-title"))]
+  fn clean_whitespace() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .filter(TitleCase)
+      .build()
+      .unwrap();
+    let template = parser
+      .parse("{{ id | snakecase }}|{{ title | titlecase: words: 1 }}")
+      .unwrap();
+    let render = |config| {
+      let globals = liquid::object!({
+        "id": " \t_user id ",
+        "title": "  the   quick\tfox ",
+        "heck_config": config,
+      });
+      template.render(&globals).unwrap()
+    };
+    assert_eq!(
+      render(liquid_core::value!({ "preserve_underscores": true })),
+      "user_id|The   quick\tfox "
+    );
+    assert_eq!(
+      render(liquid_core::value!({
+        "preserve_underscores": true,
+        "trim": true,
+        "collapse_ws": true,
+      })),
+      "_user_id|The quick fox"
+    );
+  }
+
+  #[test]
+  #[cfg(all(feature = "filter-snake", feature = "filter-kebab"))]
+  fn limit() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .filter(KebabCase)
+      .build()
+      .unwrap();
+    let render = |template: &str| {
+      let globals = liquid::object!({
+        "name": "Customer Billing Service Worker Events Archive Table",
+      });
+      parser.parse(template)?.render(&globals)
+    };
+    assert_eq!(
+      render("{{ name | snakecase: limit: 30 }}").unwrap(),
+      "customer_billing_service_worke"
+    );
+    assert_eq!(
+      render("{{ name | snakecase: limit: 'oracle', limit_strategy: 'abbrev' }}").unwrap(),
+      "cus_bil_ser_wor_eve_arc_tab"
+    );
+    let hashed = render("{{ name | kebabcase: limit: 'oracle', limit_strategy: 'hash' }}").unwrap();
+    assert_eq!(hashed.len(), 25);
+    assert!(hashed.starts_with("customer-billing-"), "{hashed}");
+    assert_eq!(
+      render("{{ name | snakecase: limit: 'postgres' }}").unwrap(),
+      "customer_billing_service_worker_events_archive_table"
+    );
+    assert!(render("{{ name | snakecase: limit: 'mysql' }}").is_err());
+    assert!(render("{{ name | snakecase: limit: -1 }}").is_err());
+    assert!(render("{{ name | snakecase: limit: 9, limit_strategy: 'cut' }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-snake")]
+  fn control_chars() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .build()
+      .unwrap()
+      .parse("{{ name | snakecase }}")
+      .unwrap();
+    let render = |style| {
+      let globals = liquid::object!({
+        "name": "Bell\u{7}ringer\u{1B}[0m",
+        "heck_config": { "control_chars": style },
+      });
+      template.render(&globals)
+    };
+    assert_eq!(render("keep").unwrap(), "bell_ringer_0m");
+    assert_eq!(render("strip").unwrap(), "bellringer_0m");
+    assert_eq!(render("replace").unwrap(), "bell_ringer_0m");
+    let error = render("error").unwrap_err().to_string();
+    assert!(error.contains("Control character in input"), "{error}");
+    assert!(error.contains("U+0007"), "{error}");
+    assert!(error.contains("offset=4"), "{error}");
+    assert!(render("drop").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-snake")]
+  fn strip_soft_hyphens() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .build()
+      .unwrap()
+      .parse("{{ 'Hyphen\u{AD}ation Rules' | snakecase }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "hyphen_ation_rules"
+    );
+    let globals = liquid::object!({ "heck_config": { "strip_soft_hyphens": true } });
+    assert_eq!(template.render(&globals).unwrap(), "hyphenation_rules");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-kebab")]
+  fn punctuation() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(KebabCase)
+      .build()
+      .unwrap();
+    let template = parser.parse("{{ title | kebabcase }}").unwrap();
+    let render = |title, config| {
+      let globals = liquid::object!({ "title": title, "heck_config": config });
+      template.render(&globals)
+    };
+    assert_eq!(
+      render("C++ Guide", liquid_core::value!({})).unwrap(),
+      "c-guide"
+    );
+    assert_eq!(
+      render(
+        "C++ Guide",
+        liquid_core::value!({ "keep_punctuation": "+'" })
+      )
+      .unwrap(),
+      "c++-guide"
+    );
+    let strict = liquid_core::value!({ "keep_punctuation": "+", "punctuation": "error" });
+    assert_eq!(
+      render("C++ Guide_v2", strict.clone()).unwrap(),
+      "c++-guide-v2"
+    );
+    let error = render("C# Guide", strict).unwrap_err().to_string();
+    assert!(error.contains("Punctuation in input"), "{error}");
+    assert!(error.contains("'#'"), "{error}");
+  }
+
+  #[test]
+  #[cfg(all(feature = "number-words", feature = "filter-snake"))]
+  fn spell_numbers() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SnakeCase)
+      .build()
+      .unwrap()
+      .parse("{{ '3D model 2' | snakecase }}")
+      .unwrap();
+    let render = |style| {
+      let globals = liquid::object!({ "heck_config": { "spell_numbers": style } });
+      template.render(&globals)
+    };
+    assert_eq!(render("keep").unwrap(), "3d_model_2");
+    assert_eq!(render("leading").unwrap(), "three_d_model_2");
+    assert_eq!(render("all").unwrap(), "three_d_model_two");
+    assert!(render("some").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-kebab")]
+  fn units() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(KebabCase)
+      .build()
+      .unwrap()
+      .parse("{{ 'Price: $19.99 (A4 size), 6 x 500 ml' | kebabcase }}")
+      .unwrap();
+    let render = |config| {
+      let globals = liquid::object!({ "heck_config": config });
+      template.render(&globals)
+    };
+    assert_eq!(
+      render(liquid_core::value!({})).unwrap(),
+      "price-19-99-a4-size-6-x-500-ml"
+    );
+    assert_eq!(
+      render(liquid_core::value!({ "units": "spell" })).unwrap(),
+      "price-19-99-dollars-a4-size-6-x-500-milliliters"
+    );
+    assert_eq!(
+      render(liquid_core::value!({ "units": "keep", "digit_boundary": true })).unwrap(),
+      "price-19.99-a-4-size-6-x-500ml"
+    );
+    assert!(render(liquid_core::value!({ "units": "round" })).is_err());
+  }
+
+  #[test]
+  #[cfg(all(feature = "filter-kebab", feature = "filter-title"))]
+  fn preprocessed_once() {
+    // A second pass would spell the currency of `$2 dollars` again.
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(KebabCase)
+      .filter(TitleCase)
+      .build()
+      .unwrap()
+      .parse("{{ '$$2' | kebabcase }}|{{ 'the $$2' | titlecase }}")
+      .unwrap();
+    let globals = liquid::object!({
+      "heck_config": { "units": "spell", "leading_article": "drop" },
+    });
+    assert_eq!(template.render(&globals).unwrap(), "2-dollars|2 Dollars");
+  }
+
+  #[test]
+  fn from_heck_case() {
+    let mut builder = liquid::ParserBuilder::with_stdlib();
+    for name in ["kebab", "shouty_snake"] {
+      let case: HeckCase = name.parse().unwrap();
+      builder = builder.filter(Box::<dyn ParseFilter>::from(case));
+    }
+    let template = builder
+      .build()
+      .unwrap()
+      .parse("{{ 'User ID' | kebabcase }} {{ 'user id' | shoutysnakecase }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "user-id USER_ID"
+    );
+    let filter = ConfiguredCase::from(HeckCase::Title);
+    assert_eq!(filter.name(), "titlecase");
+    assert_eq!(filter.description(), "Convert the string to title case.");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-case")]
+  fn to_case() {
+    assert_eq!(
+      liquid_core::call_filter!(ToCase, "userId", "snake").unwrap(),
+      liquid_core::value!("user_id")
+    );
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(ToCase)
+      .build()
+      .unwrap();
+    let globals = liquid::object!({ "settings": { "naming": "kebabcase" } });
+    let render = |template| parser.parse(template)?.render(&globals);
+    assert_eq!(
+      render("{{ 'userId' | case: to: 'shouty_snake', from: 'camel' }}").unwrap(),
+      "USER_ID"
+    );
+    assert_eq!(
+      render("{{ 'userId' | case: settings.naming }}").unwrap(),
+      "user-id"
+    );
+    assert!(render("{{ 'user_id' | case: to: 'snake', from: 'camel' }}").is_err());
+    assert!(render("{{ 'userId' | case: 'snake', to: 'kebab' }}").is_err());
+    assert!(render("{{ 'userId' | case }}").is_err());
+    assert!(render("{{ 'userId' | case: 'upcase' }}").is_err());
+  }
+}
@@ -0,0 +1,134 @@
+//! Redaction of the converted values in the `tracing` spans and `log`
+//! records. Only available with the `tracing` or `log` feature.
+use liquid_core::ValueView;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How the filter inputs and outputs appear in the instrumentation.
+///
+/// The converted strings can hold emails or names: [`Redaction::Elide`]
+/// keeps them out of the spans and logs, while keeping their length and the
+/// filter names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Redaction {
+  /// The values as is: `"jane.doe@example.com"`.
+  #[default]
+  None,
+  /// Only the length of the values: `<20 chars>`.
+  Elide,
+}
+
+static REDACTION: AtomicU8 = AtomicU8::new(Redaction::None as u8);
+
+/// Set how the filter inputs and outputs appear in the instrumentation, for
+/// the whole process.
+///
+/// ```
+/// liquid_heck::set_redaction(liquid_heck::Redaction::Elide);
+/// ```
+pub fn set_redaction(redaction: Redaction) {
+  REDACTION.store(redaction as u8, Ordering::Relaxed);
+}
+
+/// The redaction set by [`set_redaction`].
+pub fn redaction() -> Redaction {
+  match REDACTION.load(Ordering::Relaxed) {
+    1 => Redaction::Elide,
+    _ => Redaction::None,
+  }
+}
+
+/// A value displayed as the current [`Redaction`] requires.
+pub(crate) enum Redacted<'a> {
+  /// A string of the `log` records.
+  #[cfg_attr(not(feature = "log"), allow(dead_code))]
+  Str(&'a str),
+  /// A filter input of the `tracing` spans.
+  #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+  Value(&'a dyn ValueView),
+}
+
+impl fmt::Display for Redacted<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (redaction(), self) {
+      (Redaction::None, Redacted::Str(s)) => write!(f, "{s:?}"),
+      (Redaction::None, Redacted::Value(value)) => write!(f, "{value:?}"),
+      (Redaction::Elide, Redacted::Str(s)) => write!(f, "<{} chars>", s.chars().count()),
+      (Redaction::Elide, Redacted::Value(value)) => {
+        write!(f, "<{} chars>", value.to_kstr().chars().count())
+      }
+    }
+  }
+}
+
+/// Serializes the tests which change or depend on the process-wide
+/// [`Redaction`].
+#[cfg(test)]
+pub(crate) static REDACTION_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// The messages `log`ged on the current thread while running `f`, without
+/// redaction.
+#[cfg(all(test, feature = "log"))]
+pub(crate) fn captured_logs(f: impl FnOnce()) -> Vec<String> {
+  use std::cell::RefCell;
+
+  thread_local! {
+    static MESSAGES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+  }
+
+  struct Capture;
+
+  impl log::Log for Capture {
+    fn enabled(&self, _metadata: &log::Metadata<'_>) -> bool {
+      true
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+      MESSAGES.with(|messages| messages.borrow_mut().push(record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+  }
+
+  static CAPTURE: Capture = Capture;
+  // Another test may have installed the logger already.
+  let _ = log::set_logger(&CAPTURE);
+  log::set_max_level(log::LevelFilter::Trace);
+  let _lock = REDACTION_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+  MESSAGES.with(|messages| messages.borrow_mut().clear());
+  f();
+  MESSAGES.with(RefCell::take)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  /// Restores the redaction when the test ends, even when it fails.
+  struct RestoreRedaction {
+    previous: Redaction,
+    _lock: std::sync::MutexGuard<'static, ()>,
+  }
+
+  impl Drop for RestoreRedaction {
+    fn drop(&mut self) {
+      set_redaction(self.previous);
+    }
+  }
+
+  #[test]
+  fn redacted() {
+    let _restore = RestoreRedaction {
+      _lock: REDACTION_LOCK.lock().unwrap_or_else(|e| e.into_inner()),
+      previous: redaction(),
+    };
+    let value = liquid_core::Value::scalar("jane doe");
+    assert_eq!(Redacted::Str("jane doe").to_string(), "\"jane doe\"");
+    set_redaction(Redaction::Elide);
+    assert_eq!(redaction(), Redaction::Elide);
+    assert_eq!(Redacted::Str("jane doe").to_string(), "<8 chars>");
+    assert_eq!(Redacted::Value(&value).to_string(), "<8 chars>");
+  }
+}
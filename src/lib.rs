@@ -18,6 +18,168 @@
 //! | TrainCase         | traincase        |
 //! | UpperCamelCase    | uppercamelcase   |
 //!
+//! The case filters accept keyword arguments:
+//!
+//! * `each_line: true` converts each line of the input on its own and keeps
+//!   the line breaks: `{{ list | snakecase: each_line: true }}`.
+//! * `words: N` only converts the first `N` words and leaves the rest of the
+//!   string as is: `{{ title | titlecase: words: 1 }}`.
+//! * `lang: "fr"` or `lang: "es"` follows the title-casing rules of French
+//!   or Spanish, lowercase particles and elisions:
+//!   `{{ "la vie de l'été" | titlecase: lang: "fr" }}` gives `La Vie de l'Été`.
+//!   `lang: "de"` uppercases `ß` as `ẞ` and keeps the capital of the German
+//!   nouns in Title Case: `{{ "die große Straße" | titlecase: lang: "de" }}`.
+//! * `markdown: true` converts a Markdown document line by line, keeping
+//!   the block markers, the fenced code blocks, the inline code, the link
+//!   destinations and the markup as written, e.g. the headings of a
+//!   `{% capture %}` block: `{{ doc | titlecase: markdown: true }}`.
+//! * `limit: N` shortens the output to `N` bytes, or to the limit of a
+//!   preset: `"postgres"`, `"k8s"` and `"s3"` (63), `"oracle"` (30).
+//!   `limit_strategy:` chooses how: `"truncate"` (the default), `"abbrev"`
+//!   cutting all the words to the same length, or `"hash"` cutting after a
+//!   whole word and appending a hash of the name, so that distinct names
+//!   stay distinct ([`shorten_unique()`]):
+//!   `{{ table | snakecase: limit: "oracle", limit_strategy: "hash" }}`.
+//!
+//! `case` (`ToCase`) converts to a case named at render time, by a variable
+//! or a literal: `{{ name | case: settings.naming }}`, or
+//! `{{ name | case: to: "snake", from: "camel" }}` to also check the case of
+//! the input. The arguments of `case`, `skip_if_opaque` and the target
+//! filters are accepted positionally or by keyword:
+//! `{{ name | helm_name: 40 }}` or `{{ name | helm_name: max_length: 40 }}`.
+//! Every argument, of the filters and of the tags, is an expression evaluated
+//! at render time: `{{ title | titlecase: lang: page.lang }}` or
+//! `{% heck_defaults acronyms: site.acronyms %}` work as their literal forms.
+//!
+//! The modifier filters adjust an already converted string:
+//!
+//! | ... in `filter()`      | ... in templates         |
+//! |------------------------|--------------------------|
+//! | Acronymize             | acronymize               |
+//! | DowncaseKeepSeparators | downcase_keep_separators |
+//! | EscapeReserved         | escape_reserved          |
+//! | ExpandAcronyms         | expand_acronyms          |
+//! | NormalizeHomoglyphs    | normalize_homoglyphs     |
+//! | Screaming              | screaming                |
+//! | SkipIfOpaque           | skip_if_opaque           |
+//! | SpellSymbols           | spell_symbols            |
+//! | StripDiacritics        | strip_diacritics         |
+//! | TruncateWordsCase      | truncate_words_case      |
+//! | WrapIdent              | wrap_ident               |
+//!
+//! `{{ "file" | count_with: n }}` (`CountWith`) renders `1 file` or `3 files`.
+//!
+//! Each case filter has a `_values` variant (`CaseValues`) converting the
+//! string values of an object or an array, and of the nested ones with
+//! `recursive: true`: `{{ payload | shoutysnakecase_values }}`. `restyle`
+//! (`Restyle`) converts both the keys and the values of an object, to
+//! independent cases:
+//! `{{ config | restyle: keys: "lowercamelcase", values: "shoutysnakecase" }}`.
+//! The `convert_keys` tag (`ConvertKeysTag`) replaces a variable with its
+//! key-converted copy for the rest of the template:
+//! `{% convert_keys payload to: "camel" %}`. Besides the filter names, the
+//! cases are accepted by their short name: `camel`, `pascal`, `snake`, ...
+//! [`convert_value`] does the same conversions from Rust, e.g. to
+//! preprocess the globals, with a [`KeyPolicy`] and a [`ValuePolicy`].
+//!
+//! The `heck_defaults` tag (`HeckDefaultsTag`) sets the options of all the
+//! following conversions of the render, includes included:
+//! `{% heck_defaults acronyms: "ID,URL" digit_boundary: true ascii_mode: true %}`.
+//! `trim: true` and `collapse_ws: true` clean the whitespace of the input
+//! first, e.g. of the cells pasted from a spreadsheet, and
+//! `strip_soft_hyphens: true` removes the soft hyphens of a text copied from
+//! a PDF, and `strip_invisible: true` the zero-width characters and the
+//! bidi controls, also before the target filters. `control_chars` strips
+//! the other control characters (`"strip"`), marks them with `U+FFFD`
+//! (`"replace"`), or fails the render naming the first one and its byte
+//! offset (`"error"`), for the identifiers built from untrusted input.
+//! `keep_punctuation: "+'"` keeps these characters in the words
+//! (`C++ Guide` gives `c++-guide`), and `punctuation: "error"` fails the
+//! render on the other punctuation instead of dropping it.
+//! `spell_symbols: true` spells out `&`, `@`, `%`, ... as words first
+//! (`R&D` gives `r-and-d`); the `spell_symbols` filter takes a custom mapping.
+//! `units: "spell"` spells out the currency symbols and the unit
+//! abbreviations after their amount (`$19.99` gives `19-99-dollars`), and
+//! `units: "keep"` keeps an amount and its unit as one word (`19.99`,
+//! `500ml`).
+//! `strip_diacritics: true` drops the accents first (`Señor` gives
+//! `senor`), keeping the letters `ascii_mode` would drop.
+//! Without the tag, the options come from the `heck_config` global
+//! ([`HECK_CONFIG_GLOBAL`]), which also sets what the filters return for a
+//! nil input.
+//!
+//! The target filters make a string a valid name for another system:
+//!
+//! | ... in `filter()` | ... in templates  |
+//! |-------------------|-------------------|
+//! | AnchorDocusaurus  | anchor_docusaurus |
+//! | AnchorMdbook      | anchor_mdbook     |
+//! | CanonicalHeader   | canonical_header  |
+//! | CfnLogicalId      | cfn_logical_id    |
+//! | DockerRepo        | docker_repo       |
+//! | DockerTag         | docker_tag        |
+//! | EmailLocal        | email_local       |
+//! | EventName         | event_name        |
+//! | GcpName           | gcp_name          |
+//! | GitRef            | git_ref           |
+//! | HelmName          | helm_name         |
+//! | I18nKey           | i18n_key          |
+//! | IamName           | iam_name          |
+//! | InNamespace       | in_namespace      |
+//! | LambdaName        | lambda_name       |
+//! | LatexCommand      | latex_command     |
+//! | LatexLabel        | latex_label       |
+//! | MavenArtifact     | maven_artifact    |
+//! | MavenGroup        | maven_group       |
+//! | PyDistToModule    | py_dist_to_module |
+//! | PromLabel         | prom_label        |
+//! | PromMetric        | prom_metric       |
+//! | PypiName          | pypi_name         |
+//! | RstLabel          | rst_label         |
+//! | SafeFilename      | safe_filename     |
+//! | UniqueSlug        | unique_slug       |
+//!
+//! The preset filters name the items of generated code by the conventions
+//! of a language:
+//!
+//! | ... in `filter()` | ... in templates |
+//! |-------------------|------------------|
+//! | BuilderMethod     | builder_method   |
+//! | ConstName         | const_name       |
+//! | EnumVariant       | enum_variant     |
+//! | GetterName        | getter_name      |
+//! | PredicateName     | predicate_name   |
+//! | SetterName        | setter_name      |
+//! | TestName          | test_name        |
+//!
+//! The comparison and array filters match identifiers regardless of their
+//! case:
+//!
+//! | ... in `filter()` | ... in templates   |
+//! |-------------------|--------------------|
+//! | CaseEq            | case_eq            |
+//! | CaselessEq        | caseless_eq        |
+//! | DedupeIdents      | dedupe_idents      |
+//! | GroupByPrefix     | group_by_prefix    |
+//! | SortCi            | sort_ci            |
+//! | SortCollate       | sort_collate       |
+//! | SortNaturalIdent  | sort_natural_ident |
+//!
+//! `case_eq` compares the words, `caseless_eq` the whole strings after
+//! Unicode case folding ([`caseless_eq()`]): `Straße` matches `STRASSE`.
+//!
+//! The text filters convert the identifiers embedded in a longer text,
+//! leaving the rest as written:
+//!
+//! | ... in `filter()`  | ... in templates    |
+//! |--------------------|---------------------|
+//! | ConvertWithin      | convert_within      |
+//! | RenamePlaceholders | rename_placeholders |
+//!
+//! `{{ "Hello {user_name}" | rename_placeholders: "camel" }}` renders
+//! `Hello {userName}`, and ``{{ text | convert_within: "`", "`", "snake" }}``
+//! only converts the inline code spans of the text.
+//!
 //! # Example
 //!
 //! To use the case conversion filters in your Liquid templates, you first
@@ -25,6 +187,8 @@
 //!
 //!
 //! ```
+//! # #[cfg(all(feature = "filter-upper-camel", feature = "filter-snake", feature = "filter-train"))]
+//! # {
 //! use liquid_heck::{UpperCamelCase,SnakeCase,TrainCase};
 //!
 //! let template = liquid::ParserBuilder::with_stdlib()
@@ -40,338 +204,340 @@
 //!
 //! let output = template.render(&globals).unwrap();
 //! assert_eq!(output, "SomeTextToConvert some_text_to_convert Some-Text-To-Convert".to_string());
+//! # }
 //! ```
 //!
+//! Each case filter also has a `with_options()` constructor returning a
+//! [`ConfiguredCase`] honoring [`Options`], which [`ConfiguredCase::named`]
+//! registers under another name, e.g. a strict and a lenient `snakecase`.
+//! [`HeckCase::with_options`], or `Box::<dyn ParseFilter>::from(case)`,
+//! builds the filter of a case chosen at runtime.
+//! [`Options::from_env`] reads the options from the `LIQUID_HECK_*`
+//! environment variables, for template services configured by their
+//! container.
+//!
+//! Downstream crates can define their own conversion filters with
+//! [`define_case_filter!`].
+//!
+//! The conversions are also available as plain functions in [`conversion`],
+//! which only depends on `core` and `alloc`.
+//! [`conversion::to_title_case_stream`] converts whole documents chunk by
+//! chunk, with bounded memory; the filters do the same above 64 KiB.
+//! [`segment`] iterates over the words exactly as the conversions split
+//! them, for linters or custom filters. [`HeckCase`] names the cases, and
+//! parses the case names the filters, tags and command line accept;
+//! [`convert`] converts to a case chosen at runtime exactly as the templates
+//! do.
+//!
 //! # Feature
 //!
+//! * **std** (default) : the Liquid filters. Disable it to use the
+//!   [`conversion`] functions in a `#![no_std]` environment.
+//! * **liquid** : re-exports the matching [liquid](https://crates.io/crates/liquid)
+//!   version and adds `parser_with_heck()`, a ready parser with the stdlib and
+//!   all the heck filters. Without it the crate only depends on `liquid-core`.
+//! * **all-filters** (default) : all the filters and tags below. Disable the
+//!   default features and pick only the ones you register to shrink the
+//!   compiled size: **filter-acronymize**, **filter-anchor-docusaurus**,
+//!   **filter-anchor-mdbook**, **filter-aws**, **filter-builder-method**,
+//!   **filter-canonical-header**, **filter-case**, **filter-case-eq**,
+//!   **filter-caseless-eq**, **filter-const-name**, **filter-convert-within**,
+//!   **filter-count-with**, **filter-dedupe-idents**, **filter-docker**,
+//!   **filter-downcase-keep-separators**, **filter-email-local**,
+//!   **filter-enum-variant**, **filter-escape-reserved**,
+//!   **filter-event-name**, **filter-expand-acronyms**, **filter-gcp**,
+//!   **filter-getter-name**, **filter-git-ref**, **filter-group-by-prefix**,
+//!   **filter-helm**, **filter-i18n-key**, **filter-in-namespace**,
+//!   **filter-kebab**, **filter-latex**, **filter-lower-camel**,
+//!   **filter-maven**, **filter-predicate-name**, **filter-prometheus**,
+//!   **filter-pypi**, **filter-rename-placeholders**, **filter-restyle**,
+//!   **filter-rst-label**, **filter-safe-filename**, **filter-screaming**,
+//!   **filter-setter-name**, **filter-shouty-kebab**, **filter-shouty-snake**,
+//!   **filter-skip-if-opaque**, **filter-snake**, **filter-sort-ci**,
+//!   **filter-sort-natural-ident**, **filter-spell-symbols**,
+//!   **filter-strip-diacritics**, **filter-test-name**, **filter-title**,
+//!   **filter-train**, **filter-truncate-words-case**, **filter-unique-slug**,
+//!   **filter-upper-camel**, **filter-values**, **filter-wrap-ident**,
+//!   **tag-convert-keys**, **tag-heck-defaults**.
+//! * **cli** : the `liquid-heck` binary, converting its arguments or stdin
+//!   lines (`liquid-heck convert snakecase "Some Text"`) and rendering
+//!   template files with JSON or YAML globals (`liquid-heck render page.liquid
+//!   globals.yaml`). `-o NAME=VALUE` sets an [`Options`] field.
+//! * **number-words** : the `spell_numbers` option, spelling out the
+//!   numbers as English words: `"leading"` turns `3d_model` into
+//!   `three_d_model` for the targets forbidding a leading digit.
+//! * **diacritics** : the `strip_diacritics` option, dropping the accents
+//!   of the letters through their Unicode decomposition: `Crème Brûlée`
+//!   becomes `creme_brulee`. Pulled in by **filter-strip-diacritics**.
+//! * **homoglyphs** : the `normalize_homoglyphs` option and filter,
+//!   replacing the Cyrillic, Greek and fullwidth lookalikes of the ASCII
+//!   letters and digits, against the spoofing of the identifiers and slugs
+//!   generated from user input: `pаypаl`, with Cyrillic `а`, becomes
+//!   `paypal`.
+//! * **icu** : the `sort_collate` filter, sorting an array by the collation
+//!   rules of a locale with [ICU4X](https://crates.io/crates/icu_collator):
+//!   `{{ glossary | sort_collate: "de" }}` sorts `Äpfel` next to `Apfel`.
+//! * **heck04** : pin the output of Heck 0.4, the default: only the ASCII
+//!   letters and digits are word characters, `Crème Brûlée` becomes
+//!   `cr_me_br_l_e`.
+//! * **heck05** : switch to the output of Heck 0.5, which considers any
+//!   Unicode letter or digit a word character: `Crème Brûlée` becomes
+//!   `crème_brûlée`. Mutually exclusive with **heck04**.
+//! * **serde** : deserialize [`Options`] and the filter `Config` used by
+//!   `FilterSet::from_config()`.
+//! * **testing** : the `assert_filter!` macro and the `testing` module, to
+//!   check conversions with diffs in the tests of downstream crates.
 //! * **tracing** : instruments all the conversion methods using [tracing](https://crates.io/crates/tracing)
-use heck::{
-  ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase,
-  ToTrainCase, ToUpperCamelCase,
-};
-use liquid_core::{Filter, Result, Runtime, Value, ValueView};
-use liquid_derive::{Display_filter, FilterReflection, ParseFilter};
-
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "uppercamelcase",
-  description = "Convert the string to UpperCamelCase.",
-  parsed(UpperCamelCaseFilter)
-)]
-pub struct UpperCamelCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "uppercamelcase"]
-struct UpperCamelCaseFilter;
-impl Filter for UpperCamelCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_upper_camel_case()))
-  }
-}
+//! * **log** : logs the input and output of all the conversion methods using
+//!   [log](https://crates.io/crates/log). Mutually exclusive with **tracing**.
+//!   With either feature, `set_redaction()` elides the logged
+//!   values, keeping their length and the filter names, for production use.
+//! * **wasm** : `wasm` module with wasm-bindgen bindings for the conversion
+//!   functions and a template rendering helper, for `wasm32-unknown-unknown`
+//!   builds.
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "lowercamelcase",
-  description = "Convert the string to lowerCamelCase.",
-  parsed(LowerCamelCaseFilter)
-)]
-pub struct LowerCamelCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "lowercamelcase"]
-struct LowerCamelCaseFilter;
-impl Filter for LowerCamelCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_lower_camel_case()))
-  }
-}
-
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "snakecase",
-  description = "Convert the string to snake-case.",
-  parsed(SnakeCaseFilter)
-)]
-pub struct SnakeCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "snakecase"]
-struct SnakeCaseFilter;
-impl Filter for SnakeCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_snake_case()))
-  }
-}
+#[cfg(all(feature = "tracing", feature = "log"))]
+compile_error!("features `tracing` and `log` are mutually exclusive");
 
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "kebabcase",
-  description = "Convert the string to kebab-case.",
-  parsed(KebabCaseFilter)
-)]
-pub struct KebabCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "kebabcase"]
-struct KebabCaseFilter;
-impl Filter for KebabCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_kebab_case()))
-  }
-}
-
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "shoutysnakecase",
-  description = "Convert the string to SHOUTY_SNAKE_CASE.",
-  parsed(ShoutySnakeCaseFilter)
-)]
-pub struct ShoutySnakeCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "shoutysnakecase"]
-struct ShoutySnakeCaseFilter;
-impl Filter for ShoutySnakeCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_shouty_snake_case()))
-  }
-}
-
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "titlecase",
-  description = "Convert the string to title case.",
-  parsed(TitleCaseFilter)
-)]
-pub struct TitleCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "titlecase"]
-struct TitleCaseFilter;
-impl Filter for TitleCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_title_case()))
-  }
-}
+#[cfg(all(feature = "heck04", feature = "heck05"))]
+compile_error!("features `heck04` and `heck05` are mutually exclusive");
 
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "shoutykebabcase",
-  description = "Convert the string to SHOUTY-KEBAB-CASE.",
-  parsed(ShoutyKebabCaseFilter)
-)]
-pub struct ShoutyKebabCase;
-#[derive(Debug, Default, Display_filter)]
-#[name = "shoutykebabcase"]
-struct ShoutyKebabCaseFilter;
-impl Filter for ShoutyKebabCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_shouty_kebab_case()))
-  }
-}
+extern crate alloc;
 
-#[derive(Clone, ParseFilter, FilterReflection)]
-#[filter(
-  name = "traincase",
-  description = "Convert the string to Train-Case.",
-  parsed(TrainCaseFilter)
-)]
-pub struct TrainCase;
+#[cfg(feature = "std")]
+mod args;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod arrays;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod compare;
+#[cfg(feature = "std")]
+mod config;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub mod conversion;
+#[cfg(feature = "std")]
+mod docs;
+#[cfg(feature = "std")]
+mod filters;
+#[cfg(all(feature = "std", any(feature = "tracing", feature = "log")))]
+mod instrument;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+mod limit;
+#[cfg(feature = "std")]
+mod macros;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod modifiers;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod objects;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+mod options;
+#[cfg(feature = "liquid")]
+mod parser;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod plural;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod presets;
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+mod reserved;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+mod spelling;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod tags;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod targets;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code, unused_imports))]
+mod text;
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+mod translit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg_attr(not(feature = "all-filters"), allow(dead_code))]
+mod words;
 
-#[derive(Debug, Default, Display_filter)]
-#[name = "traincase"]
-struct TrainCaseFilter;
+#[cfg(feature = "std")]
+pub use compare::caseless_eq;
+pub use conversion::{convert, HeckCase, UnknownCase};
+#[cfg(feature = "std")]
+pub use limit::shorten_unique;
+#[cfg(feature = "std")]
+pub use options::InvalidOption;
+#[cfg(feature = "number-words")]
+pub use options::NumberStyle;
+pub use options::{
+  AcronymStyle, ArticleStyle, CaseOptions, CjkStyle, ControlStyle, Lang, Options, OptionsBuilder,
+  PunctuationStyle, TokenStyle, UnitStyle, DEFAULT_MAX_INPUT_LENGTH,
+};
+pub use words::segment;
 
-impl Filter for TrainCaseFilter {
-  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_runtime)))]
-  fn evaluate(&self, input: &dyn ValueView, _runtime: &dyn Runtime) -> Result<Value> {
-    let s = input.to_kstr();
-    let s = s.as_str();
-    Ok(Value::scalar(s.to_train_case()))
-  }
+/// Items used by the exported macros.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub mod __private {
+  pub use crate::filters::FnFilter;
+  pub use liquid_core::parser::{
+    FilterArguments, FilterReflection, ParameterReflection, ParseFilter,
+  };
+  pub use liquid_core::{Filter, Result};
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use pretty_assertions::assert_eq;
-
-  #[test]
-  fn upper_camel_case() {
-    assert_eq!(
-      liquid_core::call_filter!(UpperCamelCase, "abc").unwrap(),
-      liquid_core::value!("Abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(UpperCamelCase, "hello world 21").unwrap(),
-      liquid_core::value!("HelloWorld21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(UpperCamelCase, "hello world 21").unwrap(),
-      liquid_core::value!("HelloWorld21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(UpperCamelCase, "hello_world_21").unwrap(),
-      liquid_core::value!("HelloWorld21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(UpperCamelCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("HelloWorld21")
-    );
-  }
-
-  #[test]
-  fn lower_camel_case() {
-    assert_eq!(
-      liquid_core::call_filter!(LowerCamelCase, "abc").unwrap(),
-      liquid_core::value!("abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(LowerCamelCase, "hello world 21").unwrap(),
-      liquid_core::value!("helloWorld21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(LowerCamelCase, "hello_world_21").unwrap(),
-      liquid_core::value!("helloWorld21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(LowerCamelCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("helloWorld21")
-    );
-  }
-
-  #[test]
-  fn snake_case() {
-    assert_eq!(
-      liquid_core::call_filter!(SnakeCase, "abc").unwrap(),
-      liquid_core::value!("abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(SnakeCase, "hello world 21").unwrap(),
-      liquid_core::value!("hello_world_21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(SnakeCase, "hello_world_21").unwrap(),
-      liquid_core::value!("hello_world_21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(SnakeCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("hello_world21")
-    );
-  }
-
-  #[test]
-  fn kebab_case() {
-    assert_eq!(
-      liquid_core::call_filter!(KebabCase, "abc").unwrap(),
-      liquid_core::value!("abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(KebabCase, "hello world 21").unwrap(),
-      liquid_core::value!("hello-world-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(KebabCase, "hello_world_21").unwrap(),
-      liquid_core::value!("hello-world-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(KebabCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("hello-world21")
-    );
-  }
-
-  #[test]
-  fn shouty_snake_case() {
-    assert_eq!(
-      liquid_core::call_filter!(ShoutySnakeCase, "abc").unwrap(),
-      liquid_core::value!("ABC")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutySnakeCase, "hello world 21").unwrap(),
-      liquid_core::value!("HELLO_WORLD_21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutySnakeCase, "hello_world_21").unwrap(),
-      liquid_core::value!("HELLO_WORLD_21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutySnakeCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("HELLO_WORLD21")
-    );
-  }
-
-  #[test]
-  fn title_case() {
-    assert_eq!(
-      liquid_core::call_filter!(TitleCase, "abc").unwrap(),
-      liquid_core::value!("Abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TitleCase, "hello world 21").unwrap(),
-      liquid_core::value!("Hello World 21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TitleCase, "hello_world_21").unwrap(),
-      liquid_core::value!("Hello World 21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TitleCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("Hello World21")
-    );
-  }
+#[cfg(feature = "std")]
+pub use config::{Config, FilterConfig, FilterSet};
+#[cfg(feature = "std")]
+pub use docs::{
+  filter_doc, filter_docs, insert_filter_docs, ArgumentDoc, ExampleDoc, FilterDoc,
+  HECK_FILTERS_GLOBAL,
+};
+#[cfg(feature = "filter-case")]
+pub use filters::ToCase;
+#[cfg(feature = "std")]
+pub use filters::{ConfiguredCase, HECK_CONFIG_GLOBAL};
+#[cfg(all(feature = "std", any(feature = "tracing", feature = "log")))]
+pub use instrument::{redaction, set_redaction, Redaction};
+#[cfg(feature = "liquid")]
+pub use liquid;
+#[cfg(feature = "liquid")]
+pub use parser::{
+  parser_with_heck, register_all_filters, register_all_filters_with_aliases, register_all_tags,
+  register_filters,
+};
+#[cfg(feature = "std")]
+pub use registry::{FilterRegistry, UnknownFilter};
 
-  #[test]
-  fn shouty_kebab_case() {
-    assert_eq!(
-      liquid_core::call_filter!(ShoutyKebabCase, "abc").unwrap(),
-      liquid_core::value!("ABC")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutyKebabCase, "hello world 21").unwrap(),
-      liquid_core::value!("HELLO-WORLD-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutyKebabCase, "hello_world_21").unwrap(),
-      liquid_core::value!("HELLO-WORLD-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(ShoutyKebabCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("HELLO-WORLD21")
-    );
-  }
+#[cfg(feature = "filter-dedupe-idents")]
+pub use arrays::DedupeIdents;
+#[cfg(feature = "filter-group-by-prefix")]
+pub use arrays::GroupByPrefix;
+#[cfg(feature = "filter-sort-ci")]
+pub use arrays::SortCi;
+#[cfg(feature = "icu")]
+pub use arrays::SortCollate;
+#[cfg(feature = "filter-sort-natural-ident")]
+pub use arrays::SortNaturalIdent;
+#[cfg(feature = "filter-case-eq")]
+pub use compare::CaseEq;
+#[cfg(feature = "filter-caseless-eq")]
+pub use compare::CaselessEq;
+#[cfg(feature = "filter-acronymize")]
+pub use modifiers::Acronymize;
+#[cfg(feature = "filter-downcase-keep-separators")]
+pub use modifiers::DowncaseKeepSeparators;
+#[cfg(feature = "filter-escape-reserved")]
+pub use modifiers::EscapeReserved;
+#[cfg(feature = "filter-expand-acronyms")]
+pub use modifiers::ExpandAcronyms;
+#[cfg(all(feature = "homoglyphs", feature = "std"))]
+pub use modifiers::NormalizeHomoglyphs;
+#[cfg(feature = "filter-screaming")]
+pub use modifiers::Screaming;
+#[cfg(feature = "filter-skip-if-opaque")]
+pub use modifiers::SkipIfOpaque;
+#[cfg(feature = "filter-spell-symbols")]
+pub use modifiers::SpellSymbols;
+#[cfg(feature = "filter-strip-diacritics")]
+pub use modifiers::StripDiacritics;
+#[cfg(feature = "filter-truncate-words-case")]
+pub use modifiers::TruncateWordsCase;
+#[cfg(feature = "filter-wrap-ident")]
+pub use modifiers::WrapIdent;
+#[cfg(feature = "std")]
+pub use modifiers::{HECK_ACRONYMS_GLOBAL, HECK_EXPANSIONS_GLOBAL, HECK_SYMBOLS_GLOBAL};
+#[cfg(feature = "filter-values")]
+pub use objects::CaseValues;
+#[cfg(feature = "filter-restyle")]
+pub use objects::Restyle;
+#[cfg(feature = "std")]
+pub use objects::{convert_value, convert_value_with, KeyPolicy, ValuePolicy};
+#[cfg(feature = "filter-count-with")]
+pub use plural::CountWith;
+#[cfg(feature = "filter-builder-method")]
+pub use presets::BuilderMethod;
+#[cfg(feature = "filter-const-name")]
+pub use presets::ConstName;
+#[cfg(feature = "filter-enum-variant")]
+pub use presets::EnumVariant;
+#[cfg(feature = "filter-getter-name")]
+pub use presets::GetterName;
+#[cfg(feature = "filter-predicate-name")]
+pub use presets::PredicateName;
+#[cfg(feature = "filter-setter-name")]
+pub use presets::SetterName;
+#[cfg(feature = "filter-test-name")]
+pub use presets::TestName;
+#[cfg(feature = "tag-convert-keys")]
+pub use tags::ConvertKeysTag;
+#[cfg(feature = "tag-heck-defaults")]
+pub use tags::HeckDefaultsTag;
+#[cfg(feature = "filter-anchor-docusaurus")]
+pub use targets::AnchorDocusaurus;
+#[cfg(feature = "filter-anchor-mdbook")]
+pub use targets::AnchorMdbook;
+#[cfg(feature = "filter-canonical-header")]
+pub use targets::CanonicalHeader;
+#[cfg(feature = "filter-email-local")]
+pub use targets::EmailLocal;
+#[cfg(feature = "filter-event-name")]
+pub use targets::EventName;
+#[cfg(feature = "filter-gcp")]
+pub use targets::GcpName;
+#[cfg(feature = "filter-git-ref")]
+pub use targets::GitRef;
+#[cfg(feature = "filter-helm")]
+pub use targets::HelmName;
+#[cfg(feature = "filter-i18n-key")]
+pub use targets::I18nKey;
+#[cfg(feature = "filter-in-namespace")]
+pub use targets::InNamespace;
+#[cfg(feature = "filter-rst-label")]
+pub use targets::RstLabel;
+#[cfg(feature = "filter-safe-filename")]
+pub use targets::SafeFilename;
+#[cfg(feature = "filter-unique-slug")]
+pub use targets::UniqueSlug;
+#[cfg(feature = "filter-aws")]
+pub use targets::{CfnLogicalId, IamName, LambdaName};
+#[cfg(feature = "filter-docker")]
+pub use targets::{DockerRepo, DockerTag};
+#[cfg(feature = "filter-latex")]
+pub use targets::{LatexCommand, LatexLabel};
+#[cfg(feature = "filter-maven")]
+pub use targets::{MavenArtifact, MavenGroup};
+#[cfg(feature = "filter-prometheus")]
+pub use targets::{PromLabel, PromMetric};
+#[cfg(feature = "filter-pypi")]
+pub use targets::{PyDistToModule, PypiName};
+#[cfg(feature = "filter-convert-within")]
+pub use text::ConvertWithin;
+#[cfg(feature = "filter-rename-placeholders")]
+pub use text::RenamePlaceholders;
 
-  #[test]
-  fn train_case() {
-    assert_eq!(
-      liquid_core::call_filter!(TrainCase, "abc").unwrap(),
-      liquid_core::value!("Abc")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TrainCase, "hello world 21").unwrap(),
-      liquid_core::value!("Hello-World-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TrainCase, "hello_world_21").unwrap(),
-      liquid_core::value!("Hello-World-21")
-    );
-    assert_eq!(
-      liquid_core::call_filter!(TrainCase, "HelloWorld21").unwrap(),
-      liquid_core::value!("Hello-World21")
-    );
-  }
-}
+#[cfg(feature = "filter-kebab")]
+pub use filters::KebabCase;
+#[cfg(feature = "filter-lower-camel")]
+pub use filters::LowerCamelCase;
+#[cfg(feature = "filter-shouty-kebab")]
+pub use filters::ShoutyKebabCase;
+#[cfg(feature = "filter-shouty-snake")]
+pub use filters::ShoutySnakeCase;
+#[cfg(feature = "filter-snake")]
+pub use filters::SnakeCase;
+#[cfg(feature = "filter-title")]
+pub use filters::TitleCase;
+#[cfg(feature = "filter-train")]
+pub use filters::TrainCase;
+#[cfg(feature = "filter-upper-camel")]
+pub use filters::UpperCamelCase;
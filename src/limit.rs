@@ -0,0 +1,238 @@
+//! Length limits of the generated names, shared by the filters.
+use crate::args::{invalid_argument, unknown_value};
+use crate::words::word_spans;
+use crate::Options;
+use liquid_core::{Result, ValueView};
+
+/// The named limits, in bytes, of the `limit:` argument.
+pub(crate) const PRESETS: &[(&str, usize)] = &[
+  // PostgreSQL identifiers (NAMEDATALEN - 1).
+  ("postgres", 63),
+  // Oracle identifiers before 12.2.
+  ("oracle", 30),
+  // Kubernetes labels and DNS labels.
+  ("k8s", 63),
+  // S3 bucket names.
+  ("s3", 63),
+];
+
+/// The preset names of [`PRESETS`].
+const PRESET_NAMES: [&str; PRESETS.len()] = {
+  let mut names = [""; PRESETS.len()];
+  let mut i = 0;
+  while i < PRESETS.len() {
+    names[i] = PRESETS[i].0;
+    i += 1;
+  }
+  names
+};
+
+/// How a name longer than its limit is shortened.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Strategy {
+  /// Cut at the limit.
+  #[default]
+  Truncate,
+  /// Cut all the words to the same length, keeping their start.
+  Abbrev,
+  /// Replace the end with a hash of the whole name, so that distinct names
+  /// stay distinct.
+  Hash,
+}
+
+impl Strategy {
+  /// The names of the strategies, as spelled in templates.
+  pub(crate) const NAMES: &'static [&'static str] = &["truncate", "abbrev", "hash"];
+
+  /// The strategy spelled `name` in templates.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "truncate" => Strategy::Truncate,
+      "abbrev" => Strategy::Abbrev,
+      "hash" => Strategy::Hash,
+      _ => return None,
+    })
+  }
+}
+
+/// The limit set by the `limit:` argument `value`: a number of bytes, or
+/// the name of a preset.
+pub(crate) fn limit_argument(value: &dyn ValueView) -> Result<usize> {
+  let scalar = value
+    .as_scalar()
+    .ok_or_else(|| invalid_argument("limit", "Number or preset name expected"))?;
+  if let Some(limit) = scalar.to_integer() {
+    return usize::try_from(limit).map_err(|_| invalid_argument("limit", "Positive number expected"));
+  }
+  let name = scalar.to_kstr();
+  PRESETS
+    .iter()
+    .find(|(preset, _)| *preset == name.as_str())
+    .map(|&(_, limit)| limit)
+    .ok_or_else(|| unknown_value("limit", name.as_str(), &PRESET_NAMES))
+}
+
+/// `s` shortened to at most `max_len` bytes with `strategy`.
+pub(crate) fn shorten(s: &str, max_len: usize, strategy: Strategy) -> String {
+  if s.len() <= max_len {
+    return s.to_string();
+  }
+  match strategy {
+    Strategy::Truncate => cut(s, max_len).to_string(),
+    Strategy::Abbrev => abbreviate(s, max_len),
+    Strategy::Hash => shorten_unique(s, max_len),
+  }
+}
+
+/// The start of `s` of at most `max_len` bytes, at a character boundary,
+/// without trailing separators.
+fn cut(s: &str, max_len: usize) -> &str {
+  let mut end = s.len().min(max_len);
+  while !s.is_char_boundary(end) {
+    end -= 1;
+  }
+  s[..end].trim_end_matches(is_separator)
+}
+
+fn is_separator(c: char) -> bool {
+  matches!(c, '_' | '-' | '.' | ' ')
+}
+
+/// `s` with all its words cut to the longest length that fits in `max_len`
+/// bytes: `customer_billing_service` becomes `cust_bill_serv` in 15. Cut at
+/// the limit when the initials don't fit.
+fn abbreviate(s: &str, max_len: usize) -> String {
+  let spans: Vec<_> = word_spans(s, &Options::default()).collect();
+  let longest = spans
+    .iter()
+    .map(|span| s[span.clone()].chars().count())
+    .max();
+  let abbreviated = |word_len: usize| {
+    let mut output = String::with_capacity(s.len());
+    let mut end = 0;
+    for span in &spans {
+      output.push_str(&s[end..span.start]);
+      let word = &s[span.clone()];
+      let cut = word
+        .char_indices()
+        .nth(word_len)
+        .map_or(word.len(), |(i, _)| i);
+      output.push_str(&word[..cut]);
+      end = span.end;
+    }
+    output.push_str(&s[end..]);
+    output
+  };
+  // The longest word length that fits, by bisection.
+  let (mut low, mut high) = (1, longest.unwrap_or(1));
+  while low < high {
+    let middle = (low + high).div_ceil(2);
+    match abbreviated(middle).len() <= max_len {
+      true => low = middle,
+      false => high = middle - 1,
+    }
+  }
+  let output = abbreviated(low);
+  cut(&output, max_len).to_string()
+}
+
+/// Shorten `name` to at most `max_len` bytes, keeping distinct names
+/// distinct: a longer name is cut after its last whole word that fits, then
+/// followed by its separator and a stable hash of the whole name.
+///
+/// ```
+/// let name = "customer-billing-service-worker";
+/// let short = liquid_heck::shorten_unique(name, 30);
+/// assert!(short.starts_with("customer-billing-"));
+/// assert_eq!(short.len(), 25);
+/// assert_ne!(short, liquid_heck::shorten_unique("customer-billing-service-api", 30));
+/// assert_eq!(liquid_heck::shorten_unique("billing", 24), "billing");
+/// ```
+///
+/// The first word is cut within when it doesn't fit, and the hash itself
+/// when `max_len` is shorter than 8 bytes. The words are found with the
+/// default options.
+pub fn shorten_unique(name: &str, max_len: usize) -> String {
+  if name.len() <= max_len {
+    return name.to_string();
+  }
+  let hash = format!("{:08x}", stable_hash(name) as u32);
+  let separator = name.chars().find(|&c| is_separator(c));
+  let room = max_len.saturating_sub(hash.len() + separator.map_or(0, char::len_utf8));
+  let head = word_spans(name, &Options::default())
+    .map(|span| span.end)
+    .take_while(|&end| end <= room)
+    .last()
+    .map_or_else(
+      || cut(name, room),
+      |end| name[..end].trim_end_matches(is_separator),
+    );
+  if head.is_empty() {
+    return hash[..max_len.min(hash.len())].to_string();
+  }
+  let mut output = head.to_string();
+  output.extend(separator);
+  output.push_str(&hash);
+  output
+}
+
+/// A hash of `s` stable across builds and platforms (64-bit FNV-1a).
+pub(crate) fn stable_hash(s: &str) -> u64 {
+  s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+    (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn strategies() {
+    let name = "customer_billing_service_worker";
+    assert_eq!(shorten(name, 40, Strategy::Hash), name);
+    assert_eq!(
+      shorten(name, 20, Strategy::Truncate),
+      "customer_billing_ser"
+    );
+    assert_eq!(shorten(name, 17, Strategy::Truncate), "customer_billing");
+    assert_eq!(shorten(name, 20, Strategy::Abbrev), "cust_bill_serv_work");
+    assert_eq!(shorten(name, 15, Strategy::Abbrev), "cus_bil_ser_wor");
+    assert_eq!(shorten(name, 6, Strategy::Abbrev), "c_b_s");
+    assert_eq!(
+      shorten("userAccountSettings", 12, Strategy::Abbrev),
+      "userAccoSett"
+    );
+    let hashed = shorten(name, 20, Strategy::Hash);
+    assert_eq!(hashed.len(), 17);
+    assert!(hashed.starts_with("customer_"), "{hashed}");
+    assert_ne!(
+      hashed,
+      shorten("customer_billing_service_api", 20, Strategy::Hash)
+    );
+    assert_eq!(shorten(name, 5, Strategy::Hash).len(), 5);
+  }
+
+  #[test]
+  fn multibyte() {
+    assert_eq!(shorten("été_année", 5, Strategy::Truncate), "été");
+    #[cfg(feature = "heck05")]
+    assert_eq!(shorten("été_année", 9, Strategy::Abbrev), "été_ann");
+  }
+
+  #[test]
+  fn unique() {
+    let hashed = shorten_unique("UserAccountSettings", 16);
+    assert_eq!(hashed.len(), 12);
+    assert!(hashed.starts_with("User"), "{hashed}");
+    let hashed = shorten_unique("customer_billing_service_worker", 12);
+    assert_eq!(hashed.len(), 12);
+    assert!(hashed.starts_with("cus_"), "{hashed}");
+    assert_eq!(
+      shorten_unique("customer_billing_service_worker", 12),
+      hashed
+    );
+    assert_ne!(shorten_unique("customer_billing_service_api", 12), hashed);
+  }
+}
@@ -0,0 +1,99 @@
+//! Macros exported for downstream crates.
+
+/// Define a Liquid filter converting its input with a `fn(&str) -> String`.
+///
+/// The filter gets the same `each_line:`, `words:`, `markdown:` and `limit:`
+/// arguments, the same reflection and the same `tracing`/`log`
+/// instrumentation as the filters of this crate. The generated unit struct
+/// is registered like any other filter.
+///
+/// With `case = HeckCase::...`, the filter converts to that case when the
+/// render sets options, and also takes `lang:`. Only then does the unit
+/// struct get a `with_options()` constructor returning a `ConfiguredCase`.
+///
+/// ```
+/// fn reverse(s: &str) -> String {
+///     s.chars().rev().collect()
+/// }
+///
+/// liquid_heck::define_case_filter! {
+///     /// Reverse the string.
+///     pub struct Reverse;
+///     name = "reverse",
+///     description = "Reverse the string.",
+///     convert = reverse,
+/// }
+///
+/// let parser = liquid::ParserBuilder::with_stdlib()
+///     .filter(Reverse)
+///     .build().unwrap();
+/// let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+/// assert_eq!(render("{{ 'abc' | reverse }}").unwrap(), "cba");
+/// assert_eq!(render("{{ 'ab\ncd' | reverse: each_line: true }}").unwrap(), "ba\ndc");
+/// assert_eq!(render("{{ 'abc' | reverse: limit: 2 }}").unwrap(), "cb");
+/// assert!(render("{{ 'abc' | reverse: lang: 'fr' }}").is_err());
+/// ```
+#[macro_export]
+macro_rules! define_case_filter {
+  (
+    $(#[$attr:meta])*
+    $vis:vis struct $ty:ident;
+    name = $name:literal,
+    description = $description:literal,
+    convert = $convert:expr
+    $(, case = $case:expr)? $(,)?
+  ) => {
+    $(#[$attr])*
+    #[derive(Clone, Copy, Debug, Default)]
+    $vis struct $ty;
+
+    impl $crate::__private::FilterReflection for $ty {
+      fn name(&self) -> &str {
+        $name
+      }
+
+      fn description(&self) -> &str {
+        $description
+      }
+
+      fn positional_parameters(&self) -> &'static [$crate::__private::ParameterReflection] {
+        &[]
+      }
+
+      fn keyword_parameters(&self) -> &'static [$crate::__private::ParameterReflection] {
+        $crate::__private::FnFilter::keyword_parameters(
+          ::std::option::Option::None $(.or(::std::option::Option::Some($case)))?,
+        )
+      }
+    }
+
+    impl $crate::__private::ParseFilter for $ty {
+      fn parse(
+        &self,
+        arguments: $crate::__private::FilterArguments,
+      ) -> $crate::__private::Result<::std::boxed::Box<dyn $crate::__private::Filter>> {
+        ::std::result::Result::Ok(::std::boxed::Box::new($crate::__private::FnFilter::parse(
+          $name,
+          $convert,
+          ::std::option::Option::None $(.or(::std::option::Option::Some($case)))?,
+          arguments,
+        )?))
+      }
+
+      fn reflection(&self) -> &dyn $crate::__private::FilterReflection {
+        self
+      }
+    }
+
+    $(
+      impl $ty {
+        /// The filter converting with `options`, registered under the same
+        /// name. Rename it with `ConfiguredCase::named()` to register
+        /// several configurations side by side.
+        pub fn with_options(options: $crate::Options) -> $crate::ConfiguredCase {
+          $case.with_options(options).named($name)
+        }
+      }
+    )?
+  };
+}
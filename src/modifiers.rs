@@ -0,0 +1,948 @@
+//! Filters adjusting the output of the case conversion filters.
+use crate::args::{invalid_argument, named_value, positional_or_keyword};
+use crate::conversion;
+use crate::filters::{check_input_length, nil_output, prepare_input, runtime_options};
+use crate::objects::case_argument;
+#[cfg(feature = "filter-escape-reserved")]
+use crate::reserved::Reserved;
+use crate::spelling::spell_symbols;
+#[cfg(feature = "homoglyphs")]
+use crate::translit::normalize_homoglyphs;
+#[cfg(feature = "filter-strip-diacritics")]
+use crate::translit::strip_diacritics;
+use crate::words::{map_words, opaque_len, word_spans};
+use liquid_core::model::ScalarCow;
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// The global holding the default acronyms of [`Acronymize`], as an array or
+/// a comma-separated string.
+pub const HECK_ACRONYMS_GLOBAL: &str = "heck_acronyms";
+
+/// The global holding the default dictionary of [`ExpandAcronyms`], an
+/// object mapping the acronyms to their expansion.
+pub const HECK_EXPANSIONS_GLOBAL: &str = "heck_expansions";
+
+/// The global holding the default mapping of [`SpellSymbols`], an object
+/// mapping the symbols to their word.
+pub const HECK_SYMBOLS_GLOBAL: &str = "heck_symbols";
+
+/// The strings listed by `value`: the items of an array, or the
+/// comma-separated parts of a scalar.
+pub(crate) fn string_list(value: &dyn ValueView) -> Vec<String> {
+  match value.as_array() {
+    Some(array) => array
+      .values()
+      .map(|item| item.to_kstr().into_string())
+      .collect(),
+    None => value
+      .to_kstr()
+      .split(',')
+      .map(str::trim)
+      .filter(|item| !item.is_empty())
+      .map(str::to_string)
+      .collect(),
+  }
+}
+
+#[cfg(feature = "filter-acronymize")]
+#[derive(Debug, FilterParameters)]
+struct AcronymizeArgs {
+  #[parameter(
+    description = "The acronyms, as an array or a comma-separated string. Defaults to the `heck_acronyms` global.",
+    arg_type = "any"
+  )]
+  acronyms: Option<Expression>,
+}
+
+/// Uppercase the acronyms in an already converted string, for Go-style
+/// identifiers: `{{ "user_id" | lowercamelcase | acronymize: "id,url" }}`
+/// renders `userID`.
+#[cfg(feature = "filter-acronymize")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "acronymize",
+  description = "Uppercase the listed acronyms in an already converted string.",
+  parameters(AcronymizeArgs),
+  parsed(AcronymizeFilter)
+)]
+pub struct Acronymize;
+
+#[cfg(feature = "filter-acronymize")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "acronymize"]
+struct AcronymizeFilter {
+  #[parameters]
+  args: AcronymizeArgs,
+}
+
+#[cfg(feature = "filter-acronymize")]
+impl Filter for AcronymizeFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let mut options = runtime_options(runtime)?;
+    let global = runtime.try_get(&[ScalarCow::new(HECK_ACRONYMS_GLOBAL)]);
+    if let Some(acronyms) = args.acronyms.as_ref().or(global.as_ref()) {
+      options.acronyms = string_list(acronyms.as_view());
+    }
+
+    let s = input.to_kstr();
+    let s = prepare_input(&s, &options)?;
+    let output = map_words(&s, &options, |output, word| {
+      if options.is_acronym(word) {
+        output.push_str(&word.to_uppercase());
+      } else {
+        output.push_str(word);
+      }
+    });
+    #[cfg(feature = "log")]
+    log::debug!(
+      "acronymize: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-expand-acronyms")]
+#[derive(Debug, FilterParameters)]
+struct ExpandAcronymsArgs {
+  #[parameter(
+    description = "An object mapping the acronyms to their expansion. Defaults to the `heck_expansions` global.",
+    arg_type = "any"
+  )]
+  expansions: Option<Expression>,
+}
+
+/// Replace the acronyms of a string by their expansion, keeping the
+/// separators: `{{ "cfg_db_url" | expand_acronyms: dict }}` renders
+/// `Configuration_Database_URL` when `dict` maps `cfg` to `Configuration`,
+/// `db` to `Database` and `url` to `URL`.
+#[cfg(feature = "filter-expand-acronyms")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "expand_acronyms",
+  description = "Replace the acronyms of the string by their expansion.",
+  parameters(ExpandAcronymsArgs),
+  parsed(ExpandAcronymsFilter)
+)]
+pub struct ExpandAcronyms;
+
+#[cfg(feature = "filter-expand-acronyms")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "expand_acronyms"]
+struct ExpandAcronymsFilter {
+  #[parameters]
+  args: ExpandAcronymsArgs,
+}
+
+#[cfg(feature = "filter-expand-acronyms")]
+impl Filter for ExpandAcronymsFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let global = runtime.try_get(&[ScalarCow::new(HECK_EXPANSIONS_GLOBAL)]);
+    let expansions = match args.expansions.as_ref().or(global.as_ref()) {
+      Some(expansions) => expansions
+        .as_object()
+        .ok_or_else(|| invalid_argument("expansions", "Object expected"))?
+        .iter()
+        .map(|(acronym, expansion)| (acronym.into_owned(), expansion.to_kstr().into_string()))
+        .collect(),
+      None => Vec::new(),
+    };
+
+    let s = input.to_kstr();
+    let options = runtime_options(runtime)?;
+    let s = prepare_input(&s, &options)?;
+    let output = map_words(&s, &options, |output, word| {
+      let expansion = expansions.iter().find(|(acronym, _)| {
+        acronym
+          .chars()
+          .flat_map(char::to_lowercase)
+          .eq(word.chars().flat_map(char::to_lowercase))
+      });
+      output.push_str(expansion.map_or(word, |(_, expansion)| expansion));
+    });
+    #[cfg(feature = "log")]
+    log::debug!(
+      "expand_acronyms: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-spell-symbols")]
+#[derive(Debug, FilterParameters)]
+struct SpellSymbolsArgs {
+  #[parameter(
+    description = "An object mapping the symbols to their word, over the default words. Defaults to the `heck_symbols` global.",
+    arg_type = "any"
+  )]
+  symbols: Option<Expression>,
+}
+
+/// Spell out the symbols of a string as words, before a conversion:
+/// `{{ "R&D @ 50%" | spell_symbols | kebabcase }}` renders
+/// `r-and-d-at-50-percent`. `&`, `@`, `%`, `+`, `=` and `°` are spelled out
+/// by default; the mapping argument adds symbols or overrides their word,
+/// an empty word dropping the symbol.
+#[cfg(feature = "filter-spell-symbols")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "spell_symbols",
+  description = "Replace the symbols of the string by words.",
+  parameters(SpellSymbolsArgs),
+  parsed(SpellSymbolsFilter)
+)]
+pub struct SpellSymbols;
+
+#[cfg(feature = "filter-spell-symbols")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "spell_symbols"]
+struct SpellSymbolsFilter {
+  #[parameters]
+  args: SpellSymbolsArgs,
+}
+
+#[cfg(feature = "filter-spell-symbols")]
+impl Filter for SpellSymbolsFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let global = runtime.try_get(&[ScalarCow::new(HECK_SYMBOLS_GLOBAL)]);
+    let symbols: Vec<_> = match args.symbols.as_ref().or(global.as_ref()) {
+      Some(symbols) => symbols
+        .as_object()
+        .ok_or_else(|| invalid_argument("symbols", "Object expected"))?
+        .iter()
+        .map(|(symbol, word)| (symbol.into_owned(), word.to_kstr().into_string()))
+        .collect(),
+      None => Vec::new(),
+    };
+
+    let s = input.to_kstr();
+    check_input_length(&s, &runtime_options(runtime)?)?;
+    let output = spell_symbols(&s, &symbols);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "spell_symbols: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+/// Drop the diacritics of the letters of a string, before a conversion:
+/// `{{ "Crème Brûlée" | strip_diacritics | snakecase }}` renders
+/// `creme_brulee`. The letters without canonical decomposition, like `ø`,
+/// `ß` or the Cyrillic ones, are kept; `ascii_mode` drops them.
+#[cfg(feature = "filter-strip-diacritics")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "strip_diacritics",
+  description = "Remove the accents and other diacritics of the letters of the string.",
+  parsed(StripDiacriticsFilter)
+)]
+pub struct StripDiacritics;
+
+#[cfg(feature = "filter-strip-diacritics")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "strip_diacritics"]
+struct StripDiacriticsFilter;
+
+#[cfg(feature = "filter-strip-diacritics")]
+impl Filter for StripDiacriticsFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let s = input.to_kstr();
+    check_input_length(&s, &runtime_options(runtime)?)?;
+    let output = strip_diacritics(&s).into_owned();
+    #[cfg(feature = "log")]
+    log::debug!(
+      "strip_diacritics: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+/// Replace the characters confusable with an ASCII letter or digit, before
+/// a conversion of user input: `{{ "pаypаl" | normalize_homoglyphs |
+/// kebabcase }}`, with Cyrillic `а`, renders `paypal`. The Cyrillic, Greek
+/// and fullwidth lookalikes are replaced, the other letters kept.
+#[cfg(feature = "homoglyphs")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "normalize_homoglyphs",
+  description = "Replace the characters looking like an ASCII letter or digit by that character.",
+  parsed(NormalizeHomoglyphsFilter)
+)]
+pub struct NormalizeHomoglyphs;
+
+#[cfg(feature = "homoglyphs")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "normalize_homoglyphs"]
+struct NormalizeHomoglyphsFilter;
+
+#[cfg(feature = "homoglyphs")]
+impl Filter for NormalizeHomoglyphsFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let s = input.to_kstr();
+    check_input_length(&s, &runtime_options(runtime)?)?;
+    let output = normalize_homoglyphs(&s).into_owned();
+    #[cfg(feature = "log")]
+    log::debug!(
+      "normalize_homoglyphs: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-escape-reserved")]
+#[derive(Debug, FilterParameters)]
+struct EscapeReservedArgs {
+  #[parameter(
+    description = "The target reserving the names: \"windows\", \"sql\", \"rust\", \"go\", \"python\", \"java\" or \"k8s\".",
+    arg_type = "str"
+  )]
+  target: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `target`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "target"
+  )]
+  target_keyword: Option<Expression>,
+}
+
+/// Escape a name reserved by a target, the way of that target, after a
+/// conversion: `{{ "type" | snakecase | escape_reserved: "rust" }}` renders
+/// `r#type`, `{{ "order" | escape_reserved: "sql" }}` renders `"order"`
+/// and `{{ "con.txt" | escape_reserved: "windows" }}` renders `con_.txt`.
+/// The Go, Python and Java keywords take a trailing underscore; the
+/// `kubernetes.io/` and `k8s.io/` label prefixes are dropped. The names not
+/// reserved are left as is.
+#[cfg(feature = "filter-escape-reserved")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "escape_reserved",
+  description = "Escape the string if it is a keyword or a name reserved by the given target.",
+  parameters(EscapeReservedArgs),
+  parsed(EscapeReservedFilter)
+)]
+pub struct EscapeReserved;
+
+#[cfg(feature = "filter-escape-reserved")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "escape_reserved"]
+struct EscapeReservedFilter {
+  #[parameters]
+  args: EscapeReservedArgs,
+}
+
+#[cfg(feature = "filter-escape-reserved")]
+impl Filter for EscapeReservedFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let target = positional_or_keyword("target", args.target, args.target_keyword)?
+      .ok_or_else(|| invalid_argument("target", "Target name expected"))?;
+    let target = named_value("target", &target, Reserved::from_name, Reserved::NAMES)?;
+    let s = input.to_kstr();
+    check_input_length(&s, &runtime_options(runtime)?)?;
+    let output = target.escape(&s).into_owned();
+    #[cfg(feature = "log")]
+    log::debug!(
+      "escape_reserved: {} -> {}",
+      crate::instrument::Redacted::Str(&s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-truncate-words-case")]
+#[derive(Debug, FilterParameters)]
+struct TruncateWordsCaseArgs {
+  #[parameter(
+    description = "The maximum number of characters, ellipsis included. Defaults to 50.",
+    arg_type = "integer"
+  )]
+  length: Option<Expression>,
+  #[parameter(
+    description = "The text appended to a truncated string. Defaults to \"...\".",
+    arg_type = "str"
+  )]
+  ellipsis: Option<Expression>,
+}
+
+/// Truncate a converted string after its last whole word that fits, then
+/// append an ellipsis: `{{ "userAccountSettings" | truncate_words_case: 15, "…" }}`
+/// renders `userAccount…`.
+#[cfg(feature = "filter-truncate-words-case")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "truncate_words_case",
+  description = "Truncate the string at a word boundary and append an ellipsis.",
+  parameters(TruncateWordsCaseArgs),
+  parsed(TruncateWordsCaseFilter)
+)]
+pub struct TruncateWordsCase;
+
+#[cfg(feature = "filter-truncate-words-case")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "truncate_words_case"]
+struct TruncateWordsCaseFilter {
+  #[parameters]
+  args: TruncateWordsCaseArgs,
+}
+
+#[cfg(feature = "filter-truncate-words-case")]
+impl Filter for TruncateWordsCaseFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let length = args.length.unwrap_or(50);
+    let length =
+      usize::try_from(length).map_err(|_| invalid_argument("length", "Positive number expected"))?;
+    let ellipsis = args.ellipsis.unwrap_or_else(|| "...".into());
+
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let options = runtime_options(runtime)?;
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    if s.chars().count() <= length {
+      return Ok(Value::scalar(s.to_string()));
+    }
+    let budget = length.saturating_sub(ellipsis.chars().count());
+    let end = word_spans(s, &options)
+      .map(|span| span.end)
+      .take_while(|&end| s[..end].chars().count() <= budget)
+      .last()
+      .unwrap_or(0);
+    let output = format!("{}{ellipsis}", &s[..end]);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "truncate_words_case: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-wrap-ident")]
+#[derive(Debug, FilterParameters)]
+struct WrapIdentArgs {
+  #[parameter(
+    description = "The maximum number of characters of a line. Defaults to 80.",
+    arg_type = "integer"
+  )]
+  width: Option<Expression>,
+  #[parameter(
+    description = "The text inserted at each break. Defaults to a line break.",
+    arg_type = "str"
+  )]
+  continuation: Option<Expression>,
+}
+
+/// Break a long identifier or label between its words to fit a width:
+/// `{{ "user_account_settings" | wrap_ident: 13 }}` renders `user_account_`
+/// and `settings` on two lines. Words longer than the width
+/// are not broken.
+#[cfg(feature = "filter-wrap-ident")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "wrap_ident",
+  description = "Break the string between its words to fit a line width.",
+  parameters(WrapIdentArgs),
+  parsed(WrapIdentFilter)
+)]
+pub struct WrapIdent;
+
+#[cfg(feature = "filter-wrap-ident")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "wrap_ident"]
+struct WrapIdentFilter {
+  #[parameters]
+  args: WrapIdentArgs,
+}
+
+#[cfg(feature = "filter-wrap-ident")]
+impl Filter for WrapIdentFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let width = args.width.unwrap_or(80);
+    let width =
+      usize::try_from(width).map_err(|_| invalid_argument("width", "Positive number expected"))?;
+    let continuation = args.continuation.unwrap_or_else(|| "\n".into());
+
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let options = runtime_options(runtime)?;
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    let mut output = String::with_capacity(s.len());
+    let mut line_start = 0;
+    for span in word_spans(s, &options) {
+      if span.start > line_start && s[line_start..span.end].chars().count() > width {
+        output.push_str(&s[line_start..span.start]);
+        output.push_str(&continuation);
+        line_start = span.start;
+      }
+    }
+    output.push_str(&s[line_start..]);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "wrap_ident: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-screaming")]
+crate::define_case_filter! {
+  /// Uppercase an already converted string, keeping its separators:
+  /// `{{ "foo.bar" | screaming }}` renders `FOO.BAR`.
+  pub struct Screaming;
+  name = "screaming",
+  description = "Uppercase the string, keeping its separators.",
+  convert = str::to_uppercase,
+}
+
+#[cfg(feature = "filter-downcase-keep-separators")]
+crate::define_case_filter! {
+  /// Lowercase an already converted string, keeping its separators and digit
+  /// groups: `{{ "Content-Type" | downcase_keep_separators }}` renders
+  /// `content-type`.
+  pub struct DowncaseKeepSeparators;
+  name = "downcase_keep_separators",
+  description = "Lowercase the string, keeping its separators.",
+  convert = str::to_lowercase,
+}
+
+#[cfg(feature = "filter-skip-if-opaque")]
+#[derive(Debug, FilterParameters)]
+struct SkipIfOpaqueArgs {
+  #[parameter(
+    description = "The case filter converting the string, e.g. \"snakecase\".",
+    arg_type = "str"
+  )]
+  case: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `case`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "case"
+  )]
+  case_keyword: Option<Expression>,
+}
+
+/// Convert the string to a case, unless it is an opaque token (a UUID, a
+/// Git SHA, a long hexadecimal or base64 string) left as is:
+/// `{{ ref | skip_if_opaque: "kebabcase" }}`. The opaque tokens within the
+/// string are kept as written, as with the `opaque_tokens` option.
+#[cfg(feature = "filter-skip-if-opaque")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "skip_if_opaque",
+  description = "Convert the string to the given case, unless it is a UUID, a hash or a base64 token.",
+  parameters(SkipIfOpaqueArgs),
+  parsed(SkipIfOpaqueFilter)
+)]
+pub struct SkipIfOpaque;
+
+#[cfg(feature = "filter-skip-if-opaque")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "skip_if_opaque"]
+struct SkipIfOpaqueFilter {
+  #[parameters]
+  args: SkipIfOpaqueArgs,
+}
+
+#[cfg(feature = "filter-skip-if-opaque")]
+impl Filter for SkipIfOpaqueFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let case = positional_or_keyword("case", args.case, args.case_keyword)?
+      .ok_or_else(|| invalid_argument("case", "Case name expected"))?;
+    let case = case_argument("case", &case)?;
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let mut options = runtime_options(runtime)?;
+    let s = prepare_input(s, &options)?;
+    let s = &*s;
+    let token = s.trim();
+    if opaque_len(token) == Some(token.len()) {
+      return Ok(Value::scalar(s.to_string()));
+    }
+    options.opaque_tokens = true;
+    let output = conversion::convert_prepared(case, s, &options);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "skip_if_opaque: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  #[cfg(feature = "filter-acronymize")]
+  fn acronymize() {
+    assert_eq!(
+      liquid_core::call_filter!(Acronymize, "userId", "id,url,http").unwrap(),
+      liquid_core::value!("userID")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(Acronymize, "HttpClient-url", "id, url, http").unwrap(),
+      liquid_core::value!("HTTPClient-URL")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(Acronymize, "user_ids", liquid_core::value!(["id"])).unwrap(),
+      liquid_core::value!("user_ids")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(Acronymize, "userId").unwrap(),
+      liquid_core::value!("userId")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-acronymize")]
+  fn acronymize_global() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(Acronymize)
+      .build()
+      .unwrap()
+      .parse("{{ name | acronymize }}")
+      .unwrap();
+    let globals = liquid::object!({ "name": "apiUrl", "heck_acronyms": ["api", "url"] });
+    assert_eq!(template.render(&globals).unwrap(), "APIURL");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-screaming")]
+  fn screaming() {
+    assert_eq!(
+      liquid_core::call_filter!(Screaming, "foo.bar").unwrap(),
+      liquid_core::value!("FOO.BAR")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(Screaming, "Hello-World21_straße").unwrap(),
+      liquid_core::value!("HELLO-WORLD21_STRASSE")
+    );
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(Screaming)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'foo.bar' | screaming: words: 1 }}").unwrap(),
+      "FOO.bar"
+    );
+    assert!(render("{{ 'foo.bar' | screaming: lang: 'fr' }}").is_err());
+    let arguments: Vec<_> = Screaming
+      .keyword_parameters()
+      .iter()
+      .map(|p| p.name)
+      .collect();
+    assert_eq!(
+      arguments,
+      ["each_line", "words", "markdown", "limit", "limit_strategy"]
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-downcase-keep-separators")]
+  fn downcase_keep_separators() {
+    assert_eq!(
+      liquid_core::call_filter!(DowncaseKeepSeparators, "Content-Type").unwrap(),
+      liquid_core::value!("content-type")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(DowncaseKeepSeparators, "Ada_Case_2_0.ΣΑΣ").unwrap(),
+      liquid_core::value!("ada_case_2_0.σας")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-expand-acronyms")]
+  fn expand_acronyms() {
+    let dict = liquid_core::value!({ "cfg": "Configuration", "DB": "Database", "url": "URL" });
+    assert_eq!(
+      liquid_core::call_filter!(ExpandAcronyms, "cfg_db_url", dict.clone()).unwrap(),
+      liquid_core::value!("Configuration_Database_URL")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ExpandAcronyms, "dbCfg", dict).unwrap(),
+      liquid_core::value!("DatabaseConfiguration")
+    );
+    assert!(liquid_core::call_filter!(ExpandAcronyms, "db", "db=Database").is_err());
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(ExpandAcronyms)
+      .build()
+      .unwrap()
+      .parse("{{ 'max_ttl' | expand_acronyms }}")
+      .unwrap();
+    let globals = liquid::object!({ "heck_expansions": { "ttl": "Time To Live" } });
+    assert_eq!(template.render(&globals).unwrap(), "max_Time To Live");
+  }
+
+  #[test]
+  #[cfg(all(feature = "filter-spell-symbols", feature = "filter-kebab"))]
+  fn spell_symbols() {
+    assert_eq!(
+      liquid_core::call_filter!(SpellSymbols, "R&D @ 50%").unwrap(),
+      liquid_core::value!("R and D at 50 percent")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(
+        SpellSymbols,
+        "Q&A #1",
+        liquid_core::value!({ "&": "n", "#": "" })
+      )
+      .unwrap(),
+      liquid_core::value!("Q n A 1")
+    );
+    assert!(liquid_core::call_filter!(SpellSymbols, "a&b", "&=and").is_err());
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SpellSymbols)
+      .filter(crate::KebabCase)
+      .build()
+      .unwrap()
+      .parse("{{ 'Tips & Tricks' | spell_symbols | kebabcase }}|{{ 'R&D @ 50%' | kebabcase }}")
+      .unwrap();
+    let globals = liquid::object!({ "heck_symbols": { "&": "'n'" } });
+    assert_eq!(template.render(&globals).unwrap(), "tips-n-tricks|r-d-50");
+    let globals = liquid::object!({ "heck_config": { "spell_symbols": true } });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "tips-and-tricks|r-and-d-at-50-percent"
+    );
+  }
+
+  #[test]
+  #[cfg(all(
+    feature = "filter-strip-diacritics",
+    feature = "filter-snake",
+    feature = "heck05"
+  ))]
+  fn strip_diacritics() {
+    assert_eq!(
+      liquid_core::call_filter!(StripDiacritics, "Crème Brûlée, Ñandú").unwrap(),
+      liquid_core::value!("Creme Brulee, Nandu")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(StripDiacritics, "Øresund Straße").unwrap(),
+      liquid_core::value!("Øresund Straße")
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(StripDiacritics)
+      .filter(crate::SnakeCase)
+      .build()
+      .unwrap()
+      .parse("{{ 'Crème Brûlée' | strip_diacritics | snakecase }}|{{ 'Señor Løkke' | snakecase }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "creme_brulee|señor_løkke"
+    );
+    let globals = liquid::object!({ "heck_config": { "strip_diacritics": true } });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "creme_brulee|senor_løkke"
+    );
+  }
+
+  #[test]
+  #[cfg(all(feature = "homoglyphs", feature = "filter-kebab"))]
+  fn normalize_homoglyphs() {
+    // The `а` are Cyrillic.
+    assert_eq!(
+      liquid_core::call_filter!(NormalizeHomoglyphs, "pаypаl").unwrap(),
+      liquid_core::value!("paypal")
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(NormalizeHomoglyphs)
+      .filter(crate::KebabCase)
+      .build()
+      .unwrap()
+      .parse("{{ 'Αpple Ѕtore' | normalize_homoglyphs | kebabcase }}|{{ 'Ѕtore' | kebabcase }}")
+      .unwrap();
+    // Without `heck05`, `kebabcase` drops the Cyrillic `ѕ` left over.
+    #[cfg(feature = "heck05")]
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "apple-store|ѕtore"
+    );
+    let globals = liquid::object!({ "heck_config": { "normalize_homoglyphs": true } });
+    assert_eq!(template.render(&globals).unwrap(), "apple-store|store");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-truncate-words-case")]
+  fn truncate_words_case() {
+    assert_eq!(
+      liquid_core::call_filter!(TruncateWordsCase, "userAccountSettings", 15, "…").unwrap(),
+      liquid_core::value!("userAccount…")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TruncateWordsCase, "user_account_settings", 16).unwrap(),
+      liquid_core::value!("user_account...")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TruncateWordsCase, "short_name", 10).unwrap(),
+      liquid_core::value!("short_name")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TruncateWordsCase, "Internationalization", 8).unwrap(),
+      liquid_core::value!("...")
+    );
+    assert!(liquid_core::call_filter!(TruncateWordsCase, "name", -1).is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-wrap-ident")]
+  fn wrap_ident() {
+    assert_eq!(
+      liquid_core::call_filter!(WrapIdent, "user_account_settings", 13, "\\\n").unwrap(),
+      liquid_core::value!("user_account_\\\nsettings")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(WrapIdent, "UserAccountSettingsPanel", 12).unwrap(),
+      liquid_core::value!("UserAccount\nSettings\nPanel")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(WrapIdent, "Internationalization", 5).unwrap(),
+      liquid_core::value!("Internationalization")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-skip-if-opaque")]
+  fn skip_if_opaque() {
+    assert_eq!(
+      liquid_core::call_filter!(SkipIfOpaque, " 9fceb02d0ae598e9 ", "snakecase").unwrap(),
+      liquid_core::value!(" 9fceb02d0ae598e9 ")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(
+        SkipIfOpaque,
+        "Run 123e4567-e89b-12d3-a456-426614174000",
+        "kebab"
+      )
+      .unwrap(),
+      liquid_core::value!("run-123e4567-e89b-12d3-a456-426614174000")
+    );
+    assert!(liquid_core::call_filter!(SkipIfOpaque, "x", "nocase").is_err());
+    assert!(liquid_core::call_filter!(SkipIfOpaque, "x").is_err());
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SkipIfOpaque)
+      .build()
+      .unwrap()
+      .parse("{{ 'Run Id' | skip_if_opaque: case: 'snake' }}")
+      .unwrap();
+    assert_eq!(template.render(&liquid::object!({})).unwrap(), "run_id");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-escape-reserved")]
+  fn escape_reserved() {
+    assert_eq!(
+      liquid_core::call_filter!(EscapeReserved, "type", "rust").unwrap(),
+      liquid_core::value!("r#type")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(EscapeReserved, "user", "sql").unwrap(),
+      liquid_core::value!("\"user\"")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(EscapeReserved, "name", "python").unwrap(),
+      liquid_core::value!("name")
+    );
+    assert!(liquid_core::call_filter!(EscapeReserved, "type", "cobol").is_err());
+    assert!(liquid_core::call_filter!(EscapeReserved, "type").is_err());
+  }
+}
@@ -0,0 +1,444 @@
+//! Filters converting the content of objects and arrays.
+use crate::args::unknown_value;
+use crate::conversion::{self, HeckCase};
+use crate::filters::runtime_options;
+use crate::Options;
+use liquid_core::parser::{FilterArguments, FilterParametersReflection, ParameterReflection};
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+use std::fmt;
+
+/// Is `value` a string, rather than a number or a boolean?
+fn is_string(value: &dyn ValueView) -> bool {
+  value.is_scalar() && value.type_name() == "string"
+}
+
+/// How [`convert_value`] converts the keys of the objects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyPolicy {
+  /// Keep the keys as is.
+  #[default]
+  Keep,
+  /// Convert the keys of the top-level object only.
+  Shallow(HeckCase),
+  /// Convert the keys of the nested objects too.
+  Recursive(HeckCase),
+}
+
+/// How [`convert_value`] converts the string values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValuePolicy {
+  /// Keep the values as is.
+  #[default]
+  Keep,
+  /// Convert the string itself, or the string values of the top-level
+  /// object or array only.
+  Shallow(HeckCase),
+  /// Convert the string values of the nested objects and arrays too.
+  Recursive(HeckCase),
+}
+
+impl KeyPolicy {
+  /// The case of the keys of an object, at the top level or not.
+  fn case(self, top: bool) -> Option<HeckCase> {
+    match self {
+      KeyPolicy::Keep => None,
+      KeyPolicy::Shallow(case) => top.then_some(case),
+      KeyPolicy::Recursive(case) => Some(case),
+    }
+  }
+}
+
+impl ValuePolicy {
+  /// The case of the string values of an object or an array, at the top
+  /// level or not.
+  fn case(self, top: bool) -> Option<HeckCase> {
+    match self {
+      ValuePolicy::Keep => None,
+      ValuePolicy::Shallow(case) => top.then_some(case),
+      ValuePolicy::Recursive(case) => Some(case),
+    }
+  }
+}
+
+/// Convert the keys and the string values of `value`, an object, an array
+/// or a string, as the `restyle` filter does. The numbers and booleans are
+/// kept, as are the nested objects and arrays unless a policy is
+/// recursive.
+///
+/// ```
+/// use liquid_heck::{convert_value, HeckCase, KeyPolicy, ValuePolicy};
+///
+/// let globals = liquid::object!({ "user_name": "jane doe", "extra": { "home_dir": "/home" } });
+/// let converted = convert_value(
+///     &globals,
+///     KeyPolicy::Recursive(HeckCase::LowerCamel),
+///     ValuePolicy::Shallow(HeckCase::Title),
+/// );
+/// assert_eq!(
+///     converted,
+///     liquid::model::value!({ "userName": "Jane Doe", "extra": { "homeDir": "/home" } })
+/// );
+/// ```
+pub fn convert_value(value: &dyn ValueView, keys: KeyPolicy, values: ValuePolicy) -> Value {
+  convert_value_with(value, keys, values, &Options::default())
+}
+
+/// [`convert_value`] honoring `options`.
+pub fn convert_value_with(
+  value: &dyn ValueView,
+  keys: KeyPolicy,
+  values: ValuePolicy,
+  options: &Options,
+) -> Value {
+  walk(value, keys, values, options, true)
+}
+
+fn walk(
+  value: &dyn ValueView,
+  keys: KeyPolicy,
+  values: ValuePolicy,
+  options: &Options,
+  top: bool,
+) -> Value {
+  let nested = matches!(keys, KeyPolicy::Recursive(_)) || matches!(values, ValuePolicy::Recursive(_));
+  let item = |item: &dyn ValueView| match values.case(top) {
+    Some(case) if is_string(item) => {
+      Value::scalar(conversion::convert(case, item.to_kstr().as_str(), options))
+    }
+    _ if nested && (item.is_array() || item.is_object()) => walk(item, keys, values, options, false),
+    _ => item.to_value(),
+  };
+  if let Some(array) = value.as_array() {
+    Value::Array(array.values().map(item).collect())
+  } else if let Some(object) = value.as_object() {
+    let key_case = keys.case(top);
+    Value::Object(
+      object
+        .iter()
+        .map(|(key, value)| {
+          let key = match key_case {
+            Some(case) => conversion::convert(case, key.as_str(), options).into(),
+            None => key.into_owned(),
+          };
+          (key, item(value))
+        })
+        .collect(),
+    )
+  } else {
+    item(value)
+  }
+}
+
+#[derive(Debug, FilterParameters)]
+struct CaseValuesArgs {
+  #[parameter(
+    description = "Also convert the values of the nested objects and arrays.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  recursive: Option<Expression>,
+}
+
+/// A filter converting the string values of an object or an array, keeping
+/// the keys: `{{ payload | snakecase_values }}`.
+///
+/// Registered as the name of the case filter followed by `_values`.
+#[derive(Clone, Debug)]
+pub struct CaseValues {
+  name: String,
+  description: String,
+  case: HeckCase,
+}
+
+impl CaseValues {
+  pub(crate) fn new(case: HeckCase) -> Self {
+    CaseValues {
+      name: format!("{}_values", case.filter_name()),
+      description: case
+        .description()
+        .replace("the string", "the string values of the object or array"),
+      case,
+    }
+  }
+}
+
+impl FilterReflection for CaseValues {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn description(&self) -> &str {
+    &self.description
+  }
+
+  fn positional_parameters(&self) -> &'static [ParameterReflection] {
+    CaseValuesArgs::positional_parameters()
+  }
+
+  fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+    CaseValuesArgs::keyword_parameters()
+  }
+}
+
+impl ParseFilter for CaseValues {
+  fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+    Ok(Box::new(CaseValuesFilter(
+      self.clone(),
+      CaseValuesArgs::from_args(arguments)?,
+    )))
+  }
+
+  fn reflection(&self) -> &dyn FilterReflection {
+    self
+  }
+}
+
+#[derive(Debug)]
+struct CaseValuesFilter(CaseValues, CaseValuesArgs);
+
+impl fmt::Display for CaseValuesFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0.name)
+  }
+}
+
+impl Filter for CaseValuesFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(input, runtime)))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.1.evaluate(runtime)?;
+    let values = match args.recursive.unwrap_or(false) {
+      true => ValuePolicy::Recursive(self.0.case),
+      false => ValuePolicy::Shallow(self.0.case),
+    };
+    let options = runtime_options(runtime)?;
+    let output = convert_value_with(input, KeyPolicy::Keep, values, &options);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "{}: {} -> {}",
+      self.0.name,
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+/// The case named by the `argument` of a filter or a tag: a case filter
+/// name or alias, or a short style name such as `camel`, as
+/// [`HeckCase`] parses them.
+pub(crate) fn case_argument(argument: &'static str, name: &str) -> Result<HeckCase> {
+  name
+    .parse()
+    .map_err(|_| unknown_value(argument, name, &HeckCase::ALL.map(HeckCase::name)))
+}
+
+#[cfg(feature = "filter-restyle")]
+#[derive(Debug, FilterParameters)]
+struct RestyleArgs {
+  #[parameter(
+    description = "The case filter converting the keys, e.g. \"lowercamelcase\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  keys: Option<Expression>,
+  #[parameter(
+    description = "The case filter converting the string values, e.g. \"shoutysnakecase\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  values: Option<Expression>,
+  #[parameter(
+    description = "Also convert the nested objects and arrays.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  recursive: Option<Expression>,
+}
+
+/// Convert the keys and the string values of an object to independent
+/// cases: `{{ config | restyle: keys: "lowercamelcase", values:
+/// "shoutysnakecase" }}`. The cases are named after their filter, or by
+/// their short name such as `camel`.
+#[cfg(feature = "filter-restyle")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "restyle",
+  description = "Convert the keys and the string values of the object to the given cases.",
+  parameters(RestyleArgs),
+  parsed(RestyleFilter)
+)]
+pub struct Restyle;
+
+#[cfg(feature = "filter-restyle")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "restyle"]
+struct RestyleFilter {
+  #[parameters]
+  args: RestyleArgs,
+}
+
+#[cfg(feature = "filter-restyle")]
+impl Filter for RestyleFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(input, runtime)))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let keys = args
+      .keys
+      .map(|name| case_argument("keys", &name))
+      .transpose()?;
+    let values = args
+      .values
+      .map(|name| case_argument("values", &name))
+      .transpose()?;
+    let recursive = args.recursive.unwrap_or(false);
+    let keys = match keys {
+      Some(case) if recursive => KeyPolicy::Recursive(case),
+      Some(case) => KeyPolicy::Shallow(case),
+      None => KeyPolicy::Keep,
+    };
+    let values = match values {
+      Some(case) if recursive => ValuePolicy::Recursive(case),
+      Some(case) => ValuePolicy::Shallow(case),
+      None => ValuePolicy::Keep,
+    };
+    let options = runtime_options(runtime)?;
+    let output = convert_value_with(input, keys, values, &options);
+    #[cfg(feature = "log")]
+    log::debug!(
+      "restyle: {} -> {}",
+      crate::instrument::Redacted::Value(input),
+      crate::instrument::Redacted::Value(&output)
+    );
+    Ok(output)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn case_values() {
+    let filter = CaseValues::new(HeckCase::ShoutySnake);
+    assert_eq!(filter.name(), "shoutysnakecase_values");
+    assert_eq!(
+      filter.description(),
+      "Convert the string values of the object or array to SHOUTY_SNAKE_CASE."
+    );
+    let payload = liquid_core::value!({
+      "status": "in progress",
+      "count": 2,
+      "tags": ["new item"],
+    });
+    assert_eq!(
+      liquid_core::call_filter!(filter, payload).unwrap(),
+      liquid_core::value!({
+        "status": "IN_PROGRESS",
+        "count": 2,
+        "tags": ["new item"],
+      })
+    );
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(CaseValues::new(HeckCase::Kebab))
+      .build()
+      .unwrap()
+      .parse("{% assign v = payload | kebabcase_values: recursive: true %}{{ v.tags[0] }} {{ v.nested.kind }}")
+      .unwrap();
+    let globals = liquid::object!({
+      "payload": { "tags": ["New Item"], "nested": { "kind": "BigThing" } },
+    });
+    assert_eq!(template.render(&globals).unwrap(), "new-item big-thing");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-restyle")]
+  fn restyle() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(Restyle)
+      .build()
+      .unwrap();
+    let globals = liquid::object!({
+      "config": { "api_url": "base url", "retry_policy": { "max_tries": "three" } },
+    });
+    let render = |template| parser.parse(template)?.render(&globals);
+    assert_eq!(
+      render(
+        "{% assign c = config | restyle: keys: 'lowercamelcase', values: 'shouty_snake_case' %}\
+         {{ c.apiUrl }} {{ c.retryPolicy.max_tries }}"
+      )
+      .unwrap(),
+      "BASE_URL three"
+    );
+    assert_eq!(
+      render(
+        "{% assign c = config | restyle: keys: 'kebabcase', recursive: true %}\
+         {{ c.api-url }} {{ c.retry-policy.max-tries }}"
+      )
+      .unwrap(),
+      "base url three"
+    );
+    let error = render("{{ config | restyle: keys: 'upcase' }}")
+      .unwrap_err()
+      .to_string();
+    assert!(error.contains("argument=keys"), "{error}");
+    assert!(error.contains("value=\"upcase\""), "{error}");
+    assert!(
+      error.contains("\"shouty_snake\", \"snake\", \"title\", \"train\" or \"upper_camel\""),
+      "{error}"
+    );
+  }
+
+  #[test]
+  fn convert_value() {
+    let value = liquid_core::value!({
+      "user_name": "jane doe",
+      "roles": ["site admin", { "role_name": "owner" }],
+      "active": true,
+    });
+    assert_eq!(
+      super::convert_value(
+        &value,
+        KeyPolicy::Shallow(HeckCase::UpperCamel),
+        ValuePolicy::Recursive(HeckCase::Kebab)
+      ),
+      liquid_core::value!({
+        "UserName": "jane-doe",
+        "Roles": ["site-admin", { "role_name": "owner" }],
+        "Active": true,
+      })
+    );
+    assert_eq!(
+      super::convert_value(&value, KeyPolicy::Keep, ValuePolicy::Keep),
+      value
+    );
+    assert_eq!(
+      super::convert_value(
+        &liquid_core::value!("site admin"),
+        KeyPolicy::Recursive(HeckCase::Snake),
+        ValuePolicy::Shallow(HeckCase::ShoutySnake)
+      ),
+      liquid_core::value!("SITE_ADMIN")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "log")]
+  fn logs_conversions() {
+    let logs = crate::instrument::captured_logs(|| {
+      let filter = CaseValues::new(HeckCase::Snake);
+      liquid_core::call_filter!(filter, liquid_core::value!({ "name": "Jane Doe" })).unwrap();
+    });
+    assert_eq!(
+      logs,
+      [
+        "snakecase_values: Object({\"name\": Scalar(Str(\"Jane Doe\"))}) \
+         -> Object({\"name\": Scalar(Str(\"jane_doe\"))})"
+      ]
+    );
+  }
+}
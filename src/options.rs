@@ -0,0 +1,1191 @@
+//! Options tuning the case conversions.
+use crate::spelling::{convert_units, spell_symbols};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// Options tuning how the input is split into words and how the words are
+/// cased.
+///
+/// The same options are understood everywhere: by the filters
+/// (`with_options()`), by the `heck_defaults` tag and the `heck_config`
+/// global, by the `conversion::to_*_with()` functions and by the `--option`
+/// flag of the command line. The default options give exactly the output of
+/// the [Heck](https://crates.io/crates/heck) conversions.
+///
+/// Build them field by field, or with [`Options::builder`]:
+///
+/// ```
+/// use liquid_heck::{AcronymStyle, Options};
+///
+/// let options = Options::builder()
+///     .acronyms(["ID", "URL"])
+///     .acronym_style(AcronymStyle::Preserve)
+///     .digit_boundary(true)
+///     .build();
+/// assert_eq!(options.acronyms, ["ID", "URL"]);
+/// ```
+///
+/// With the `serde` feature, the options can be loaded from TOML, YAML, ...
+/// Missing fields take their default value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Options {
+  /// Words kept fully uppercase in the capitalized cases (UpperCamelCase,
+  /// Title Case, ...), e.g. `["ID", "URL"]` turns `user_id` into `UserID`.
+  /// Matched case-insensitively.
+  pub acronyms: Vec<String>,
+  /// How the acronyms are cased in the capitalized cases.
+  pub acronym_style: AcronymStyle,
+  /// How the Roman numerals (`iv`, `XII`) are cased in the capitalized
+  /// cases: `Upper` turns `chapter iv` into `Chapter IV`. Some words, like
+  /// `mix`, are valid numerals too.
+  pub roman_numerals: TokenStyle,
+  /// How the version tokens (`v2`, `V10`) are cased in the capitalized
+  /// cases: `Preserve` turns `api v2 overview` into `Api v2 Overview`.
+  pub version_tokens: TokenStyle,
+  /// Keep the version strings (`1.2`, `v1.2.3-rc.1`) as written, as one
+  /// word: `release v1.2.3-rc.1 notes` becomes `release-v1.2.3-rc.1-notes`
+  /// instead of `release-v1-2-3-rc-1-notes`.
+  pub version_strings: bool,
+  /// Keep the opaque tokens (UUIDs, Git SHAs, long hexadecimal or base64
+  /// strings) as written, as one word: `commit 9fceb02d0ae5` becomes
+  /// `commit_9fceb02d0ae5`, and a UUID keeps its hyphens.
+  pub opaque_tokens: bool,
+  /// Split words between letters and digits: `hello21world` becomes
+  /// `hello_21_world` instead of `hello21world`.
+  pub digit_boundary: bool,
+  /// Split words where the script changes between Latin, Greek and
+  /// Cyrillic letters: `получитьuserId` becomes `получить_user_id` instead
+  /// of `получитьuser_id`.
+  pub script_boundary: bool,
+  /// Keep the leading and trailing underscores of the input:
+  /// `_private_field` becomes `_privateField` instead of `privateField`.
+  pub preserve_underscores: bool,
+  /// Only ASCII letters and digits make words, everything else separates
+  /// them: `café au lait` becomes `caf_au_lait`.
+  pub ascii_mode: bool,
+  /// What Title Case does with a leading article (`The`, `A`, `An`), for
+  /// sort keys: `Move` turns `the great gatsby` into `Great Gatsby, The`.
+  pub leading_article: ArticleStyle,
+  /// The casing rules of a language: `Fr` turns `la vie de l'été`
+  /// into `La Vie de l'Été` instead of `La Vie De L Été`.
+  pub lang: Lang,
+  /// With [`Lang::De`], uppercase `ß` as `SS` rather than `ẞ`:
+  /// `GROSSE_STRASSE` instead of `GROẞE_STRAẞE`.
+  pub eszett_as_ss: bool,
+  /// How the runs of Chinese, Japanese and Korean characters are split:
+  /// `Word` turns `hello世界World` into `hello_世界_world` instead of
+  /// `hello世界_world`.
+  pub cjk: CjkStyle,
+  /// Remove the leading and trailing whitespace of the input before the
+  /// conversion, e.g. of the cells pasted from a spreadsheet:
+  /// `" _id "` becomes `_id` with
+  /// [`preserve_underscores`](Self::preserve_underscores).
+  pub trim: bool,
+  /// Replace the runs of spaces and tabs of the input by one space before
+  /// the conversion, for the filters keeping the separators:
+  /// `"Content \t Type"` becomes `CONTENT TYPE` with `screaming`. The line
+  /// breaks are kept.
+  pub collapse_ws: bool,
+  /// Remove the soft hyphens and the other discretionary characters of the
+  /// input, e.g. copied from a PDF, before the conversion:
+  /// `"identi\u{AD}fier"` becomes `identifier` instead of `identi_fier`.
+  pub strip_soft_hyphens: bool,
+  /// Remove the zero-width characters and the bidi controls of the input
+  /// before the conversion, so that no invisible character reaches an
+  /// identifier or a slug: `"user\u{200B}name"` becomes `username`, also
+  /// with the target filters.
+  pub strip_invisible: bool,
+  /// What the filters do with the C0 and C1 control characters of the
+  /// input, tabs and line breaks excepted, e.g. when the identifiers come
+  /// from untrusted input.
+  pub control_chars: ControlStyle,
+  /// The punctuation characters kept in the words instead of separating
+  /// them: `"'+"` turns `C++ Guide` into `c++-guide` and `don't panic` into
+  /// `don't-panic`.
+  pub keep_punctuation: String,
+  /// What the filters do with the other punctuation of the input.
+  pub punctuation: PunctuationStyle,
+  /// Spell out the common symbols as words before the conversion:
+  /// `R&D @ 50%` becomes `r_and_d_at_50_percent` instead of `r_d_50`.
+  /// The `spell_symbols` filter takes a custom mapping.
+  pub spell_symbols: bool,
+  /// What the conversions do with the currency amounts and the quantities:
+  /// `Spell` turns `$19.99 for 500ml` into `19_99_dollars_for_500_milliliters`,
+  /// `Keep` into `19.99_for_500ml`.
+  pub units: UnitStyle,
+  /// Spell out the numbers as English words before the conversion, for the
+  /// targets forbidding a leading digit: `Leading` turns `3d_model` into
+  /// `three_d_model`.
+  #[cfg(feature = "number-words")]
+  pub spell_numbers: NumberStyle,
+  /// Drop the diacritics of the letters before the conversion, keeping the
+  /// letters without decomposition: `Crème Brûlée` becomes `creme_brulee`,
+  /// `Øresund` stays `øresund`. Lighter than `ascii_mode` and
+  /// transliteration, for Latin-script text.
+  #[cfg(feature = "diacritics")]
+  pub strip_diacritics: bool,
+  /// Replace the characters confusable with an ASCII letter or digit before
+  /// the conversion, so that a slug or an identifier generated from user
+  /// input can't spoof another one: `pаypаl` with Cyrillic `а` becomes
+  /// `paypal`.
+  #[cfg(feature = "homoglyphs")]
+  pub normalize_homoglyphs: bool,
+  /// The longest input, in bytes, the filters convert. Longer inputs fail
+  /// the render instead of allocating without bound. Defaults to
+  /// [`DEFAULT_MAX_INPUT_LENGTH`]; `usize::MAX` removes the limit.
+  pub max_input_length: usize,
+}
+
+/// The default [`Options::max_input_length`]: 1 MiB.
+pub const DEFAULT_MAX_INPUT_LENGTH: usize = 1 << 20;
+
+impl Default for Options {
+  fn default() -> Self {
+    Options {
+      acronyms: Vec::new(),
+      acronym_style: AcronymStyle::default(),
+      roman_numerals: TokenStyle::default(),
+      version_tokens: TokenStyle::default(),
+      version_strings: false,
+      opaque_tokens: false,
+      digit_boundary: false,
+      script_boundary: false,
+      preserve_underscores: false,
+      ascii_mode: false,
+      leading_article: ArticleStyle::default(),
+      lang: Lang::default(),
+      eszett_as_ss: false,
+      cjk: CjkStyle::default(),
+      trim: false,
+      collapse_ws: false,
+      strip_soft_hyphens: false,
+      strip_invisible: false,
+      control_chars: ControlStyle::default(),
+      keep_punctuation: String::new(),
+      punctuation: PunctuationStyle::default(),
+      spell_symbols: false,
+      units: UnitStyle::default(),
+      #[cfg(feature = "number-words")]
+      spell_numbers: NumberStyle::default(),
+      #[cfg(feature = "diacritics")]
+      strip_diacritics: false,
+      #[cfg(feature = "homoglyphs")]
+      normalize_homoglyphs: false,
+      max_input_length: DEFAULT_MAX_INPUT_LENGTH,
+    }
+  }
+}
+
+/// How the acronyms are cased in the capitalized cases and lowerCamelCase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AcronymStyle {
+  /// The [`acronyms`](Options::acronyms) are fully uppercase, except as
+  /// the first word of lowerCamelCase: `xmlHTTPRequest`.
+  #[default]
+  Upper,
+  /// The acronyms are cased like the other words: `xmlHttpRequest`.
+  Lower,
+  /// The words written in uppercase in the input keep their spelling, even
+  /// as the first word of lowerCamelCase: `XMLHttpRequest`. The
+  /// [`acronyms`](Options::acronyms) are not needed.
+  Preserve,
+}
+
+/// How the special words, like Roman numerals, are cased in the capitalized
+/// cases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TokenStyle {
+  /// Like the other words: `Iv`, `V2`.
+  #[default]
+  Convert,
+  /// Fully uppercase: `IV`, `V2`.
+  Upper,
+  /// As written in the input: `iv`, `v2`.
+  Preserve,
+}
+
+/// What Title Case does with a leading article.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ArticleStyle {
+  /// Like the other words: `The Great Gatsby`.
+  #[default]
+  Keep,
+  /// Removed: `Great Gatsby`.
+  Drop,
+  /// Moved to the end after a comma: `Great Gatsby, The`.
+  Move,
+}
+
+/// The language whose casing rules the conversions follow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Lang {
+  /// Every word capitalized, like Heck: `La Vie De L Été`.
+  #[default]
+  None,
+  /// French: the particles (`de`, `la`, `et`, ...) stay lowercase after
+  /// the first word and the elisions (`l'`, `d'`, `qu'`, ...) keep their
+  /// apostrophe: `La Vie de l'Été`.
+  Fr,
+  /// Spanish: the particles (`de`, `del`, `la`, `y`, ...) stay lowercase
+  /// after the first word: `El Señor de los Anillos`.
+  Es,
+  /// German: Title Case only capitalizes the first word and keeps the
+  /// others as written, so the nouns keep their capital: `Die große
+  /// Straße`. Uppercase `ß` is `ẞ`, see [`Options::eszett_as_ss`].
+  De,
+}
+
+/// How the runs of Chinese, Japanese and Korean characters are split into
+/// words.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CjkStyle {
+  /// Like the other letters, without case: a run joins the neighboring
+  /// words, `東京Tower` is one word.
+  #[default]
+  Letters,
+  /// Each run is a word on its own: `東京_tower`.
+  Word,
+  /// Each run, with its CJK punctuation and fullwidth characters, is a
+  /// word kept as written: `東京、日本_tower`.
+  Preserve,
+}
+
+/// What the filters do with the punctuation of the input not kept by
+/// [`Options::keep_punctuation`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PunctuationStyle {
+  /// Drop it, like Heck: `C++ Guide` becomes `c-guide`.
+  #[default]
+  Drop,
+  /// Fail the render, for the names that must not lose characters. The
+  /// `conversion` functions still drop it.
+  Error,
+}
+
+/// What the filters do with the control characters of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ControlStyle {
+  /// Keep them, like Heck: they separate the words.
+  #[default]
+  Keep,
+  /// Remove them: `"user\u{7}name"` becomes `username`.
+  Strip,
+  /// Replace each with the replacement character `U+FFFD`, a visible
+  /// marker in the outputs keeping the punctuation.
+  Replace,
+  /// Fail the render, naming the character and its byte offset in the
+  /// input. The `conversion` functions keep them.
+  Error,
+}
+
+/// What the conversions do with the currency amounts (`$19.99`) and the
+/// quantities (`500 ml`) of the input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum UnitStyle {
+  /// Like Heck, the currency symbols are dropped and the amounts split at
+  /// their decimal point: `price_19_99`.
+  #[default]
+  Drop,
+  /// The currency symbols and the unit abbreviations are spelled out after
+  /// the amount: `price_19_99_dollars`, `500_milliliters`.
+  Spell,
+  /// The currency symbols are dropped, the amounts and their unit kept as
+  /// one word: `price_19.99`, `500ml`, even with
+  /// [`digit_boundary`](Options::digit_boundary).
+  Keep,
+}
+
+/// Which numbers of the input are spelled out as words.
+#[cfg(feature = "number-words")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum NumberStyle {
+  /// None, the digits are kept: `3d_model_2`.
+  #[default]
+  Keep,
+  /// The number starting the input: `three_d_model_2`.
+  Leading,
+  /// All of them: `three_d_model_two`.
+  All,
+}
+
+/// The names of the [`Options`] fields, as understood in templates and
+/// environment variables.
+pub(crate) const OPTION_NAMES: [&str;
+  24 + cfg!(feature = "number-words") as usize
+    + cfg!(feature = "diacritics") as usize
+    + cfg!(feature = "homoglyphs") as usize] = [
+  "acronyms",
+  "acronym_style",
+  "roman_numerals",
+  "version_tokens",
+  "version_strings",
+  "opaque_tokens",
+  "digit_boundary",
+  "script_boundary",
+  "preserve_underscores",
+  "ascii_mode",
+  "leading_article",
+  "lang",
+  "eszett_as_ss",
+  "cjk",
+  "trim",
+  "collapse_ws",
+  "strip_soft_hyphens",
+  "strip_invisible",
+  "control_chars",
+  "keep_punctuation",
+  "punctuation",
+  "spell_symbols",
+  "units",
+  #[cfg(feature = "number-words")]
+  "spell_numbers",
+  #[cfg(feature = "diacritics")]
+  "strip_diacritics",
+  #[cfg(feature = "homoglyphs")]
+  "normalize_homoglyphs",
+  "max_input_length",
+];
+
+/// The former name of [`Options`].
+pub type CaseOptions = Options;
+
+impl AcronymStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["upper", "lower", "preserve"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "upper" => AcronymStyle::Upper,
+      "lower" => AcronymStyle::Lower,
+      "preserve" => AcronymStyle::Preserve,
+      _ => return None,
+    })
+  }
+}
+
+impl TokenStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["convert", "upper", "preserve"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "convert" => TokenStyle::Convert,
+      "upper" => TokenStyle::Upper,
+      "preserve" => TokenStyle::Preserve,
+      _ => return None,
+    })
+  }
+}
+
+impl ArticleStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["keep", "drop", "move"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "keep" => ArticleStyle::Keep,
+      "drop" => ArticleStyle::Drop,
+      "move" => ArticleStyle::Move,
+      _ => return None,
+    })
+  }
+}
+
+impl PunctuationStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["drop", "error"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "drop" => PunctuationStyle::Drop,
+      "error" => PunctuationStyle::Error,
+      _ => return None,
+    })
+  }
+}
+
+impl ControlStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["keep", "strip", "replace", "error"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "keep" => ControlStyle::Keep,
+      "strip" => ControlStyle::Strip,
+      "replace" => ControlStyle::Replace,
+      "error" => ControlStyle::Error,
+      _ => return None,
+    })
+  }
+}
+
+impl UnitStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["drop", "spell", "keep"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "drop" => UnitStyle::Drop,
+      "spell" => UnitStyle::Spell,
+      "keep" => UnitStyle::Keep,
+      _ => return None,
+    })
+  }
+}
+
+#[cfg(feature = "number-words")]
+impl NumberStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["keep", "leading", "all"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "keep" => NumberStyle::Keep,
+      "leading" => NumberStyle::Leading,
+      "all" => NumberStyle::All,
+      _ => return None,
+    })
+  }
+}
+
+impl Lang {
+  /// The names of the languages with their own rules.
+  pub(crate) const NAMES: &'static [&'static str] = &["fr", "es", "de", "en", "none"];
+
+  /// The language spelled `name`, an ISO 639-1 code, in templates and
+  /// configuration files. `en` and `none` are the default rules.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "none" | "en" => Lang::None,
+      "fr" => Lang::Fr,
+      "es" => Lang::Es,
+      "de" => Lang::De,
+      _ => return None,
+    })
+  }
+}
+
+impl CjkStyle {
+  /// The names of the styles, as spelled in templates and configuration
+  /// files.
+  pub(crate) const NAMES: &'static [&'static str] = &["letters", "word", "preserve"];
+
+  /// The style spelled `name` in templates and configuration files.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "letters" => CjkStyle::Letters,
+      "word" => CjkStyle::Word,
+      "preserve" => CjkStyle::Preserve,
+      _ => return None,
+    })
+  }
+}
+
+/// Error for an environment variable holding an invalid option value, see
+/// [`Options::from_env`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidOption {
+  /// The variable name.
+  pub variable: String,
+  /// The invalid value.
+  pub value: String,
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for InvalidOption {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "invalid value `{}` for `{}`", self.value, self.variable)
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidOption {}
+
+impl Options {
+  /// A builder starting from the default options.
+  pub fn builder() -> OptionsBuilder {
+    OptionsBuilder::default()
+  }
+
+  /// The options set by the `LIQUID_HECK_*` environment variables, named
+  /// after the fields: `LIQUID_HECK_ACRONYMS=ID,URL`,
+  /// `LIQUID_HECK_DIGIT_BOUNDARY=true`, `LIQUID_HECK_ACRONYM_STYLE=preserve`,
+  /// ... The fields without a variable keep their default value.
+  ///
+  /// The booleans accept `true`, `false`, `1`, `0`, `yes`, `no`, `on` and
+  /// `off`. Fails on the first variable holding another value.
+  ///
+  /// ```no_run
+  /// # #[cfg(feature = "filter-snake")]
+  /// # {
+  /// use liquid_heck::{Options, SnakeCase};
+  ///
+  /// let snakecase = SnakeCase::with_options(Options::from_env().unwrap());
+  /// let parser = liquid::ParserBuilder::with_stdlib().filter(snakecase).build();
+  /// # }
+  /// ```
+  #[cfg(feature = "std")]
+  pub fn from_env() -> Result<Self, InvalidOption> {
+    Self::from_vars(|variable| std::env::var(variable).ok())
+  }
+
+  /// The options set by the `LIQUID_HECK_*` variables, as read by `var`.
+  #[cfg(feature = "std")]
+  fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, InvalidOption> {
+    let flag = |value: &str| match value.to_lowercase().as_str() {
+      "true" | "1" | "yes" | "on" => Some(true),
+      "false" | "0" | "no" | "off" | "" => Some(false),
+      _ => None,
+    };
+    let mut options = Options::default();
+    for field in OPTION_NAMES {
+      let variable = format!("LIQUID_HECK_{}", field.to_uppercase());
+      let Some(value) = var(&variable) else {
+        continue;
+      };
+      let trimmed = value.trim();
+      let valid = match field {
+        "acronyms" => {
+          options.acronyms = trimmed
+            .split(',')
+            .map(str::trim)
+            .filter(|acronym| !acronym.is_empty())
+            .map(String::from)
+            .collect();
+          true
+        }
+        "acronym_style" => AcronymStyle::from_name(trimmed)
+          .map(|style| options.acronym_style = style)
+          .is_some(),
+        "roman_numerals" => TokenStyle::from_name(trimmed)
+          .map(|style| options.roman_numerals = style)
+          .is_some(),
+        "version_tokens" => TokenStyle::from_name(trimmed)
+          .map(|style| options.version_tokens = style)
+          .is_some(),
+        "version_strings" => flag(trimmed)
+          .map(|flag| options.version_strings = flag)
+          .is_some(),
+        "opaque_tokens" => flag(trimmed)
+          .map(|flag| options.opaque_tokens = flag)
+          .is_some(),
+        "digit_boundary" => flag(trimmed)
+          .map(|flag| options.digit_boundary = flag)
+          .is_some(),
+        "script_boundary" => flag(trimmed)
+          .map(|flag| options.script_boundary = flag)
+          .is_some(),
+        "preserve_underscores" => flag(trimmed)
+          .map(|flag| options.preserve_underscores = flag)
+          .is_some(),
+        "ascii_mode" => flag(trimmed)
+          .map(|flag| options.ascii_mode = flag)
+          .is_some(),
+        "leading_article" => ArticleStyle::from_name(trimmed)
+          .map(|style| options.leading_article = style)
+          .is_some(),
+        "lang" => Lang::from_name(trimmed)
+          .map(|lang| options.lang = lang)
+          .is_some(),
+        "eszett_as_ss" => flag(trimmed)
+          .map(|flag| options.eszett_as_ss = flag)
+          .is_some(),
+        "cjk" => CjkStyle::from_name(trimmed)
+          .map(|style| options.cjk = style)
+          .is_some(),
+        "trim" => flag(trimmed).map(|flag| options.trim = flag).is_some(),
+        "collapse_ws" => flag(trimmed)
+          .map(|flag| options.collapse_ws = flag)
+          .is_some(),
+        "strip_soft_hyphens" => flag(trimmed)
+          .map(|flag| options.strip_soft_hyphens = flag)
+          .is_some(),
+        "strip_invisible" => flag(trimmed)
+          .map(|flag| options.strip_invisible = flag)
+          .is_some(),
+        "control_chars" => ControlStyle::from_name(trimmed)
+          .map(|style| options.control_chars = style)
+          .is_some(),
+        "keep_punctuation" => {
+          options.keep_punctuation = trimmed.into();
+          true
+        }
+        "punctuation" => PunctuationStyle::from_name(trimmed)
+          .map(|style| options.punctuation = style)
+          .is_some(),
+        "spell_symbols" => flag(trimmed)
+          .map(|flag| options.spell_symbols = flag)
+          .is_some(),
+        "units" => UnitStyle::from_name(trimmed)
+          .map(|style| options.units = style)
+          .is_some(),
+        #[cfg(feature = "number-words")]
+        "spell_numbers" => NumberStyle::from_name(trimmed)
+          .map(|style| options.spell_numbers = style)
+          .is_some(),
+        #[cfg(feature = "diacritics")]
+        "strip_diacritics" => flag(trimmed)
+          .map(|flag| options.strip_diacritics = flag)
+          .is_some(),
+        #[cfg(feature = "homoglyphs")]
+        "normalize_homoglyphs" => flag(trimmed)
+          .map(|flag| options.normalize_homoglyphs = flag)
+          .is_some(),
+        "max_input_length" => trimmed
+          .parse()
+          .map(|length| options.max_input_length = length)
+          .is_ok(),
+        _ => true,
+      };
+      if !valid {
+        return Err(InvalidOption { variable, value });
+      }
+    }
+    Ok(options)
+  }
+
+  /// These options, with the fields left at their default value taken from
+  /// `base` instead.
+  pub(crate) fn over(&self, base: &Options) -> Options {
+    let default = Options::default();
+    macro_rules! pick {
+      ($field:ident) => {
+        match self.$field == default.$field {
+          true => base.$field.clone(),
+          false => self.$field.clone(),
+        }
+      };
+    }
+    Options {
+      acronyms: pick!(acronyms),
+      acronym_style: pick!(acronym_style),
+      roman_numerals: pick!(roman_numerals),
+      version_tokens: pick!(version_tokens),
+      version_strings: pick!(version_strings),
+      opaque_tokens: pick!(opaque_tokens),
+      digit_boundary: pick!(digit_boundary),
+      script_boundary: pick!(script_boundary),
+      preserve_underscores: pick!(preserve_underscores),
+      ascii_mode: pick!(ascii_mode),
+      leading_article: pick!(leading_article),
+      lang: pick!(lang),
+      eszett_as_ss: pick!(eszett_as_ss),
+      cjk: pick!(cjk),
+      trim: pick!(trim),
+      collapse_ws: pick!(collapse_ws),
+      strip_soft_hyphens: pick!(strip_soft_hyphens),
+      strip_invisible: pick!(strip_invisible),
+      control_chars: pick!(control_chars),
+      keep_punctuation: pick!(keep_punctuation),
+      punctuation: pick!(punctuation),
+      spell_symbols: pick!(spell_symbols),
+      units: pick!(units),
+      #[cfg(feature = "number-words")]
+      spell_numbers: pick!(spell_numbers),
+      #[cfg(feature = "diacritics")]
+      strip_diacritics: pick!(strip_diacritics),
+      #[cfg(feature = "homoglyphs")]
+      normalize_homoglyphs: pick!(normalize_homoglyphs),
+      max_input_length: pick!(max_input_length),
+    }
+  }
+
+  /// Do the options give exactly the Heck output? The limits and the
+  /// preprocessing of the input, see [`preprocess`](Self::preprocess), are
+  /// ignored.
+  pub(crate) fn is_heck(&self) -> bool {
+    *self
+      == Options {
+        trim: self.trim,
+        collapse_ws: self.collapse_ws,
+        strip_soft_hyphens: self.strip_soft_hyphens,
+        strip_invisible: self.strip_invisible,
+        control_chars: self.control_chars,
+        punctuation: self.punctuation,
+        spell_symbols: self.spell_symbols,
+        units: match self.units {
+          UnitStyle::Keep => UnitStyle::Drop,
+          units => units,
+        },
+        #[cfg(feature = "number-words")]
+        spell_numbers: self.spell_numbers,
+        #[cfg(feature = "diacritics")]
+        strip_diacritics: self.strip_diacritics,
+        #[cfg(feature = "homoglyphs")]
+        normalize_homoglyphs: self.normalize_homoglyphs,
+        max_input_length: self.max_input_length,
+        ..Options::default()
+      }
+  }
+
+  /// `s` trimmed and with its runs of spaces and tabs collapsed, as set by
+  /// [`trim`](Self::trim) and [`collapse_ws`](Self::collapse_ws).
+  pub(crate) fn clean_whitespace<'s>(&self, s: &'s str) -> Cow<'s, str> {
+    let s = if self.trim { s.trim() } else { s };
+    if !self.collapse_ws {
+      return Cow::Borrowed(s);
+    }
+    let mut output = String::with_capacity(s.len());
+    let mut blank = false;
+    for c in s.chars() {
+      let is_blank = c.is_whitespace() && !matches!(c, '\n' | '\r');
+      if !is_blank {
+        output.push(c);
+      } else if !blank {
+        output.push(' ');
+      }
+      blank = is_blank;
+    }
+    Cow::Owned(output)
+  }
+
+  /// `s` without the characters the options remove before anything else:
+  /// the discretionary characters with
+  /// [`strip_soft_hyphens`](Self::strip_soft_hyphens), the invisible ones
+  /// with [`strip_invisible`](Self::strip_invisible), the control ones as
+  /// set by [`control_chars`](Self::control_chars).
+  pub(crate) fn strip_chars<'s>(&self, s: &'s str) -> Cow<'s, str> {
+    let strip = |c| {
+      (self.strip_soft_hyphens && is_discretionary(c))
+        || (self.strip_invisible && is_invisible(c))
+        || (self.control_chars == ControlStyle::Strip && is_control(c))
+    };
+    let replace = |c| self.control_chars == ControlStyle::Replace && is_control(c);
+    if !s.contains(|c| strip(c) || replace(c)) {
+      return Cow::Borrowed(s);
+    }
+    Cow::Owned(
+      s.chars()
+        .filter(|&c| !strip(c))
+        .map(|c| if replace(c) { '\u{FFFD}' } else { c })
+        .collect(),
+    )
+  }
+
+  /// `s` prepared for the conversion: its discretionary and invisible
+  /// characters stripped, its whitespace cleaned, its homoglyphs and
+  /// diacritics replaced as set by `normalize_homoglyphs` and
+  /// `strip_diacritics`, its amounts
+  /// rewritten as set by [`units`](Self::units), then its symbols and
+  /// numbers spelled out as set by [`spell_symbols`](Self::spell_symbols)
+  /// and `spell_numbers`.
+  pub(crate) fn preprocess<'s>(&self, s: &'s str) -> Cow<'s, str> {
+    let s = match self.strip_chars(s) {
+      Cow::Borrowed(s) => self.clean_whitespace(s),
+      Cow::Owned(s) => Cow::Owned(self.clean_whitespace(&s).into_owned()),
+    };
+    #[cfg(feature = "homoglyphs")]
+    let s = match self.normalize_homoglyphs {
+      true if !s.is_ascii() => Cow::Owned(crate::translit::normalize_homoglyphs(&s).into_owned()),
+      _ => s,
+    };
+    #[cfg(feature = "diacritics")]
+    let s = match self.strip_diacritics {
+      true if !s.is_ascii() => Cow::Owned(crate::translit::strip_diacritics(&s).into_owned()),
+      _ => s,
+    };
+    let s = match self.units {
+      UnitStyle::Drop => s,
+      units => Cow::Owned(convert_units(&s, units == UnitStyle::Spell)),
+    };
+    let s = match self.spell_symbols {
+      true => Cow::Owned(spell_symbols::<&str, &str>(&s, &[])),
+      false => s,
+    };
+    #[cfg(feature = "number-words")]
+    let s = match self.spell_numbers {
+      NumberStyle::Keep => s,
+      style => Cow::Owned(crate::spelling::spell_numbers(
+        &s,
+        style == NumberStyle::Leading,
+      )),
+    };
+    s
+  }
+
+  /// Is `word` one of the configured acronyms?
+  pub(crate) fn is_acronym(&self, word: &str) -> bool {
+    self.acronyms.iter().any(|acronym| {
+      acronym
+        .chars()
+        .flat_map(char::to_lowercase)
+        .eq(word.chars().flat_map(char::to_lowercase))
+    })
+  }
+}
+
+/// Builder of [`Options`], see [`Options::builder`].
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct OptionsBuilder {
+  options: Options,
+}
+
+/// Is `c` a discretionary character, only shown at a line break: the soft
+/// hyphen and the Mongolian todo soft hyphen.
+fn is_discretionary(c: char) -> bool {
+  matches!(c, '\u{AD}' | '\u{1806}')
+}
+
+/// Is `c` a C0 or C1 control character other than a tab or a line break?
+pub(crate) fn is_control(c: char) -> bool {
+  c.is_control() && !matches!(c, '\t' | '\n' | '\r')
+}
+
+/// Is `c` a zero-width character (space, joiner, non-joiner, byte order
+/// mark) or a bidi control (mark, embedding, override, isolate)?
+pub(crate) fn is_invisible(c: char) -> bool {
+  matches!(
+    c,
+    '\u{200B}'..='\u{200F}'
+      | '\u{180E}'
+      | '\u{2060}'
+      | '\u{FEFF}'
+      | '\u{061C}'
+      | '\u{202A}'..='\u{202E}'
+      | '\u{2066}'..='\u{2069}'
+  )
+}
+
+impl OptionsBuilder {
+  /// Set [`Options::acronyms`].
+  pub fn acronyms<I>(mut self, acronyms: I) -> Self
+  where
+    I: IntoIterator,
+    I::Item: Into<String>,
+  {
+    self.options.acronyms = acronyms.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Set [`Options::acronym_style`].
+  pub fn acronym_style(mut self, style: AcronymStyle) -> Self {
+    self.options.acronym_style = style;
+    self
+  }
+
+  /// Set [`Options::roman_numerals`].
+  pub fn roman_numerals(mut self, style: TokenStyle) -> Self {
+    self.options.roman_numerals = style;
+    self
+  }
+
+  /// Set [`Options::version_tokens`].
+  pub fn version_tokens(mut self, style: TokenStyle) -> Self {
+    self.options.version_tokens = style;
+    self
+  }
+
+  /// Set [`Options::version_strings`].
+  pub fn version_strings(mut self, version_strings: bool) -> Self {
+    self.options.version_strings = version_strings;
+    self
+  }
+
+  /// Set [`Options::opaque_tokens`].
+  pub fn opaque_tokens(mut self, opaque_tokens: bool) -> Self {
+    self.options.opaque_tokens = opaque_tokens;
+    self
+  }
+
+  /// Set [`Options::digit_boundary`].
+  pub fn digit_boundary(mut self, digit_boundary: bool) -> Self {
+    self.options.digit_boundary = digit_boundary;
+    self
+  }
+
+  /// Set [`Options::script_boundary`].
+  pub fn script_boundary(mut self, script_boundary: bool) -> Self {
+    self.options.script_boundary = script_boundary;
+    self
+  }
+
+  /// Set [`Options::preserve_underscores`].
+  pub fn preserve_underscores(mut self, preserve_underscores: bool) -> Self {
+    self.options.preserve_underscores = preserve_underscores;
+    self
+  }
+
+  /// Set [`Options::ascii_mode`].
+  pub fn ascii_mode(mut self, ascii_mode: bool) -> Self {
+    self.options.ascii_mode = ascii_mode;
+    self
+  }
+
+  /// Set [`Options::leading_article`].
+  pub fn leading_article(mut self, style: ArticleStyle) -> Self {
+    self.options.leading_article = style;
+    self
+  }
+
+  /// Set [`Options::lang`].
+  pub fn lang(mut self, lang: Lang) -> Self {
+    self.options.lang = lang;
+    self
+  }
+
+  /// Set [`Options::eszett_as_ss`].
+  pub fn eszett_as_ss(mut self, eszett_as_ss: bool) -> Self {
+    self.options.eszett_as_ss = eszett_as_ss;
+    self
+  }
+
+  /// Set [`Options::cjk`].
+  pub fn cjk(mut self, style: CjkStyle) -> Self {
+    self.options.cjk = style;
+    self
+  }
+
+  /// Set [`Options::trim`].
+  pub fn trim(mut self, trim: bool) -> Self {
+    self.options.trim = trim;
+    self
+  }
+
+  /// Set [`Options::collapse_ws`].
+  pub fn collapse_ws(mut self, collapse_ws: bool) -> Self {
+    self.options.collapse_ws = collapse_ws;
+    self
+  }
+
+  /// Set [`Options::strip_soft_hyphens`].
+  pub fn strip_soft_hyphens(mut self, strip_soft_hyphens: bool) -> Self {
+    self.options.strip_soft_hyphens = strip_soft_hyphens;
+    self
+  }
+
+  /// Set [`Options::strip_invisible`].
+  pub fn strip_invisible(mut self, strip_invisible: bool) -> Self {
+    self.options.strip_invisible = strip_invisible;
+    self
+  }
+
+  /// Set [`Options::control_chars`].
+  pub fn control_chars(mut self, style: ControlStyle) -> Self {
+    self.options.control_chars = style;
+    self
+  }
+
+  /// Set [`Options::keep_punctuation`].
+  pub fn keep_punctuation(mut self, keep_punctuation: impl Into<String>) -> Self {
+    self.options.keep_punctuation = keep_punctuation.into();
+    self
+  }
+
+  /// Set [`Options::punctuation`].
+  pub fn punctuation(mut self, style: PunctuationStyle) -> Self {
+    self.options.punctuation = style;
+    self
+  }
+
+  /// Set [`Options::spell_symbols`].
+  pub fn spell_symbols(mut self, spell_symbols: bool) -> Self {
+    self.options.spell_symbols = spell_symbols;
+    self
+  }
+
+  /// Set [`Options::units`].
+  pub fn units(mut self, style: UnitStyle) -> Self {
+    self.options.units = style;
+    self
+  }
+
+  /// Set [`Options::spell_numbers`].
+  #[cfg(feature = "number-words")]
+  pub fn spell_numbers(mut self, style: NumberStyle) -> Self {
+    self.options.spell_numbers = style;
+    self
+  }
+
+  /// Set [`Options::strip_diacritics`].
+  #[cfg(feature = "diacritics")]
+  pub fn strip_diacritics(mut self, strip_diacritics: bool) -> Self {
+    self.options.strip_diacritics = strip_diacritics;
+    self
+  }
+
+  /// Set [`Options::normalize_homoglyphs`].
+  #[cfg(feature = "homoglyphs")]
+  pub fn normalize_homoglyphs(mut self, normalize_homoglyphs: bool) -> Self {
+    self.options.normalize_homoglyphs = normalize_homoglyphs;
+    self
+  }
+
+  /// Set [`Options::max_input_length`].
+  pub fn max_input_length(mut self, max_input_length: usize) -> Self {
+    self.options.max_input_length = max_input_length;
+    self
+  }
+
+  /// The built options.
+  pub fn build(self) -> Options {
+    self.options
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::vec;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn builder() {
+    let options = Options::builder()
+      .acronyms(["ID"])
+      .version_tokens(TokenStyle::Preserve)
+      .ascii_mode(true)
+      .build();
+    assert_eq!(
+      options,
+      Options {
+        acronyms: vec!["ID".into()],
+        version_tokens: TokenStyle::Preserve,
+        ascii_mode: true,
+        ..Options::default()
+      }
+    );
+    assert_eq!(AcronymStyle::from_name("lower"), Some(AcronymStyle::Lower));
+    assert_eq!(TokenStyle::from_name("lower"), None);
+    assert_eq!(Lang::from_name("es"), Some(Lang::Es));
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serde() {
+    let options: Options = serde_json::from_str(
+      r#"{ "acronyms": ["ID"], "acronym_style": "preserve", "ascii_mode": true }"#,
+    )
+    .unwrap();
+    assert_eq!(
+      options,
+      Options {
+        acronyms: vec!["ID".into()],
+        acronym_style: AcronymStyle::Preserve,
+        ascii_mode: true,
+        ..Options::default()
+      }
+    );
+    let json = serde_json::to_string(&options).unwrap();
+    assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), options);
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn from_vars() {
+    let vars = |vars: &'static [(&str, &str)]| {
+      move |variable: &str| {
+        vars
+          .iter()
+          .find(|(name, _)| *name == variable)
+          .map(|(_, value)| value.to_string())
+      }
+    };
+    assert_eq!(Options::from_vars(vars(&[])), Ok(Options::default()));
+    assert_eq!(
+      Options::from_vars(vars(&[
+        ("LIQUID_HECK_ACRONYMS", "ID, URL,"),
+        ("LIQUID_HECK_DIGIT_BOUNDARY", "yes"),
+        ("LIQUID_HECK_ACRONYM_STYLE", "preserve"),
+        ("LIQUID_HECK_LEADING_ARTICLE", "move"),
+      ])),
+      Ok(
+        Options::builder()
+          .acronyms(["ID", "URL"])
+          .digit_boundary(true)
+          .acronym_style(AcronymStyle::Preserve)
+          .leading_article(ArticleStyle::Move)
+          .build()
+      )
+    );
+    assert_eq!(
+      Options::from_vars(vars(&[("LIQUID_HECK_ASCII_MODE", "maybe")])),
+      Err(InvalidOption {
+        variable: "LIQUID_HECK_ASCII_MODE".into(),
+        value: "maybe".into(),
+      })
+    );
+  }
+
+  #[test]
+  fn clean_whitespace() {
+    let text = " \tFirst  \t Name \n  Age ";
+    assert_eq!(Options::default().clean_whitespace(text), text);
+    let trim = Options::builder().trim(true).build();
+    assert_eq!(trim.clean_whitespace(text), "First  \t Name \n  Age");
+    let collapse = Options::builder().collapse_ws(true).build();
+    assert_eq!(collapse.clean_whitespace(text), " First Name \n Age ");
+    let both = Options::builder().trim(true).collapse_ws(true).build();
+    assert_eq!(both.clean_whitespace(text), "First Name \n Age");
+    assert!(both.is_heck());
+  }
+
+  #[test]
+  fn strip_soft_hyphens() {
+    let text = "identi\u{AD}fier\u{1806}name";
+    assert_eq!(Options::default().strip_chars(text), text);
+    let strip = Options::builder().strip_soft_hyphens(true).build();
+    assert_eq!(strip.strip_chars(text), "identifiername");
+    assert_eq!(strip.preprocess(" soft\u{AD}ware "), " software ");
+    assert!(strip.is_heck());
+  }
+
+  #[test]
+  fn strip_invisible() {
+    let text = "\u{FEFF}user\u{200B}na\u{200D}me\u{202E}txt.exe\u{202C}";
+    assert_eq!(Options::default().strip_chars(text), text);
+    let strip = Options::builder().strip_invisible(true).build();
+    assert_eq!(strip.strip_chars(text), "usernametxt.exe");
+    assert_eq!(strip.strip_chars("soft\u{AD}ware"), "soft\u{AD}ware");
+    assert!(strip.is_heck());
+  }
+
+  #[test]
+  fn control_chars() {
+    let text = "user\u{7}name\tid\u{85}";
+    assert_eq!(Options::default().strip_chars(text), text);
+    let strip = Options::builder()
+      .control_chars(ControlStyle::Strip)
+      .build();
+    assert_eq!(strip.strip_chars(text), "username\tid");
+    let replace = Options::builder()
+      .control_chars(ControlStyle::Replace)
+      .build();
+    assert_eq!(replace.strip_chars(text), "user\u{FFFD}name\tid\u{FFFD}");
+    assert!(replace.is_heck());
+  }
+}
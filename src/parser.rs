@@ -0,0 +1,172 @@
+//! Ready-to-use Liquid parsers. Only available with the `liquid` feature.
+use crate::{FilterRegistry, UnknownFilter};
+use liquid::{Parser, ParserBuilder};
+
+/// Register all the heck filters enabled by the cargo features on `builder`.
+pub fn register_all_filters(builder: ParserBuilder) -> ParserBuilder {
+  FilterRegistry::names()
+    .iter()
+    .fold(builder, |builder, name| {
+      builder.filter(FilterRegistry::get(name).expect("registered filter"))
+    })
+}
+
+/// Register all the heck tags enabled by the cargo features on `builder`.
+///
+/// ```
+/// let builder = liquid_heck::register_all_tags(liquid::ParserBuilder::with_stdlib());
+/// let template = builder
+///     .build()
+///     .unwrap()
+///     .parse("{% convert_keys user to: 'camel' %}{{ user.firstName }}")
+///     .unwrap();
+/// let globals = liquid::object!({ "user": { "first_name": "Ada" } });
+/// assert_eq!(template.render(&globals).unwrap(), "Ada");
+/// ```
+pub fn register_all_tags(builder: ParserBuilder) -> ParserBuilder {
+  #[cfg(feature = "tag-convert-keys")]
+  let builder = builder.tag(crate::ConvertKeysTag);
+  #[cfg(feature = "tag-heck-defaults")]
+  let builder = builder.tag(crate::HeckDefaultsTag);
+  builder
+}
+
+/// Register all the heck filters enabled by the cargo features on `builder`,
+/// both under their name and under their [aliases](FilterRegistry::aliases).
+///
+/// ```
+/// let builder = liquid::ParserBuilder::with_stdlib();
+/// let parser = liquid_heck::register_all_filters_with_aliases(builder).build().unwrap();
+/// let template = parser.parse("{{ 'Hello World' | snake_case }}").unwrap();
+/// assert_eq!(template.render(&liquid::object!({})).unwrap(), "hello_world");
+/// ```
+pub fn register_all_filters_with_aliases(builder: ParserBuilder) -> ParserBuilder {
+  FilterRegistry::names()
+    .iter()
+    .flat_map(|name| FilterRegistry::aliases(name))
+    .fold(register_all_filters(builder), |builder, alias| {
+      builder.filter(FilterRegistry::get(alias).expect("registered filter"))
+    })
+}
+
+/// Register only the filters listed in `names` on `builder`.
+///
+/// Useful to expose a restricted set of filters to untrusted template
+/// authors. Fails, without registering anything, on a name unknown to
+/// [`FilterRegistry`].
+///
+/// ```
+/// let builder = liquid::ParserBuilder::with_stdlib();
+/// let builder = liquid_heck::register_filters(builder, &["snakecase", "kebabcase"]).unwrap();
+/// let parser = builder.build().unwrap();
+/// assert!(parser.parse("{{ 'a' | snakecase }}").is_ok());
+/// assert!(parser.parse("{{ 'a' | titlecase }}").is_err());
+/// ```
+pub fn register_filters(
+  builder: ParserBuilder,
+  names: &[&str],
+) -> Result<ParserBuilder, UnknownFilter> {
+  let filters = names
+    .iter()
+    .map(|name| FilterRegistry::get(name).ok_or_else(|| UnknownFilter(name.to_string())))
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(filters.into_iter().fold(builder, ParserBuilder::filter))
+}
+
+/// Build a [`Parser`] with the Liquid stdlib and all the heck filters and
+/// tags.
+///
+/// ```
+/// let template = liquid_heck::parser_with_heck()
+///     .unwrap()
+///     .parse("{{text | snakecase}}")
+///     .unwrap();
+///
+/// let globals = liquid_heck::liquid::object!({ "text": "Some text" });
+/// assert_eq!(template.render(&globals).unwrap(), "some_text");
+/// ```
+pub fn parser_with_heck() -> Result<Parser, liquid::Error> {
+  register_all_tags(register_all_filters(ParserBuilder::with_stdlib())).build()
+}
+
+#[cfg(all(test, feature = "all-filters"))]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn parser_with_heck_registers_filters() {
+    let template = parser_with_heck()
+      .unwrap()
+      .parse("{{t | uppercamelcase}} {{t | kebabcase}} {{t | upcase}}")
+      .unwrap();
+    let globals = liquid::object!({ "t": "hello world" });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "HelloWorld hello-world HELLO WORLD"
+    );
+  }
+
+  #[test]
+  fn aliases() {
+    let parser = register_all_filters_with_aliases(ParserBuilder::new())
+      .build()
+      .unwrap();
+    let template = parser
+      .parse("{{ t | kebab-case }} {{ t | upper_camel_case }}")
+      .unwrap();
+    let globals = liquid::object!({ "t": "hello world" });
+    assert_eq!(template.render(&globals).unwrap(), "hello-world HelloWorld");
+  }
+
+  #[test]
+  fn register_only_filters() {
+    let builder = register_filters(ParserBuilder::new(), &["snakecase"]).unwrap();
+    let parser = builder.build().unwrap();
+    assert!(parser.parse("{{ 'a' | snakecase }}").is_ok());
+    assert!(parser.parse("{{ 'a' | kebabcase }}").is_err());
+
+    let error = register_filters(ParserBuilder::new(), &["snakecase", "snek"]).err();
+    assert_eq!(error, Some(UnknownFilter("snek".to_string())));
+  }
+
+  #[test]
+  fn variable_arguments() {
+    let parser = register_all_tags(register_all_filters(ParserBuilder::with_stdlib()))
+      .build()
+      .unwrap();
+    let globals = liquid::object!({
+      "settings": { "naming": "kebab", "keys": "camel", "lang": "fr", "acronyms": ["ID"] },
+      "limits": { "helm": 8 },
+      "payload": { "user_id": 1 },
+    });
+    let render = |template| parser.parse(template)?.render(&globals);
+    assert_eq!(
+      render("{{ 'userId' | case: settings.naming }}").unwrap(),
+      "user-id"
+    );
+    assert_eq!(
+      render("{% assign p = payload | restyle: keys: settings.keys %}{{ p.userId }}").unwrap(),
+      "1"
+    );
+    assert_eq!(
+      render("{% convert_keys payload to: settings.naming %}{{ payload.user-id }}").unwrap(),
+      "1"
+    );
+    #[cfg(feature = "heck05")]
+    assert_eq!(
+      render("{{ \"la vie de l'été\" | titlecase: lang: settings.lang }}").unwrap(),
+      "La Vie de l'Été"
+    );
+    assert_eq!(
+      render("{{ 'Payments API' | helm_name: limits.helm }}").unwrap(),
+      "payments"
+    );
+    assert_eq!(
+      render("{% heck_defaults acronyms: settings.acronyms %}{{ 'user id' | uppercamelcase }}")
+        .unwrap(),
+      "UserID"
+    );
+    assert!(render("{{ 'userId' | case: settings.missing }}").is_err());
+  }
+}
@@ -0,0 +1,143 @@
+//! Count-aware nouns.
+//!
+//! There is no pluralization backend to reuse yet, so the plural follows the
+//! regular English rules. Irregular nouns take the plural as an argument.
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// The regular English plural of `noun`, or of the last word of a phrase:
+/// `file` → `files`, `box` → `boxes`, `city` → `cities`. The case of the
+/// noun is kept: `FILE` → `FILES`.
+pub(crate) fn pluralize(noun: &str) -> String {
+  let upper = noun.chars().any(char::is_uppercase) && !noun.chars().any(char::is_lowercase);
+  let lower = noun.to_lowercase();
+  let (stem, suffix) = if ["s", "x", "z", "ch", "sh"]
+    .iter()
+    .any(|end| lower.ends_with(end))
+  {
+    (noun, "es")
+  } else if lower.ends_with('y')
+    && !lower[..lower.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u'])
+    && lower.len() > 1
+  {
+    (&noun[..noun.len() - 1], "ies")
+  } else if noun.is_empty() {
+    (noun, "")
+  } else {
+    (noun, "s")
+  };
+  if upper {
+    stem.to_string() + &suffix.to_uppercase()
+  } else {
+    stem.to_string() + suffix
+  }
+}
+
+#[cfg(feature = "filter-count-with")]
+#[derive(Debug, FilterParameters)]
+struct CountWithArgs {
+  #[parameter(description = "The number of things.", arg_type = "integer")]
+  count: Expression,
+  #[parameter(
+    description = "The plural of the noun, for the irregular ones.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  plural: Option<Expression>,
+}
+
+/// Prefix a noun with a count, in the singular or the plural:
+/// `{{ "file" | count_with: 3 }}` renders `3 files`.
+#[cfg(feature = "filter-count-with")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "count_with",
+  description = "Prefix the noun with the count, pluralizing it unless the count is 1.",
+  parameters(CountWithArgs),
+  parsed(CountWithFilter)
+)]
+pub struct CountWith;
+
+#[cfg(feature = "filter-count-with")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "count_with"]
+struct CountWithFilter {
+  #[parameters]
+  args: CountWithArgs,
+}
+
+#[cfg(feature = "filter-count-with")]
+impl Filter for CountWithFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let noun = input.to_kstr();
+    let output = match (args.count, args.plural) {
+      (1, _) => format!("1 {noun}"),
+      (count, Some(plural)) => format!("{count} {plural}"),
+      (count, None) => format!("{count} {}", pluralize(noun.as_str())),
+    };
+    #[cfg(feature = "log")]
+    log::debug!(
+      "count_with: {} -> {}",
+      crate::instrument::Redacted::Str(&noun),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn plurals() {
+    for (noun, plural) in [
+      ("file", "files"),
+      ("box", "boxes"),
+      ("match", "matches"),
+      ("city", "cities"),
+      ("day", "days"),
+      ("config file", "config files"),
+      ("FILE", "FILES"),
+      ("Entry", "Entries"),
+      ("", ""),
+    ] {
+      assert_eq!(pluralize(noun), plural, "{noun}");
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "filter-count-with")]
+  fn count_with() {
+    assert_eq!(
+      liquid_core::call_filter!(CountWith, "file", 1).unwrap(),
+      liquid_core::value!("1 file")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(CountWith, "file", 3).unwrap(),
+      liquid_core::value!("3 files")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(CountWith, "entry", 0).unwrap(),
+      liquid_core::value!("0 entries")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(CountWith)
+      .build()
+      .unwrap()
+      .parse("{{ 'person' | count_with: n, plural: 'people' }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({ "n": 2 })).unwrap(),
+      "2 people"
+    );
+  }
+}
@@ -0,0 +1,916 @@
+//! Filters naming the items of generated code by the conventions of a
+//! language: enum variants, constants, accessors, builder methods, tests,
+//! ...
+use crate::args::{invalid_argument, named_value, positional_or_keyword, unknown_value};
+use crate::conversion::{self, HeckCase};
+use crate::filters::runtime_options;
+#[cfg(any(
+  feature = "filter-const-name",
+  feature = "filter-getter-name",
+  feature = "filter-setter-name",
+  feature = "filter-predicate-name",
+  feature = "filter-builder-method",
+  feature = "filter-test-name"
+))]
+use crate::reserved::Reserved;
+use crate::targets::evaluate;
+use crate::words::word_spans;
+use crate::Options;
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// The conventions of the enum variants of a language.
+#[cfg(feature = "filter-enum-variant")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum VariantStyle {
+  /// `UpperCamelCase`, without the enum name.
+  Rust,
+  /// `UpperCamelCase` or `SCREAMING_SNAKE_CASE`, without the enum name.
+  Ts,
+  /// `SCREAMING_SNAKE_CASE` prefixed with the enum name, the values of a
+  /// Protocol Buffers enum sharing the scope of the package.
+  Proto,
+}
+
+#[cfg(feature = "filter-enum-variant")]
+impl VariantStyle {
+  const NAMES: &'static [&'static str] = &["rust", "ts", "proto"];
+
+  fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "rust" => VariantStyle::Rust,
+      "ts" | "typescript" => VariantStyle::Ts,
+      "proto" => VariantStyle::Proto,
+      _ => return None,
+    })
+  }
+}
+
+/// `variant` split into its prefix and the rest: the leading words of
+/// `prefix`, as many times as they are repeated, or without `prefix` a run
+/// of words repeated back to back, as `Color` in `ColorColorRed`. A variant
+/// made of the prefix only is kept whole.
+#[cfg(feature = "filter-enum-variant")]
+fn split_prefix<'a>(
+  variant: &'a str,
+  prefix: Option<&'a str>,
+  options: &Options,
+) -> (Option<&'a str>, &'a str) {
+  let spans: Vec<_> = word_spans(variant, options).collect();
+  let words: Vec<_> = spans
+    .iter()
+    .map(|span| variant[span.clone()].to_lowercase())
+    .collect();
+  let (prefix, prefix_words) = match prefix {
+    Some(prefix) => {
+      let prefix_words: Vec<_> = word_spans(prefix, options)
+        .map(|span| prefix[span].to_lowercase())
+        .collect();
+      (Some(prefix), prefix_words)
+    }
+    None => match (1..=words.len() / 2).find(|&n| words[..n] == words[n..2 * n]) {
+      Some(n) => (
+        Some(&variant[spans[0].start..spans[n - 1].end]),
+        words[..n].to_vec(),
+      ),
+      None => (None, Vec::new()),
+    },
+  };
+  let mut start = 0;
+  while !prefix_words.is_empty()
+    && start + prefix_words.len() < words.len()
+    && words[start..].starts_with(&prefix_words)
+  {
+    start += prefix_words.len();
+  }
+  match start {
+    0 => (prefix, variant),
+    _ => (prefix, &variant[spans[start].start..]),
+  }
+}
+
+#[cfg(feature = "filter-enum-variant")]
+#[derive(Debug, FilterParameters)]
+struct EnumVariantArgs {
+  #[parameter(
+    description = "The language: \"rust\", \"ts\" or \"proto\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+  #[parameter(
+    description = "The name of the enum, stripped from the start of the variant, and prefixing the Protocol Buffers values.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "enum"
+  )]
+  enum_name: Option<Expression>,
+  #[parameter(
+    description = "Name the TypeScript variants in SCREAMING_SNAKE_CASE rather than PascalCase.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  screaming: Option<Expression>,
+}
+
+/// Name an enum variant by the conventions of a language, from a schema:
+/// `{{ "COLOR_RED" | enum_variant: "rust", enum: "Color" }}` renders `Red`,
+/// `{{ "red" | enum_variant: "proto", enum: "Color" }}` `COLOR_RED`. The
+/// Rust and TypeScript variants are in UpperCamelCase, or in
+/// SCREAMING_SNAKE_CASE for TypeScript with `screaming: true`; the
+/// Protocol Buffers values in SCREAMING_SNAKE_CASE prefixed with the enum
+/// name. The enum name repeated at the start of the variant is dropped,
+/// and without `enum:` a repeated prefix too: `ColorColorRed` gives `Red`.
+#[cfg(feature = "filter-enum-variant")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "enum_variant",
+  description = "Name an enum variant by the conventions of a language.",
+  parameters(EnumVariantArgs),
+  parsed(EnumVariantFilter)
+)]
+pub struct EnumVariant;
+
+#[cfg(feature = "filter-enum-variant")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "enum_variant"]
+struct EnumVariantFilter {
+  #[parameters]
+  args: EnumVariantArgs,
+}
+
+#[cfg(feature = "filter-enum-variant")]
+impl Filter for EnumVariantFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let lang = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .ok_or_else(|| invalid_argument("lang", "Language name expected"))?;
+    let style = named_value("lang", &lang, VariantStyle::from_name, VariantStyle::NAMES)?;
+    let screaming = args.screaming.unwrap_or(false);
+    let options = runtime_options(runtime)?;
+    evaluate("enum_variant", input, runtime, |s| {
+      let (prefix, variant) = split_prefix(s, args.enum_name.as_deref(), &options);
+      let convert = |case, s| conversion::convert(case, s, &options);
+      Ok(match style {
+        VariantStyle::Ts if screaming => convert(HeckCase::ShoutySnake, variant),
+        VariantStyle::Rust | VariantStyle::Ts => convert(HeckCase::UpperCamel, variant),
+        VariantStyle::Proto => match prefix {
+          Some(prefix) => format!(
+            "{}_{}",
+            convert(HeckCase::ShoutySnake, prefix),
+            convert(HeckCase::ShoutySnake, variant)
+          ),
+          None => convert(HeckCase::ShoutySnake, variant),
+        },
+      })
+    })
+  }
+}
+
+/// `s` as a constant of the language reserving the names of `reserved`,
+/// in SCREAMING_SNAKE_CASE, with an underscore before a leading digit.
+#[cfg(feature = "filter-const-name")]
+fn const_name(s: &str, reserved: Option<Reserved>, options: &Options) -> String {
+  let mut name = conversion::convert(HeckCase::ShoutySnake, s, options);
+  if name.starts_with(|c: char| c.is_ascii_digit()) {
+    name.insert(0, '_');
+  }
+  match reserved {
+    Some(reserved) => reserved.escape(&name).into_owned(),
+    None => name,
+  }
+}
+
+#[cfg(feature = "filter-const-name")]
+#[derive(Debug, FilterParameters)]
+struct ConstNameArgs {
+  #[parameter(
+    description = "The language whose keywords are escaped: \"rust\", \"java\" or \"python\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+}
+
+/// Name a constant, a Rust `const` or `static`, a Java `static final` field
+/// or a Python module constant: `{{ "max retries" | const_name: "rust" }}`
+/// renders `MAX_RETRIES`. A leading digit, invalid in the three languages,
+/// takes an underscore before it: `2fa timeout` gives `_2FA_TIMEOUT`, which
+/// Python reads as private to its module. The keywords of `lang:` are
+/// escaped as `escape_reserved` does.
+#[cfg(feature = "filter-const-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "const_name",
+  description = "Name a constant in SCREAMING_SNAKE_CASE by the rules of a language.",
+  parameters(ConstNameArgs),
+  parsed(ConstNameFilter)
+)]
+pub struct ConstName;
+
+#[cfg(feature = "filter-const-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "const_name"]
+struct ConstNameFilter {
+  #[parameters]
+  args: ConstNameArgs,
+}
+
+#[cfg(feature = "filter-const-name")]
+impl Filter for ConstNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let reserved = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .map(|lang| {
+        let from_name = |name: &str| match name {
+          "rust" | "java" | "python" => Reserved::from_name(name),
+          _ => None,
+        };
+        named_value("lang", &lang, from_name, &["rust", "java", "python"])
+      })
+      .transpose()?;
+    let options = runtime_options(runtime)?;
+    evaluate("const_name", input, runtime, |s| {
+      Ok(const_name(s, reserved, &options))
+    })
+  }
+}
+
+/// The conventions of the accessor and builder methods of a language.
+#[cfg(any(
+  feature = "filter-getter-name",
+  feature = "filter-setter-name",
+  feature = "filter-predicate-name",
+  feature = "filter-builder-method"
+))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AccessorLang {
+  /// `user_id`, `set_user_id`, `is_active`: no `get_` prefix, as the Rust
+  /// API guidelines require.
+  Rust,
+  /// `UserId`, `SetUserId`, `IsActive`: exported, without `Get` prefix.
+  Go,
+  /// `getUserId`, `setUserId`, `isActive`: the JavaBeans conventions.
+  Java,
+  /// `getUserId`, `setUserId`, `isActive`, as in Java.
+  Ts,
+  /// `get_user_id`, `set_user_id`, `is_active`.
+  Python,
+}
+
+#[cfg(any(
+  feature = "filter-getter-name",
+  feature = "filter-setter-name",
+  feature = "filter-predicate-name",
+  feature = "filter-builder-method"
+))]
+impl AccessorLang {
+  const NAMES: &'static [&'static str] = &["rust", "go", "java", "ts", "python"];
+
+  fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "rust" => AccessorLang::Rust,
+      "go" => AccessorLang::Go,
+      "java" | "kotlin" => AccessorLang::Java,
+      "ts" | "typescript" | "js" | "javascript" => AccessorLang::Ts,
+      "python" => AccessorLang::Python,
+      _ => return None,
+    })
+  }
+
+  /// The case of the method names.
+  fn case(self) -> HeckCase {
+    match self {
+      AccessorLang::Rust | AccessorLang::Python => HeckCase::Snake,
+      AccessorLang::Go => HeckCase::UpperCamel,
+      AccessorLang::Java | AccessorLang::Ts => HeckCase::LowerCamel,
+    }
+  }
+
+  /// The names reserved by the language.
+  fn reserved(self) -> Option<Reserved> {
+    match self {
+      AccessorLang::Rust => Some(Reserved::Rust),
+      AccessorLang::Go => Some(Reserved::Go),
+      AccessorLang::Java => Some(Reserved::Java),
+      AccessorLang::Ts => None,
+      AccessorLang::Python => Some(Reserved::Python),
+    }
+  }
+
+  /// The name of the method of `lang` accessing the field `field`, with
+  /// the word `prefix` before it, but without a leading word of `strip`:
+  /// `get` doesn't repeat in the getter of `get_user_id`. The keywords are
+  /// escaped.
+  fn method(self, field: &str, prefix: Option<&str>, strip: &[&str], options: &Options) -> String {
+    let spans: Vec<_> = word_spans(field, options).collect();
+    let field = match spans.as_slice() {
+      [first, second, ..]
+        if strip
+          .iter()
+          .any(|word| field[first.clone()].eq_ignore_ascii_case(word)) =>
+      {
+        &field[second.start..]
+      }
+      _ => field,
+    };
+    let name = match prefix {
+      Some(prefix) => conversion::convert(self.case(), &format!("{prefix} {field}"), options),
+      None => conversion::convert(self.case(), field, options),
+    };
+    match self.reserved() {
+      Some(reserved) => reserved.escape(&name).into_owned(),
+      None => name,
+    }
+  }
+}
+
+/// The words starting the names of the predicates.
+#[cfg(feature = "filter-predicate-name")]
+const PREDICATE_VERBS: &[&str] = &[
+  "is", "are", "has", "have", "can", "should", "was", "will", "does",
+];
+
+#[cfg(feature = "filter-getter-name")]
+#[derive(Debug, FilterParameters)]
+struct GetterNameArgs {
+  #[parameter(
+    description = "The language: \"rust\", \"go\", \"java\", \"ts\" or \"python\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+}
+
+/// Name the getter of a field: `{{ "user id" | getter_name: "python" }}`
+/// renders `get_user_id`, `{{ "user id" | getter_name: "java" }}`
+/// `getUserId`. Rust and Go getters are named as their field, as their
+/// guidelines require: `user_id` and `UserId`. A leading `get` isn't
+/// repeated, and the keywords are escaped as `escape_reserved` does.
+#[cfg(feature = "filter-getter-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "getter_name",
+  description = "Name the getter of a field by the conventions of a language.",
+  parameters(GetterNameArgs),
+  parsed(GetterNameFilter)
+)]
+pub struct GetterName;
+
+#[cfg(feature = "filter-getter-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "getter_name"]
+struct GetterNameFilter {
+  #[parameters]
+  args: GetterNameArgs,
+}
+
+#[cfg(feature = "filter-getter-name")]
+impl Filter for GetterNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let lang = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .ok_or_else(|| invalid_argument("lang", "Language name expected"))?;
+    let lang = named_value("lang", &lang, AccessorLang::from_name, AccessorLang::NAMES)?;
+    let prefix = match lang {
+      AccessorLang::Rust | AccessorLang::Go => None,
+      AccessorLang::Java | AccessorLang::Ts | AccessorLang::Python => Some("get"),
+    };
+    let options = runtime_options(runtime)?;
+    evaluate("getter_name", input, runtime, |s| {
+      Ok(lang.method(s, prefix, &["get"], &options))
+    })
+  }
+}
+
+#[cfg(feature = "filter-setter-name")]
+#[derive(Debug, FilterParameters)]
+struct SetterNameArgs {
+  #[parameter(
+    description = "The language: \"rust\", \"go\", \"java\", \"ts\" or \"python\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+}
+
+/// Name the setter of a field: `{{ "user id" | setter_name: "rust" }}`
+/// renders `set_user_id`, `{{ "user id" | setter_name: "go" }}`
+/// `SetUserId`. A leading `set` isn't repeated.
+#[cfg(feature = "filter-setter-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "setter_name",
+  description = "Name the setter of a field by the conventions of a language.",
+  parameters(SetterNameArgs),
+  parsed(SetterNameFilter)
+)]
+pub struct SetterName;
+
+#[cfg(feature = "filter-setter-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "setter_name"]
+struct SetterNameFilter {
+  #[parameters]
+  args: SetterNameArgs,
+}
+
+#[cfg(feature = "filter-setter-name")]
+impl Filter for SetterNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let lang = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .ok_or_else(|| invalid_argument("lang", "Language name expected"))?;
+    let lang = named_value("lang", &lang, AccessorLang::from_name, AccessorLang::NAMES)?;
+    let options = runtime_options(runtime)?;
+    evaluate("setter_name", input, runtime, |s| {
+      Ok(lang.method(s, Some("set"), &["set"], &options))
+    })
+  }
+}
+
+#[cfg(feature = "filter-predicate-name")]
+#[derive(Debug, FilterParameters)]
+struct PredicateNameArgs {
+  #[parameter(
+    description = "The language: \"rust\", \"go\", \"java\", \"ts\" or \"python\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+  #[parameter(
+    description = "The verb starting the name when the field doesn't start with one. Defaults to \"is\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  verb: Option<Expression>,
+}
+
+/// Name the boolean accessor of a field: `{{ "active" | predicate_name:
+/// "rust" }}` renders `is_active`, `{{ "active" | predicate_name: "java" }}`
+/// `isActive`. The fields already starting with a verb, as `has children`
+/// or `can_edit`, keep it; `verb:` replaces the default `is`:
+/// `{{ "children" | predicate_name: "python", verb: "has" }}` renders
+/// `has_children`.
+#[cfg(feature = "filter-predicate-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "predicate_name",
+  description = "Name the boolean accessor of a field by the conventions of a language.",
+  parameters(PredicateNameArgs),
+  parsed(PredicateNameFilter)
+)]
+pub struct PredicateName;
+
+#[cfg(feature = "filter-predicate-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "predicate_name"]
+struct PredicateNameFilter {
+  #[parameters]
+  args: PredicateNameArgs,
+}
+
+#[cfg(feature = "filter-predicate-name")]
+impl Filter for PredicateNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let lang = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .ok_or_else(|| invalid_argument("lang", "Language name expected"))?;
+    let lang = named_value("lang", &lang, AccessorLang::from_name, AccessorLang::NAMES)?;
+    let verb = args.verb.unwrap_or_else(|| "is".into());
+    let options = runtime_options(runtime)?;
+    evaluate("predicate_name", input, runtime, |s| {
+      let first_word = word_spans(s, &options).next().map(|span| &s[span]);
+      let verb = match first_word {
+        Some(word)
+          if PREDICATE_VERBS
+            .iter()
+            .any(|verb| verb.eq_ignore_ascii_case(word)) =>
+        {
+          None
+        }
+        _ => Some(verb.as_str()),
+      };
+      Ok(lang.method(s, verb, &[], &options))
+    })
+  }
+}
+
+#[cfg(feature = "filter-builder-method")]
+#[derive(Debug, FilterParameters)]
+struct BuilderMethodArgs {
+  #[parameter(
+    description = "The language: \"rust\", \"go\", \"java\", \"ts\" or \"python\".",
+    arg_type = "str"
+  )]
+  lang: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `lang`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "lang"
+  )]
+  lang_keyword: Option<Expression>,
+  #[parameter(
+    description = "The word starting the name, e.g. \"set\", or \"\" for none. Defaults to \"with\" in Rust, Go and Python, none in Java and TypeScript.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  prefix: Option<Expression>,
+}
+
+/// Name the method of a builder setting a field: `{{ "max_retries" |
+/// builder_method: "rust" }}` renders `with_max_retries`, `{{ "max_retries"
+/// | builder_method: "java" }}` the fluent `maxRetries`, and Go the
+/// functional option `WithMaxRetries`. `prefix:` changes the leading word,
+/// or drops it with `""`. The prefix isn't repeated, and the keywords are
+/// escaped as `escape_reserved` does: `{{ "type" | builder_method: "rust",
+/// prefix: "" }}` renders `r#type`.
+#[cfg(feature = "filter-builder-method")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "builder_method",
+  description = "Name the builder method setting a field by the conventions of a language.",
+  parameters(BuilderMethodArgs),
+  parsed(BuilderMethodFilter)
+)]
+pub struct BuilderMethod;
+
+#[cfg(feature = "filter-builder-method")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "builder_method"]
+struct BuilderMethodFilter {
+  #[parameters]
+  args: BuilderMethodArgs,
+}
+
+#[cfg(feature = "filter-builder-method")]
+impl Filter for BuilderMethodFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let lang = positional_or_keyword("lang", args.lang, args.lang_keyword)?
+      .ok_or_else(|| invalid_argument("lang", "Language name expected"))?;
+    let lang = named_value("lang", &lang, AccessorLang::from_name, AccessorLang::NAMES)?;
+    let prefix = match args.prefix {
+      Some(prefix) => prefix.into_string(),
+      None => match lang {
+        AccessorLang::Rust | AccessorLang::Go | AccessorLang::Python => "with".to_string(),
+        AccessorLang::Java | AccessorLang::Ts => String::new(),
+      },
+    };
+    let prefix = Some(prefix.trim()).filter(|prefix| !prefix.is_empty());
+    let options = runtime_options(runtime)?;
+    evaluate("builder_method", input, runtime, |s| {
+      Ok(lang.method(s, prefix, prefix.as_slice(), &options))
+    })
+  }
+}
+
+/// The conventions of the test function names of a test framework: its
+/// prefix, the case of the name and the keywords it reserves.
+#[cfg(feature = "filter-test-name")]
+const TEST_STYLES: &[(&str, &str, HeckCase, Option<Reserved>)] = &[
+  ("rust", "test", HeckCase::Snake, None),
+  ("pytest", "test", HeckCase::Snake, None),
+  ("junit", "", HeckCase::LowerCamel, Some(Reserved::Java)),
+  ("go", "Test", HeckCase::UpperCamel, None),
+];
+
+#[cfg(feature = "filter-test-name")]
+#[derive(Debug, FilterParameters)]
+struct TestNameArgs {
+  #[parameter(
+    description = "The test framework: \"rust\" (the default), \"pytest\", \"junit\" or \"go\".",
+    arg_type = "str"
+  )]
+  framework: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `framework`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "framework"
+  )]
+  framework_keyword: Option<Expression>,
+}
+
+/// Name a test function after the sentence describing it:
+/// `{{ "returns 404 when user is missing" | test_name }}` renders
+/// `test_returns_404_when_user_is_missing`, for Rust and pytest, and
+/// `{{ "returns 404 when user is missing" | test_name: "junit" }}`
+/// `returns404WhenUserIsMissing`. Go tests start with `Test` in
+/// UpperCamelCase. A leading `test` isn't repeated; a JUnit name starting
+/// with a digit takes an underscore before it, and a Java keyword one after.
+#[cfg(feature = "filter-test-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "test_name",
+  description = "Make a sentence the name of a test function by the conventions of a test framework.",
+  parameters(TestNameArgs),
+  parsed(TestNameFilter)
+)]
+pub struct TestName;
+
+#[cfg(feature = "filter-test-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "test_name"]
+struct TestNameFilter {
+  #[parameters]
+  args: TestNameArgs,
+}
+
+#[cfg(feature = "filter-test-name")]
+impl Filter for TestNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let framework = positional_or_keyword("framework", args.framework, args.framework_keyword)?
+      .unwrap_or_else(|| "rust".into());
+    let names: Vec<_> = TEST_STYLES.iter().map(|style| style.0).collect();
+    let &(_, prefix, case, reserved) = TEST_STYLES
+      .iter()
+      .find(|(name, ..)| *name == framework.as_str())
+      .ok_or_else(|| unknown_value("framework", &framework, &names))?;
+    let options = runtime_options(runtime)?;
+    evaluate("test_name", input, runtime, |s| {
+      let spans: Vec<_> = word_spans(s, &options).collect();
+      let s = match spans.as_slice() {
+        [first, second, ..] if s[first.clone()].eq_ignore_ascii_case("test") => &s[second.start..],
+        _ => s,
+      };
+      let mut name = conversion::convert(case, &format!("{prefix} {s}"), &options);
+      if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+      }
+      Ok(match reserved {
+        Some(reserved) => reserved.escape(&name).into_owned(),
+        None => name,
+      })
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  #[cfg(feature = "filter-enum-variant")]
+  fn enum_variant() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(EnumVariant)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'COLOR_RED' | enum_variant: 'rust', enum: 'Color' }}").unwrap(),
+      "Red"
+    );
+    assert_eq!(
+      render("{{ 'ColorColorRed' | enum_variant: 'rust' }}").unwrap(),
+      "Red"
+    );
+    assert_eq!(
+      render("{{ 'ColorColorRed' | enum_variant: 'proto' }}").unwrap(),
+      "COLOR_RED"
+    );
+    assert_eq!(
+      render("{{ 'red' | enum_variant: 'proto', enum: 'Color' }}").unwrap(),
+      "COLOR_RED"
+    );
+    assert_eq!(
+      render("{{ 'light blue' | enum_variant: 'proto', enum: 'ColorMode' }}").unwrap(),
+      "COLOR_MODE_LIGHT_BLUE"
+    );
+    assert_eq!(
+      render("{{ 'dark-green' | enum_variant: 'ts' }}").unwrap(),
+      "DarkGreen"
+    );
+    assert_eq!(
+      render("{{ 'dark-green' | enum_variant: lang: 'ts', screaming: true }}").unwrap(),
+      "DARK_GREEN"
+    );
+    assert_eq!(
+      render("{{ 'Color' | enum_variant: 'rust', enum: 'Color' }}").unwrap(),
+      "Color"
+    );
+    assert_eq!(
+      render("{{ 'red' | enum_variant: 'proto' }}").unwrap(),
+      "RED"
+    );
+    assert!(render("{{ 'red' | enum_variant: 'cobol' }}").is_err());
+    assert!(render("{{ 'red' | enum_variant }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-const-name")]
+  fn const_name() {
+    assert_eq!(
+      liquid_core::call_filter!(ConstName, "max retries", "rust").unwrap(),
+      liquid_core::value!("MAX_RETRIES")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ConstName, "defaultTimeoutMs", "java").unwrap(),
+      liquid_core::value!("DEFAULT_TIMEOUT_MS")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ConstName, "2fa timeout", "python").unwrap(),
+      liquid_core::value!("_2FA_TIMEOUT")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(ConstName, "page-size").unwrap(),
+      liquid_core::value!("PAGE_SIZE")
+    );
+    assert!(liquid_core::call_filter!(ConstName, "x", "go").is_err());
+  }
+
+  #[test]
+  #[cfg(all(
+    feature = "filter-getter-name",
+    feature = "filter-setter-name",
+    feature = "filter-predicate-name"
+  ))]
+  fn accessors() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(GetterName)
+      .filter(SetterName)
+      .filter(PredicateName)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'user id' | getter_name: 'python' }} {{ 'user id' | getter_name: 'java' }}")
+        .unwrap(),
+      "get_user_id getUserId"
+    );
+    assert_eq!(
+      render("{{ 'userId' | getter_name: 'rust' }} {{ 'user_id' | getter_name: 'go' }}").unwrap(),
+      "user_id UserId"
+    );
+    assert_eq!(
+      render("{{ 'get_user_id' | getter_name: 'ts' }} {{ 'type' | getter_name: 'rust' }}").unwrap(),
+      "getUserId r#type"
+    );
+    assert_eq!(
+      render("{{ 'user id' | setter_name: 'rust' }} {{ 'user id' | setter_name: lang: 'go' }}")
+        .unwrap(),
+      "set_user_id SetUserId"
+    );
+    assert_eq!(
+      render("{{ 'set_name' | setter_name: 'java' }}").unwrap(),
+      "setName"
+    );
+    assert_eq!(
+      render("{{ 'active' | predicate_name: 'rust' }} {{ 'active' | predicate_name: 'java' }}")
+        .unwrap(),
+      "is_active isActive"
+    );
+    assert_eq!(
+      render(
+        "{{ 'has children' | predicate_name: 'python' }} {{ 'CanEdit' | predicate_name: 'go' }}"
+      )
+      .unwrap(),
+      "has_children CanEdit"
+    );
+    assert_eq!(
+      render("{{ 'children' | predicate_name: 'python', verb: 'has' }}").unwrap(),
+      "has_children"
+    );
+    assert!(render("{{ 'x' | getter_name: 'cobol' }}").is_err());
+    assert!(render("{{ 'x' | setter_name }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-builder-method")]
+  fn builder_method() {
+    assert_eq!(
+      liquid_core::call_filter!(BuilderMethod, "max_retries", "rust").unwrap(),
+      liquid_core::value!("with_max_retries")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(BuilderMethod, "max_retries", "java").unwrap(),
+      liquid_core::value!("maxRetries")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(BuilderMethod, "max retries", "go").unwrap(),
+      liquid_core::value!("WithMaxRetries")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(BuilderMethod, "withTimeout", "python").unwrap(),
+      liquid_core::value!("with_timeout")
+    );
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(BuilderMethod)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'type' | builder_method: 'rust', prefix: '' }}").unwrap(),
+      "r#type"
+    );
+    assert_eq!(
+      render("{{ 'timeout' | builder_method: 'ts', prefix: 'set' }}").unwrap(),
+      "setTimeout"
+    );
+    assert!(render("{{ 'timeout' | builder_method }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-test-name")]
+  fn test_name() {
+    let sentence = "returns 404 when user is missing";
+    assert_eq!(
+      liquid_core::call_filter!(TestName, sentence).unwrap(),
+      liquid_core::value!("test_returns_404_when_user_is_missing")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TestName, sentence, "junit").unwrap(),
+      liquid_core::value!("returns404WhenUserIsMissing")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TestName, sentence, "go").unwrap(),
+      liquid_core::value!("TestReturns404WhenUserIsMissing")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TestName, "Test parses empty input", "pytest").unwrap(),
+      liquid_core::value!("test_parses_empty_input")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TestName, "404 on missing user", "junit").unwrap(),
+      liquid_core::value!("_404OnMissingUser")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(TestName, "default", "junit").unwrap(),
+      liquid_core::value!("default_")
+    );
+    assert!(liquid_core::call_filter!(TestName, sentence, "mocha").is_err());
+  }
+
+  #[test]
+  #[cfg(all(feature = "log", feature = "filter-const-name"))]
+  fn logs_conversions() {
+    let logs = crate::instrument::captured_logs(|| {
+      liquid_core::call_filter!(ConstName, "max retries").unwrap();
+    });
+    assert_eq!(logs, ["const_name: \"max retries\" -> \"MAX_RETRIES\""]);
+  }
+}
@@ -0,0 +1,202 @@
+//! Runtime lookup of the filters by their template name.
+use crate::conversion::HeckCase;
+use crate::filters::ConfiguredCase;
+use liquid_core::ParseFilter;
+use std::fmt;
+
+/// Error for a filter name unknown to [`FilterRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownFilter(pub String);
+
+impl fmt::Display for UnknownFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "unknown filter `{}`, expected one of: {}",
+      self.0,
+      FilterRegistry::names().join(", ")
+    )
+  }
+}
+
+impl std::error::Error for UnknownFilter {}
+
+/// Lookup of the filters by the name used in templates.
+///
+/// Useful when the filters to register come from configuration rather than
+/// from compile-time types. Only the filters enabled by the cargo features
+/// are known.
+///
+/// ```
+/// # #[cfg(all(feature = "filter-snake", feature = "filter-kebab"))]
+/// # {
+/// use liquid_heck::FilterRegistry;
+///
+/// let mut builder = liquid::ParserBuilder::with_stdlib();
+/// for name in ["snakecase", "kebabcase"] {
+///     builder = builder.filter(FilterRegistry::get(name).unwrap());
+/// }
+/// # }
+/// ```
+pub struct FilterRegistry;
+
+/// Declare the known filters, each as its cargo feature, its template name
+/// and its value.
+macro_rules! known_filters {
+  ($($feature:literal => $name:literal => $filter:expr,)*) => {
+    /// The template names of the filters enabled by the cargo features.
+    const NAMES: &[&str] = &[$(#[cfg(feature = $feature)] $name,)*];
+
+    /// The filter registered as `name`, without considering the aliases.
+    fn known_filter(name: &str) -> Option<Box<dyn ParseFilter>> {
+      match name {
+        $(#[cfg(feature = $feature)] $name => Some(Box::new($filter)),)*
+        _ => None,
+      }
+    }
+  };
+}
+
+known_filters! {
+  "filter-kebab" => "kebabcase" => crate::filters::KebabCase,
+  "filter-lower-camel" => "lowercamelcase" => crate::filters::LowerCamelCase,
+  "filter-shouty-kebab" => "shoutykebabcase" => crate::filters::ShoutyKebabCase,
+  "filter-shouty-snake" => "shoutysnakecase" => crate::filters::ShoutySnakeCase,
+  "filter-snake" => "snakecase" => crate::filters::SnakeCase,
+  "filter-title" => "titlecase" => crate::filters::TitleCase,
+  "filter-train" => "traincase" => crate::filters::TrainCase,
+  "filter-upper-camel" => "uppercamelcase" => crate::filters::UpperCamelCase,
+  "filter-acronymize" => "acronymize" => crate::modifiers::Acronymize,
+  "filter-downcase-keep-separators" => "downcase_keep_separators" => crate::modifiers::DowncaseKeepSeparators,
+  "filter-expand-acronyms" => "expand_acronyms" => crate::modifiers::ExpandAcronyms,
+  "filter-screaming" => "screaming" => crate::modifiers::Screaming,
+  "filter-skip-if-opaque" => "skip_if_opaque" => crate::modifiers::SkipIfOpaque,
+  "filter-truncate-words-case" => "truncate_words_case" => crate::modifiers::TruncateWordsCase,
+  "filter-wrap-ident" => "wrap_ident" => crate::modifiers::WrapIdent,
+  "filter-restyle" => "restyle" => crate::objects::Restyle,
+  "filter-values" => "kebabcase_values" => crate::objects::CaseValues::new(HeckCase::Kebab),
+  "filter-values" => "lowercamelcase_values" => crate::objects::CaseValues::new(HeckCase::LowerCamel),
+  "filter-values" => "shoutykebabcase_values" => crate::objects::CaseValues::new(HeckCase::ShoutyKebab),
+  "filter-values" => "shoutysnakecase_values" => crate::objects::CaseValues::new(HeckCase::ShoutySnake),
+  "filter-values" => "snakecase_values" => crate::objects::CaseValues::new(HeckCase::Snake),
+  "filter-values" => "titlecase_values" => crate::objects::CaseValues::new(HeckCase::Title),
+  "filter-values" => "traincase_values" => crate::objects::CaseValues::new(HeckCase::Train),
+  "filter-values" => "uppercamelcase_values" => crate::objects::CaseValues::new(HeckCase::UpperCamel),
+  "filter-count-with" => "count_with" => crate::plural::CountWith,
+  "filter-case-eq" => "case_eq" => crate::compare::CaseEq,
+  "filter-dedupe-idents" => "dedupe_idents" => crate::arrays::DedupeIdents,
+  "filter-group-by-prefix" => "group_by_prefix" => crate::arrays::GroupByPrefix,
+  "filter-sort-ci" => "sort_ci" => crate::arrays::SortCi,
+  "filter-sort-natural-ident" => "sort_natural_ident" => crate::arrays::SortNaturalIdent,
+  "filter-email-local" => "email_local" => crate::targets::EmailLocal,
+  "filter-git-ref" => "git_ref" => crate::targets::GitRef,
+  "filter-docker" => "docker_repo" => crate::targets::DockerRepo,
+  "filter-docker" => "docker_tag" => crate::targets::DockerTag,
+  "filter-pypi" => "pypi_name" => crate::targets::PypiName,
+  "filter-pypi" => "py_dist_to_module" => crate::targets::PyDistToModule,
+  "filter-maven" => "maven_artifact" => crate::targets::MavenArtifact,
+  "filter-maven" => "maven_group" => crate::targets::MavenGroup,
+  "filter-helm" => "helm_name" => crate::targets::HelmName,
+  "filter-aws" => "lambda_name" => crate::targets::LambdaName,
+  "filter-aws" => "iam_name" => crate::targets::IamName,
+  "filter-aws" => "cfn_logical_id" => crate::targets::CfnLogicalId,
+  "filter-gcp" => "gcp_name" => crate::targets::GcpName,
+  "filter-safe-filename" => "safe_filename" => crate::targets::SafeFilename,
+  "filter-unique-slug" => "unique_slug" => crate::targets::UniqueSlug,
+  "filter-anchor-mdbook" => "anchor_mdbook" => crate::targets::AnchorMdbook,
+  "filter-anchor-docusaurus" => "anchor_docusaurus" => crate::targets::AnchorDocusaurus,
+  "filter-rst-label" => "rst_label" => crate::targets::RstLabel,
+  "filter-latex" => "latex_label" => crate::targets::LatexLabel,
+  "filter-latex" => "latex_command" => crate::targets::LatexCommand,
+  "filter-case" => "case" => crate::filters::ToCase,
+  "filter-rename-placeholders" => "rename_placeholders" => crate::text::RenamePlaceholders,
+  "filter-convert-within" => "convert_within" => crate::text::ConvertWithin,
+  "filter-spell-symbols" => "spell_symbols" => crate::modifiers::SpellSymbols,
+  "filter-strip-diacritics" => "strip_diacritics" => crate::modifiers::StripDiacritics,
+  "filter-caseless-eq" => "caseless_eq" => crate::compare::CaselessEq,
+  "icu" => "sort_collate" => crate::arrays::SortCollate,
+  "homoglyphs" => "normalize_homoglyphs" => crate::modifiers::NormalizeHomoglyphs,
+  "filter-escape-reserved" => "escape_reserved" => crate::modifiers::EscapeReserved,
+  "filter-in-namespace" => "in_namespace" => crate::targets::InNamespace,
+  "filter-enum-variant" => "enum_variant" => crate::presets::EnumVariant,
+  "filter-const-name" => "const_name" => crate::presets::ConstName,
+  "filter-getter-name" => "getter_name" => crate::presets::GetterName,
+  "filter-setter-name" => "setter_name" => crate::presets::SetterName,
+  "filter-predicate-name" => "predicate_name" => crate::presets::PredicateName,
+  "filter-builder-method" => "builder_method" => crate::presets::BuilderMethod,
+  "filter-test-name" => "test_name" => crate::presets::TestName,
+  "filter-event-name" => "event_name" => crate::targets::EventName,
+  "filter-prometheus" => "prom_metric" => crate::targets::PromMetric,
+  "filter-prometheus" => "prom_label" => crate::targets::PromLabel,
+  "filter-canonical-header" => "canonical_header" => crate::targets::CanonicalHeader,
+  "filter-i18n-key" => "i18n_key" => crate::targets::I18nKey,
+}
+
+impl FilterRegistry {
+  /// The template names of all the known filters.
+  pub fn names() -> &'static [&'static str] {
+    NAMES
+  }
+
+  /// The alias spellings of the filter `name`, e.g. `snake_case` and
+  /// `snake-case` for `snakecase`.
+  pub fn aliases(name: &str) -> impl Iterator<Item = &'static str> + '_ {
+    HeckCase::ALL
+      .into_iter()
+      .filter(move |case| case.filter_name() == name)
+      .flat_map(HeckCase::aliases)
+  }
+
+  /// The filter registered as `name` in templates, if any.
+  ///
+  /// `name` may also be one of the [aliases](Self::aliases). The filter is
+  /// then registered under the alias.
+  pub fn get(name: &str) -> Option<Box<dyn ParseFilter>> {
+    known_filter(name).or_else(|| {
+      let case = Self::case(name)?;
+      Some(Box::new(ConfiguredCase::new(name, case, None)) as _)
+    })
+  }
+
+  /// The case the filter registered as `name`, or one of its aliases,
+  /// converts to.
+  pub(crate) fn case(name: &str) -> Option<HeckCase> {
+    HeckCase::from_filter_name(name).filter(|case| Self::names().contains(&case.filter_name()))
+  }
+}
+
+#[cfg(all(test, feature = "all-filters"))]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn registry() {
+    for name in FilterRegistry::names() {
+      assert_eq!(
+        FilterRegistry::get(name).unwrap().reflection().name(),
+        *name
+      );
+    }
+    assert_eq!(
+      FilterRegistry::names().len(),
+      70 + cfg!(feature = "icu") as usize + cfg!(feature = "homoglyphs") as usize
+    );
+    assert!(FilterRegistry::get("upcase").is_none());
+  }
+
+  #[test]
+  fn aliases() {
+    assert_eq!(
+      FilterRegistry::aliases("snakecase").collect::<Vec<_>>(),
+      ["snake_case", "snake-case"]
+    );
+    for name in FilterRegistry::names() {
+      for alias in FilterRegistry::aliases(name) {
+        let filter = FilterRegistry::get(alias).unwrap();
+        assert_eq!(filter.reflection().name(), alias);
+        assert_eq!(FilterRegistry::case(alias), FilterRegistry::case(name));
+      }
+    }
+  }
+}
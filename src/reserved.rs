@@ -0,0 +1,333 @@
+//! The names reserved by the targets of the generated names, and how each
+//! target escapes them.
+use std::borrow::Cow;
+
+/// A system reserving names: a language, a database, a file system, ...
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Reserved {
+  /// The Windows device names, `CON`, `NUL`, `COM1`, ..., whatever their
+  /// case and extension: `con.txt` becomes `con_.txt`.
+  Windows,
+  /// The SQL reserved words, whatever their case, quoted: `order` becomes
+  /// `"order"`.
+  Sql,
+  /// The Rust keywords, as raw identifiers: `type` becomes `r#type`, and
+  /// `self` which can't be raw `self_`.
+  Rust,
+  /// The Go keywords, with a trailing underscore: `type_`.
+  Go,
+  /// The Python keywords, with a trailing underscore as PEP 8 suggests:
+  /// `class_`.
+  Python,
+  /// The Java keywords and literals, with a trailing underscore as the
+  /// Java Language Specification suggests: `int_`.
+  Java,
+  /// The label prefixes reserved for the Kubernetes components,
+  /// `kubernetes.io/` and `k8s.io/` and their subdomains, dropped:
+  /// `kubernetes.io/team` becomes `team`.
+  K8s,
+}
+
+impl Reserved {
+  /// The names of the targets, as spelled in templates.
+  pub(crate) const NAMES: &'static [&'static str] =
+    &["windows", "sql", "rust", "go", "python", "java", "k8s"];
+
+  /// The target spelled `name` in templates.
+  pub(crate) fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "windows" => Reserved::Windows,
+      "sql" => Reserved::Sql,
+      "rust" => Reserved::Rust,
+      "go" => Reserved::Go,
+      "python" => Reserved::Python,
+      "java" => Reserved::Java,
+      "k8s" | "kubernetes" => Reserved::K8s,
+      _ => return None,
+    })
+  }
+
+  /// Is `name` reserved by the target?
+  pub(crate) fn is_reserved(self, name: &str) -> bool {
+    match self {
+      Reserved::Windows => is_device_name(name),
+      Reserved::Sql => SQL_RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(name)),
+      Reserved::Rust => RUST_KEYWORDS.contains(&name),
+      Reserved::Go => GO_KEYWORDS.contains(&name),
+      Reserved::Python => PYTHON_KEYWORDS.contains(&name),
+      Reserved::Java => JAVA_KEYWORDS.contains(&name),
+      Reserved::K8s => k8s_reserved_prefix(name).is_some(),
+    }
+  }
+
+  /// `name` escaped the way of the target when it is reserved.
+  pub(crate) fn escape(self, name: &str) -> Cow<'_, str> {
+    if !self.is_reserved(name) {
+      return Cow::Borrowed(name);
+    }
+    Cow::Owned(match self {
+      Reserved::Windows => {
+        let stem = name.find('.').unwrap_or(name.len());
+        format!("{}_{}", &name[..stem], &name[stem..])
+      }
+      Reserved::Sql => format!("\"{name}\""),
+      Reserved::Rust if !matches!(name, "_" | "crate" | "self" | "Self" | "super") => {
+        format!("r#{name}")
+      }
+      Reserved::Rust | Reserved::Go | Reserved::Python | Reserved::Java => format!("{name}_"),
+      Reserved::K8s => {
+        let prefix = k8s_reserved_prefix(name).unwrap_or_default();
+        name[prefix.len()..].to_string()
+      }
+    })
+  }
+}
+
+/// The Windows device names, reserved whatever the case and extension.
+const DEVICE_NAMES: &[&str] = &["AUX", "CON", "CONIN$", "CONOUT$", "NUL", "PRN"];
+
+/// Whether `stem`, the file name before its extension, is a Windows device
+/// name.
+fn is_device_name(stem: &str) -> bool {
+  let stem = stem.split('.').next().unwrap_or(stem).trim_end_matches(' ');
+  let numbered = stem.len() == 4
+    && stem
+      .get(..3)
+      .is_some_and(|port| port.eq_ignore_ascii_case("COM") || port.eq_ignore_ascii_case("LPT"))
+    && stem.as_bytes()[3].is_ascii_digit();
+  numbered
+    || DEVICE_NAMES
+      .iter()
+      .any(|name| name.eq_ignore_ascii_case(stem))
+}
+
+/// The reserved prefix of the Kubernetes label key `key`, its slash
+/// included.
+fn k8s_reserved_prefix(key: &str) -> Option<&str> {
+  let (prefix, _) = key.split_once('/')?;
+  let prefix = prefix.to_ascii_lowercase();
+  let reserved = ["kubernetes.io", "k8s.io"]
+    .iter()
+    .any(|domain| prefix == *domain || prefix.ends_with(&format!(".{domain}")));
+  reserved.then(|| &key[..=prefix.len()])
+}
+
+/// The SQL:2016 reserved words most likely to clash with a table or a
+/// column name.
+const SQL_RESERVED_WORDS: &[&str] = &[
+  "all",
+  "alter",
+  "and",
+  "any",
+  "as",
+  "asc",
+  "between",
+  "by",
+  "case",
+  "cast",
+  "check",
+  "column",
+  "constraint",
+  "create",
+  "cross",
+  "current",
+  "current_date",
+  "current_time",
+  "current_timestamp",
+  "current_user",
+  "default",
+  "delete",
+  "desc",
+  "distinct",
+  "drop",
+  "else",
+  "end",
+  "except",
+  "exists",
+  "false",
+  "fetch",
+  "for",
+  "foreign",
+  "from",
+  "full",
+  "grant",
+  "group",
+  "having",
+  "in",
+  "inner",
+  "insert",
+  "intersect",
+  "interval",
+  "into",
+  "is",
+  "join",
+  "left",
+  "like",
+  "limit",
+  "natural",
+  "not",
+  "null",
+  "offset",
+  "on",
+  "or",
+  "order",
+  "outer",
+  "primary",
+  "references",
+  "right",
+  "select",
+  "session_user",
+  "table",
+  "then",
+  "to",
+  "true",
+  "union",
+  "unique",
+  "update",
+  "user",
+  "using",
+  "values",
+  "when",
+  "where",
+  "window",
+  "with",
+];
+
+/// The Rust keywords, strict and reserved, of the 2024 edition.
+const RUST_KEYWORDS: &[&str] = &[
+  "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+  "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+  "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+  "self", "Self", "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe",
+  "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The Go keywords.
+const GO_KEYWORDS: &[&str] = &[
+  "break",
+  "case",
+  "chan",
+  "const",
+  "continue",
+  "default",
+  "defer",
+  "else",
+  "fallthrough",
+  "for",
+  "func",
+  "go",
+  "goto",
+  "if",
+  "import",
+  "interface",
+  "map",
+  "package",
+  "range",
+  "return",
+  "select",
+  "struct",
+  "switch",
+  "type",
+  "var",
+];
+
+/// The Python keywords.
+const PYTHON_KEYWORDS: &[&str] = &[
+  "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+  "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+  "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+  "yield",
+];
+
+/// The Java keywords and literals.
+const JAVA_KEYWORDS: &[&str] = &[
+  "_",
+  "abstract",
+  "assert",
+  "boolean",
+  "break",
+  "byte",
+  "case",
+  "catch",
+  "char",
+  "class",
+  "const",
+  "continue",
+  "default",
+  "do",
+  "double",
+  "else",
+  "enum",
+  "extends",
+  "false",
+  "final",
+  "finally",
+  "float",
+  "for",
+  "goto",
+  "if",
+  "implements",
+  "import",
+  "instanceof",
+  "int",
+  "interface",
+  "long",
+  "native",
+  "new",
+  "null",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "short",
+  "static",
+  "strictfp",
+  "super",
+  "switch",
+  "synchronized",
+  "this",
+  "throw",
+  "throws",
+  "transient",
+  "true",
+  "try",
+  "void",
+  "volatile",
+  "while",
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn escape() {
+    assert_eq!(Reserved::Windows.escape("con.txt"), "con_.txt");
+    assert_eq!(Reserved::Windows.escape("LPT1"), "LPT1_");
+    assert_eq!(Reserved::Windows.escape("console"), "console");
+    assert_eq!(Reserved::Sql.escape("Order"), "\"Order\"");
+    assert_eq!(Reserved::Sql.escape("orders"), "orders");
+    assert_eq!(Reserved::Rust.escape("type"), "r#type");
+    assert_eq!(Reserved::Rust.escape("self"), "self_");
+    assert_eq!(Reserved::Rust.escape("_"), "__");
+    assert_eq!(Reserved::Go.escape("func"), "func_");
+    assert_eq!(Reserved::Python.escape("class"), "class_");
+    assert_eq!(Reserved::Python.escape("None"), "None_");
+    assert_eq!(Reserved::Java.escape("int"), "int_");
+    assert_eq!(Reserved::K8s.escape("kubernetes.io/team"), "team");
+    assert_eq!(Reserved::K8s.escape("node.k8s.io/pool"), "pool");
+    assert_eq!(Reserved::K8s.escape("example.com/team"), "example.com/team");
+    assert_eq!(Reserved::K8s.escape("notk8s.io/team"), "notk8s.io/team");
+  }
+
+  #[test]
+  fn device_names() {
+    assert!(is_device_name("nul"));
+    assert!(is_device_name("Com3.log"));
+    assert!(!is_device_name("abé"));
+  }
+}
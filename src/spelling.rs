@@ -0,0 +1,385 @@
+//! Spelling out the symbols, the amounts and the numbers of the input as
+//! words, before the conversion.
+use crate::words::amount_len;
+use alloc::string::String;
+
+/// The words of the symbols spelled out by the `spell_symbols` option and
+/// filter.
+pub(crate) const SYMBOL_WORDS: &[(&str, &str)] = &[
+  ("&", "and"),
+  ("@", "at"),
+  ("%", "percent"),
+  ("+", "plus"),
+  ("=", "equals"),
+  ("°", "degrees"),
+];
+
+/// `s` with the symbols of `symbols`, then of [`SYMBOL_WORDS`], replaced by
+/// their word, separated from the neighboring words by a space. The first
+/// symbol of `symbols` matching at a position wins, so `symbols` overrides
+/// the default words. An empty word drops its symbol.
+pub(crate) fn spell_symbols<K: AsRef<str>, W: AsRef<str>>(s: &str, symbols: &[(K, W)]) -> String {
+  let symbols = symbols
+    .iter()
+    .map(|(symbol, word)| (symbol.as_ref(), word.as_ref()))
+    .chain(SYMBOL_WORDS.iter().copied())
+    .filter(|(symbol, _)| !symbol.is_empty());
+  let mut output = String::with_capacity(s.len());
+  let mut spaced = false;
+  let mut rest = s;
+  while let Some(c) = rest.chars().next() {
+    match symbols.clone().find(|(symbol, _)| rest.starts_with(symbol)) {
+      Some((symbol, word)) => {
+        if !word.is_empty() {
+          if output
+            .chars()
+            .next_back()
+            .is_some_and(|c| !c.is_whitespace())
+          {
+            output.push(' ');
+          }
+          output.push_str(word);
+        }
+        spaced = true;
+        rest = &rest[symbol.len()..];
+      }
+      None => {
+        let after_word = output
+          .chars()
+          .next_back()
+          .is_some_and(|c| !c.is_whitespace());
+        if spaced && after_word && !c.is_whitespace() {
+          output.push(' ');
+        }
+        spaced = false;
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+      }
+    }
+  }
+  output
+}
+
+/// The currency symbols with the singular and plural word of their
+/// currency.
+const CURRENCIES: &[(char, &str, &str)] = &[
+  ('$', "dollar", "dollars"),
+  ('€', "euro", "euros"),
+  ('£', "pound", "pounds"),
+  ('¥', "yen", "yen"),
+  ('₹', "rupee", "rupees"),
+  ('₽', "ruble", "rubles"),
+  ('₩', "won", "won"),
+  ('¢', "cent", "cents"),
+];
+
+/// The unit abbreviations following an amount, with the singular and
+/// plural word of their unit.
+const UNITS: &[(&str, &str, &str)] = &[
+  ("mm", "millimeter", "millimeters"),
+  ("cm", "centimeter", "centimeters"),
+  ("m", "meter", "meters"),
+  ("km", "kilometer", "kilometers"),
+  ("mg", "milligram", "milligrams"),
+  ("g", "gram", "grams"),
+  ("kg", "kilogram", "kilograms"),
+  ("oz", "ounce", "ounces"),
+  ("ml", "milliliter", "milliliters"),
+  ("l", "liter", "liters"),
+  ("kb", "kilobyte", "kilobytes"),
+  ("mb", "megabyte", "megabytes"),
+  ("gb", "gigabyte", "gigabytes"),
+  ("tb", "terabyte", "terabytes"),
+  ("ms", "millisecond", "milliseconds"),
+  ("s", "second", "seconds"),
+  ("min", "minute", "minutes"),
+  ("h", "hour", "hours"),
+  ("hz", "hertz", "hertz"),
+  ("khz", "kilohertz", "kilohertz"),
+  ("mhz", "megahertz", "megahertz"),
+  ("ghz", "gigahertz", "gigahertz"),
+  ("w", "watt", "watts"),
+  ("kw", "kilowatt", "kilowatts"),
+  ("px", "pixel", "pixels"),
+];
+
+/// The length of the unit abbreviation, of [`UNITS`], starting `s` and
+/// ending its word, with the words of the unit.
+pub(crate) fn unit_len(s: &str) -> Option<(usize, &'static str, &'static str)> {
+  let len = s.len() - s.trim_start_matches(|c: char| c.is_alphabetic()).len();
+  if s[len..].starts_with(|c: char| c.is_alphanumeric()) {
+    return None;
+  }
+  UNITS
+    .iter()
+    .find(|(unit, _, _)| unit.eq_ignore_ascii_case(&s[..len]))
+    .map(|&(_, singular, plural)| (len, singular, plural))
+}
+
+/// The currency of the symbol starting `s`, followed by an amount, with
+/// the length of the symbol and the space after it.
+fn leading_currency(s: &str) -> Option<(usize, &'static str, &'static str)> {
+  let c = s.chars().next()?;
+  let &(_, singular, plural) = CURRENCIES.iter().find(|(symbol, _, _)| *symbol == c)?;
+  let len = c.len_utf8() + usize::from(s[c.len_utf8()..].starts_with(' '));
+  amount_len(&s[len..]).map(|_| (len, singular, plural))
+}
+
+/// The currency of the symbol starting `s`, after an amount, with the
+/// length of the space before it and the symbol.
+fn trailing_currency(s: &str) -> Option<(usize, &'static str, &'static str)> {
+  let space = usize::from(s.starts_with(' '));
+  let c = s[space..].chars().next()?;
+  let &(_, singular, plural) = CURRENCIES.iter().find(|(symbol, _, _)| *symbol == c)?;
+  Some((space + c.len_utf8(), singular, plural))
+}
+
+/// `s` with its currency amounts and quantities rewritten: with `spell`,
+/// the currency symbols and the unit abbreviations are spelled out after
+/// the amount (`$19.99` becomes `19.99 dollars`, `500ml` becomes
+/// `500 milliliters`); without, the currency symbols are dropped and the
+/// units joined to their amount (`500 ml` becomes `500ml`).
+pub(crate) fn convert_units(s: &str, spell: bool) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut rest = s;
+  let mut currency = None;
+  while let Some(c) = rest.chars().next() {
+    let after_word = output.ends_with(|c: char| c.is_alphanumeric());
+    if let Some((len, singular, plural)) = leading_currency(rest).filter(|_| !after_word) {
+      currency = Some((singular, plural));
+      rest = &rest[len..];
+      continue;
+    }
+    let Some(len) = amount_len(rest).filter(|_| !after_word) else {
+      output.push(c);
+      rest = &rest[c.len_utf8()..];
+      continue;
+    };
+    let amount = &rest[..len];
+    rest = &rest[len..];
+    let one = amount == "1";
+    let word = |(singular, plural): (&'static str, &'static str)| if one { singular } else { plural };
+    if let Some((len, singular, plural)) = trailing_currency(rest).filter(|_| currency.is_none()) {
+      currency = Some((singular, plural));
+      rest = &rest[len..];
+    }
+    output.push_str(amount);
+    let space = usize::from(rest.starts_with(' '));
+    if let Some((len, singular, plural)) = unit_len(&rest[space..]) {
+      match spell {
+        true => {
+          output.push(' ');
+          output.push_str(word((singular, plural)));
+        }
+        false => output.push_str(&rest[space..space + len]),
+      }
+      rest = &rest[space + len..];
+    }
+    if let Some(currency) = currency.take().filter(|_| spell) {
+      output.push(' ');
+      output.push_str(word(currency));
+    }
+  }
+  output
+}
+
+/// The English words of the numbers below twenty.
+#[cfg(feature = "number-words")]
+const ONES: [&str; 20] = [
+  "zero",
+  "one",
+  "two",
+  "three",
+  "four",
+  "five",
+  "six",
+  "seven",
+  "eight",
+  "nine",
+  "ten",
+  "eleven",
+  "twelve",
+  "thirteen",
+  "fourteen",
+  "fifteen",
+  "sixteen",
+  "seventeen",
+  "eighteen",
+  "nineteen",
+];
+
+/// The English words of the tens, from twenty.
+#[cfg(feature = "number-words")]
+const TENS: [&str; 8] = [
+  "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// The English words of the powers of a thousand, from a thousand.
+#[cfg(feature = "number-words")]
+const SCALES: [&str; 6] = [
+  "thousand",
+  "million",
+  "billion",
+  "trillion",
+  "quadrillion",
+  "quintillion",
+];
+
+/// Push the English words of `n`, separated by spaces: `342` is
+/// `three hundred forty two`.
+#[cfg(feature = "number-words")]
+fn push_number_words(output: &mut String, n: u64) {
+  fn push(output: &mut String, word: &str) {
+    if !output.is_empty() && !output.ends_with(' ') {
+      output.push(' ');
+    }
+    output.push_str(word);
+  }
+  /// Push the words of `n`, below a thousand and not zero.
+  fn push_hundreds(output: &mut String, n: u64) {
+    if n >= 100 {
+      push(output, ONES[(n / 100) as usize]);
+      push(output, "hundred");
+    }
+    match n % 100 {
+      0 => {}
+      n @ 1..=19 => push(output, ONES[n as usize]),
+      n => {
+        push(output, TENS[(n / 10 - 2) as usize]);
+        if n % 10 != 0 {
+          push(output, ONES[(n % 10) as usize]);
+        }
+      }
+    }
+  }
+  if n == 0 {
+    push(output, ONES[0]);
+    return;
+  }
+  let mut groups = [0; 7];
+  let mut rest = n;
+  for group in &mut groups {
+    *group = rest % 1000;
+    rest /= 1000;
+  }
+  for (scale, &group) in groups.iter().enumerate().rev() {
+    if group == 0 {
+      continue;
+    }
+    push_hundreds(output, group);
+    if scale > 0 {
+      push(output, SCALES[scale - 1]);
+    }
+  }
+}
+
+/// `s` with its runs of ASCII digits spelled out as English words, all of
+/// them or only a run starting the first word with `leading_only`:
+/// `3d_model` becomes `three d_model`. The runs with a leading zero or too
+/// long for a `u64` are spelled digit by digit.
+#[cfg(feature = "number-words")]
+pub(crate) fn spell_numbers(s: &str, leading_only: bool) -> String {
+  let mut output = String::with_capacity(s.len() * 2);
+  let mut rest = s;
+  let mut first = true;
+  while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+    let leading = first && rest[..start].chars().all(|c| !c.is_alphanumeric());
+    first = false;
+    let len = rest[start..]
+      .find(|c: char| !c.is_ascii_digit())
+      .unwrap_or(rest.len() - start);
+    let (before, digits) = (&rest[..start], &rest[start..start + len]);
+    rest = &rest[start + len..];
+    output.push_str(before);
+    if leading_only && !leading {
+      output.push_str(digits);
+      continue;
+    }
+    let mut words = String::new();
+    match digits.parse::<u64>() {
+      Ok(n) if !(digits.starts_with('0') && digits.len() > 1) => push_number_words(&mut words, n),
+      _ => {
+        for digit in digits.bytes() {
+          push_number_words(&mut words, u64::from(digit - b'0'));
+        }
+      }
+    }
+    if output.ends_with(|c: char| c.is_alphanumeric()) {
+      output.push(' ');
+    }
+    output.push_str(&words);
+    if rest.starts_with(|c: char| c.is_alphanumeric()) {
+      output.push(' ');
+    }
+  }
+  output.push_str(rest);
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn spelled_symbols() {
+    let none: &[(&str, &str)] = &[];
+    assert_eq!(spell_symbols("R&D @ 50%", none), "R and D at 50 percent");
+    assert_eq!(spell_symbols("Q&A+", none), "Q and A plus");
+    assert_eq!(
+      spell_symbols("R&D->ops #1", &[("&", "n"), ("->", "to"), ("#", "")]),
+      "R n D to ops 1"
+    );
+    assert_eq!(spell_symbols("a#b", &[("#", "")]), "a b");
+    assert_eq!(spell_symbols("no symbol", none), "no symbol");
+  }
+
+  #[test]
+  #[cfg(feature = "number-words")]
+  fn spelled_numbers() {
+    assert_eq!(spell_numbers("3d_model", true), "three d_model");
+    assert_eq!(spell_numbers("_2fa code 2", true), "_two fa code 2");
+    assert_eq!(spell_numbers("model 3d", true), "model 3d");
+    assert_eq!(
+      spell_numbers("top 10 of 2024, 007", false),
+      "top ten of two thousand twenty four, zero zero seven"
+    );
+    assert_eq!(
+      spell_numbers("1000001 115", false),
+      "one million one one hundred fifteen"
+    );
+    assert_eq!(spell_numbers("0", false), "zero");
+    assert_eq!(
+      spell_numbers("18446744073709551615", false),
+      "eighteen quintillion four hundred forty six quadrillion seven hundred forty four \
+       trillion seventy three billion seven hundred nine million five hundred fifty one \
+       thousand six hundred fifteen"
+    );
+    assert_eq!(
+      spell_numbers("99999999999999999999", false)
+        .split(' ')
+        .count(),
+      20
+    );
+  }
+
+  #[test]
+  fn units() {
+    let price = "Price: $19.99 (A4 size)";
+    assert_eq!(convert_units(price, true), "Price: 19.99 dollars (A4 size)");
+    assert_eq!(convert_units(price, false), "Price: 19.99 (A4 size)");
+    assert_eq!(
+      convert_units("6 x 500 ml for 1€, 1 kg, 10 songs", true),
+      "6 x 500 milliliters for 1 euro, 1 kilogram, 10 songs"
+    );
+    assert_eq!(
+      convert_units("6 x 500 ml for 1€, 1 kg, 10 songs", false),
+      "6 x 500ml for 1, 1kg, 10 songs"
+    );
+    assert_eq!(
+      convert_units("$ 1,299.50 v1.2 A4", true),
+      "1,299.50 dollars v1.2 A4"
+    );
+    assert_eq!(convert_units("a $ sign", true), "a $ sign");
+  }
+}
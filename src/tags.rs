@@ -0,0 +1,289 @@
+//! Tags converting the variables of the template scope.
+use crate::args::{invalid_argument, one_of};
+use crate::filters::{runtime_options, update_options, HeckDefaults};
+use crate::objects::{case_argument, convert_value_with, KeyPolicy, ValuePolicy};
+use crate::options::OPTION_NAMES;
+use liquid_core::model::{ScalarCow, State};
+use liquid_core::{
+  Expression, Language, Object, ParseTag, Renderable, Result, Runtime, TagReflection, TagTokenIter,
+  ValueView,
+};
+use std::io::Write;
+
+/// Parse the `name: value` attributes of a tag, in any order, as the values
+/// of the attributes `names`.
+fn attributes<const N: usize>(
+  mut arguments: TagTokenIter<'_>,
+  names: [&str; N],
+) -> Result<[Option<Expression>; N]> {
+  let mut values = [(); N].map(|_| None);
+  while let Some(token) = arguments.next() {
+    let Some(index) = names.iter().position(|name| *name == token.as_str()) else {
+      let expected = one_of(&names);
+      return Err(token.raise_custom_error(&format!("Unexpected attribute, {expected} expected.")));
+    };
+    arguments
+      .expect_next("\":\" expected.")?
+      .expect_str(":")
+      .into_result_custom_msg("\":\" expected.")?;
+    let value = arguments
+      .expect_next("Value expected.")?
+      .expect_value()
+      .into_result()?;
+    values[index] = Some(value);
+  }
+  Ok(values)
+}
+
+/// Is the optional attribute `value` truthy?
+fn is_set(value: &Option<Expression>, runtime: &dyn Runtime) -> Result<Option<bool>> {
+  value
+    .as_ref()
+    .map(|value| Ok(value.evaluate(runtime)?.query_state(State::Truthy)))
+    .transpose()
+}
+
+/// Replace a variable with a copy whose keys are converted:
+/// `{% convert_keys payload to: "camel" %}`.
+///
+/// The rest of the template then reads `payload.userId` without piping at
+/// every access. `recursive: true` also converts the keys of the nested
+/// objects.
+#[cfg(feature = "tag-convert-keys")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConvertKeysTag;
+
+#[cfg(feature = "tag-convert-keys")]
+impl TagReflection for ConvertKeysTag {
+  fn tag(&self) -> &'static str {
+    "convert_keys"
+  }
+
+  fn description(&self) -> &'static str {
+    "Replace the variable with a copy whose keys are converted to the given case."
+  }
+}
+
+#[cfg(feature = "tag-convert-keys")]
+impl ParseTag for ConvertKeysTag {
+  fn parse(
+    &self,
+    mut arguments: TagTokenIter<'_>,
+    _options: &Language,
+  ) -> Result<Box<dyn Renderable>> {
+    let name = arguments
+      .expect_next("Identifier expected.")?
+      .expect_identifier()
+      .into_result()?
+      .to_owned();
+
+    let [to, recursive] = attributes(arguments, ["to", "recursive"])?;
+    let to = to.ok_or_else(|| invalid_argument("to", "Case name expected"))?;
+
+    Ok(Box::new(ConvertKeys {
+      name,
+      to,
+      recursive,
+    }))
+  }
+
+  fn reflection(&self) -> &dyn TagReflection {
+    self
+  }
+}
+
+#[cfg(feature = "tag-convert-keys")]
+#[derive(Debug)]
+struct ConvertKeys {
+  name: String,
+  to: Expression,
+  recursive: Option<Expression>,
+}
+
+#[cfg(feature = "tag-convert-keys")]
+impl Renderable for ConvertKeys {
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_writer, runtime)))]
+  fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+    let case = case_argument("to", self.to.evaluate(runtime)?.to_kstr().as_str())?;
+    let recursive = is_set(&self.recursive, runtime)?.unwrap_or(false);
+    let value = runtime.get(&[ScalarCow::new(self.name.as_str())])?;
+    let keys = match recursive {
+      true => KeyPolicy::Recursive(case),
+      false => KeyPolicy::Shallow(case),
+    };
+    let options = runtime_options(runtime)?;
+    let converted = convert_value_with(value.as_view(), keys, ValuePolicy::Keep, &options);
+    runtime.set_global(self.name.clone().into(), converted);
+    Ok(())
+  }
+}
+
+/// Set the default options of the conversions for the rest of the render:
+/// `{% heck_defaults acronyms: "ID,URL" digit_boundary: true %}`.
+///
+/// All the following filters, in the template and in its includes, then
+/// honor the options without repeating them. The attributes are the fields
+/// of [`Options`](crate::Options), e.g. `acronyms` (an array or a
+/// comma-separated string), `digit_boundary` or `acronym_style: "preserve"`,
+/// and only update the options they name. The filters registered with their
+/// own options, see [`ConfiguredCase`](crate::ConfiguredCase), keep the
+/// fields they set and take the others from the tag.
+#[cfg(feature = "tag-heck-defaults")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct HeckDefaultsTag;
+
+#[cfg(feature = "tag-heck-defaults")]
+impl TagReflection for HeckDefaultsTag {
+  fn tag(&self) -> &'static str {
+    "heck_defaults"
+  }
+
+  fn description(&self) -> &'static str {
+    "Set the default options of the conversions for the rest of the render."
+  }
+}
+
+#[cfg(feature = "tag-heck-defaults")]
+impl ParseTag for HeckDefaultsTag {
+  fn parse(&self, arguments: TagTokenIter<'_>, _options: &Language) -> Result<Box<dyn Renderable>> {
+    Ok(Box::new(HeckDefaultsRenderable(attributes(
+      arguments,
+      OPTION_NAMES,
+    )?)))
+  }
+
+  fn reflection(&self) -> &dyn TagReflection {
+    self
+  }
+}
+
+#[cfg(feature = "tag-heck-defaults")]
+#[derive(Debug)]
+struct HeckDefaultsRenderable([Option<Expression>; OPTION_NAMES.len()]);
+
+#[cfg(feature = "tag-heck-defaults")]
+impl Renderable for HeckDefaultsRenderable {
+  #[cfg_attr(feature = "tracing", tracing::instrument(skip(_writer, runtime)))]
+  fn render_to(&self, _writer: &mut dyn Write, runtime: &dyn Runtime) -> Result<()> {
+    let mut fields = Object::new();
+    for (name, value) in OPTION_NAMES.iter().zip(&self.0) {
+      if let Some(value) = value {
+        fields.insert((*name).into(), value.evaluate(runtime)?.into_owned());
+      }
+    }
+    let mut options = runtime_options(runtime)?;
+    update_options(&mut options, &fields)?;
+    runtime.registers().get_mut::<HeckDefaults>().0 = Some(options);
+    Ok(())
+  }
+}
+
+#[cfg(all(test, feature = "all-filters"))]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn convert_keys() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .tag(ConvertKeysTag)
+      .build()
+      .unwrap();
+    let globals = liquid::object!({
+      "payload": { "user_id": 7, "home_page": { "page_url": "/" } },
+    });
+    let render = |template| parser.parse(template)?.render(&globals);
+    assert_eq!(
+      render(
+        "{% convert_keys payload to: 'camel' %}{{ payload.userId }} {{ payload.homePage.page_url }}"
+      )
+      .unwrap(),
+      "7 /"
+    );
+    assert_eq!(
+      render(
+        "{% convert_keys payload to: 'kebabcase' recursive: true %}{{ payload.home-page.page-url }}"
+      )
+      .unwrap(),
+      "/"
+    );
+    assert!(render("{% convert_keys payload to: 'upcase' %}").is_err());
+    assert!(render("{% convert_keys payload %}").is_err());
+  }
+
+  #[test]
+  fn heck_defaults() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(crate::UpperCamelCase)
+      .filter(crate::SnakeCase)
+      .filter(
+        crate::UpperCamelCase::with_options(crate::Options {
+          acronyms: vec!["URL".to_string()],
+          ..crate::Options::default()
+        })
+        .named("type_name"),
+      )
+      .tag(HeckDefaultsTag)
+      .build()
+      .unwrap();
+    let globals = liquid::object!({ "field": "user_id2" });
+    let render = |template| parser.parse(template)?.render(&globals);
+    assert_eq!(
+      render(
+        "{{ field | uppercamelcase }} \
+         {% heck_defaults acronyms: 'id' digit_boundary: true %}\
+         {{ field | uppercamelcase }} {{ field | snakecase }} \
+         {% heck_defaults digit_boundary: false %}{{ field | uppercamelcase }} \
+         {% heck_defaults acronym_style: 'lower' %}{{ 'user_id' | uppercamelcase }}"
+      )
+      .unwrap(),
+      "UserId2 UserID2 user_id_2 UserId2 UserId"
+    );
+    // The acronyms of `type_name` win over the tag, its acronym style comes
+    // from the tag.
+    assert_eq!(
+      render(
+        "{% heck_defaults acronyms: 'id' %}{{ 'user_id url' | type_name }} \
+         {% heck_defaults acronym_style: 'lower' %}{{ 'user_id url' | type_name }}"
+      )
+      .unwrap(),
+      "UserIdURL UserIdUrl"
+    );
+    assert!(render("{% heck_defaults acronym: 'id' %}").is_err());
+    assert!(render("{% heck_defaults acronym_style: 'loud' %}").is_err());
+  }
+
+  #[test]
+  fn heck_defaults_with_filter_options() {
+    let type_name = crate::UpperCamelCase::with_options(crate::Options {
+      acronyms: vec!["ID".to_string()],
+      ..crate::Options::default()
+    })
+    .named("type_name");
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(type_name)
+      .tag(HeckDefaultsTag)
+      .build()
+      .unwrap();
+    let template = parser
+      .parse(
+        "{{ 'user_id2 url' | type_name }} \
+         {% heck_defaults acronyms: 'url' digit_boundary: true %}{{ 'user_id2 url' | type_name }}",
+      )
+      .unwrap();
+    // The acronyms of the filter win, the other options come from the render.
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "UserId2Url UserID2Url"
+    );
+    let globals = liquid::object!({ "heck_config": { "digit_boundary": true } });
+    assert_eq!(
+      parser
+        .parse("{{ 'user_id2' | type_name }}")
+        .unwrap()
+        .render(&globals)
+        .unwrap(),
+      "UserID2"
+    );
+  }
+}
@@ -0,0 +1,2247 @@
+//! Filters making strings valid names for other systems: email addresses,
+//! Git refs, Docker references, Python packages, Maven coordinates, Helm
+//! releases, AWS and GCP resources, file names, unique slugs, heading
+//! anchors, Sphinx and LaTeX labels, ...
+use crate::args::{invalid_argument, positional_or_keyword, unknown_value};
+use crate::conversion::{self, to_kebab_case, to_lower_camel_case, to_upper_camel_case, HeckCase};
+use crate::filters::{nil_output, prepare_input, runtime_options};
+use crate::limit::{shorten, shorten_unique, stable_hash, Strategy};
+use crate::objects::case_argument;
+#[cfg(feature = "filter-safe-filename")]
+use crate::options::is_invisible;
+use crate::reserved::Reserved;
+use crate::translit::to_ascii;
+use crate::Options;
+use liquid_core::parser::{FilterArguments, ParameterReflection};
+use liquid_core::{
+  Display_filter, Error, Expression, Filter, FilterParameters, FilterReflection,
+  FromFilterParameters, ParseFilter, Result, Runtime, Value, ValueView,
+};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Define a target filter converting with a `fn(&str) -> String`, or a
+/// `fn(&str) -> Result<String>` failing the render with `try_convert`,
+/// without arguments: the name is valid for its target as is.
+#[cfg_attr(not(feature = "all-filters"), allow(unused_macros))]
+macro_rules! define_target_filter {
+  (
+    $(#[$attr:meta])*
+    $vis:vis struct $ty:ident;
+    name = $name:literal,
+    description = $description:literal,
+    convert = $convert:expr $(,)?
+  ) => {
+    define_target_filter! {
+      $(#[$attr])*
+      $vis struct $ty;
+      name = $name,
+      description = $description,
+      try_convert = |s| Ok($convert(s)),
+    }
+  };
+  (
+    $(#[$attr:meta])*
+    $vis:vis struct $ty:ident;
+    name = $name:literal,
+    description = $description:literal,
+    try_convert = $convert:expr $(,)?
+  ) => {
+    $(#[$attr])*
+    #[derive(Clone, Copy, Debug, Default)]
+    $vis struct $ty;
+
+    impl FilterReflection for $ty {
+      fn name(&self) -> &str {
+        $name
+      }
+
+      fn description(&self) -> &str {
+        $description
+      }
+
+      fn positional_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+      }
+
+      fn keyword_parameters(&self) -> &'static [ParameterReflection] {
+        &[]
+      }
+    }
+
+    impl ParseFilter for $ty {
+      fn parse(&self, arguments: FilterArguments) -> Result<Box<dyn Filter>> {
+        no_arguments(arguments)?;
+        Ok(Box::new(TargetFilter {
+          name: $name,
+          convert: $convert,
+        }))
+      }
+
+      fn reflection(&self) -> &dyn FilterReflection {
+        self
+      }
+    }
+  };
+}
+
+/// Fail on any argument, as the filters deriving `ParseFilter` without
+/// parameters do.
+fn no_arguments(mut arguments: FilterArguments) -> Result<()> {
+  if arguments.positional.next().is_some() {
+    return Err(
+      Error::with_msg("Invalid number of positional arguments")
+        .context("cause", "expected at most 0 positional arguments"),
+    );
+  }
+  match arguments.keyword.next() {
+    Some((name, _)) => Err(Error::with_msg(format!(
+      "Unexpected named argument `{name}`"
+    ))),
+    None => Ok(()),
+  }
+}
+
+/// The parsed filter of [`define_target_filter!`].
+struct TargetFilter {
+  name: &'static str,
+  convert: fn(&str) -> Result<String>,
+}
+
+impl fmt::Debug for TargetFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TargetFilter")
+      .field("name", &self.name)
+      .finish()
+  }
+}
+
+impl fmt::Display for TargetFilter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name)
+  }
+}
+
+impl Filter for TargetFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    evaluate(self.name, input, runtime, self.convert)
+  }
+}
+
+/// Evaluate the target filter `name` on `input` with `convert`, the nil
+/// input as the `nil_policy` requires.
+#[cfg_attr(not(feature = "log"), allow(unused_variables))]
+pub(crate) fn evaluate(
+  name: &str,
+  input: &dyn ValueView,
+  runtime: &dyn Runtime,
+  convert: impl FnOnce(&str) -> Result<String>,
+) -> Result<Value> {
+  if let Some(output) = nil_output(input, runtime)? {
+    return Ok(output);
+  }
+  let s = input.to_kstr();
+  let s = s.as_str();
+  let s = prepare_input(s, &runtime_options(runtime)?)?;
+  let s = &*s;
+  let output = convert(s)?;
+  #[cfg(feature = "log")]
+  log::debug!(
+    "{name}: {} -> {}",
+    crate::instrument::Redacted::Str(s),
+    crate::instrument::Redacted::Str(&output)
+  );
+  Ok(Value::scalar(output))
+}
+
+#[cfg(feature = "filter-email-local")]
+define_target_filter! {
+  /// Make a display name the local part of an email address, lowercase and
+  /// dot-separated: `{{ "María-José O'Neill" | email_local }}` renders
+  /// `maria.jose.oneill`.
+  pub struct EmailLocal;
+  name = "email_local",
+  description = "Make the string a lowercase, dot-separated email local part.",
+  convert = email_local,
+}
+
+/// The maximum length of an email local part (RFC 5321).
+const EMAIL_LOCAL_MAX_LEN: usize = 64;
+
+/// The words of `s` transliterated to ASCII, lowercase and joined with dots,
+/// within the 64 characters of RFC 5321. The apostrophes are dropped and
+/// the other characters separate the words.
+fn email_local(s: &str) -> String {
+  let ascii = to_ascii(&s.replace(['\'', '’'], ""), ' ');
+  let mut output = String::with_capacity(ascii.len());
+  for word in ascii
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|word| !word.is_empty())
+  {
+    let separator = usize::from(!output.is_empty());
+    if output.len() + separator + word.len() > EMAIL_LOCAL_MAX_LEN {
+      if output.is_empty() {
+        output.push_str(&word[..EMAIL_LOCAL_MAX_LEN]);
+      }
+      break;
+    }
+    if separator == 1 {
+      output.push('.');
+    }
+    output.push_str(word);
+  }
+  output.make_ascii_lowercase();
+  output
+}
+
+#[cfg(feature = "filter-git-ref")]
+define_target_filter! {
+  /// Make a string a valid Git branch name, its `/`-separated components in
+  /// kebab-case: `{{ "feature/PROJ-42 Fix the login!" | git_ref }}` renders
+  /// `feature/proj-42-fix-the-login`.
+  pub struct GitRef;
+  name = "git_ref",
+  description = "Make the string a valid Git ref name, in kebab-case.",
+  convert = git_ref,
+}
+
+/// The `/`-separated components of `s` in kebab-case, the empty ones
+/// dropped. Kebab-case only keeps letters, digits and single inner hyphens,
+/// so the ref follows `git check-ref-format`: no `..`, `~^:?*[`, `@{`,
+/// spaces or control characters, no component starting with a dot or
+/// ending with `.lock`, no leading, trailing or double slash.
+fn git_ref(s: &str) -> String {
+  let mut output = String::with_capacity(s.len());
+  for component in s.split('/').map(to_kebab_case) {
+    if component.is_empty() {
+      continue;
+    }
+    if !output.is_empty() {
+      output.push('/');
+    }
+    output.push_str(&component);
+  }
+  output
+}
+
+#[cfg(feature = "filter-docker")]
+define_target_filter! {
+  /// Make a project name a valid Docker image repository, its
+  /// `/`-separated components lowercase and hyphenated:
+  /// `{{ "ACME Corp/Web App" | docker_repo }}` renders `acme-corp/web-app`.
+  pub struct DockerRepo;
+  name = "docker_repo",
+  description = "Make the string a valid Docker image repository name.",
+  convert = docker_repo,
+}
+
+#[cfg(feature = "filter-docker")]
+define_target_filter! {
+  /// Make a string a valid Docker image tag, at most 128 letters, digits,
+  /// `_`, `.` and `-`: `{{ "feature/Login Fix v1.2" | docker_tag }}` renders
+  /// `feature-Login-Fix-v1.2`.
+  pub struct DockerTag;
+  name = "docker_tag",
+  description = "Make the string a valid Docker image tag.",
+  convert = docker_tag,
+}
+
+/// The maximum length of a Docker repository name.
+const DOCKER_REPO_MAX_LEN: usize = 255;
+
+/// The maximum length of a Docker tag.
+const DOCKER_TAG_MAX_LEN: usize = 128;
+
+/// The `/`-separated components of `s` transliterated to ASCII, in
+/// kebab-case, the empty ones dropped, within 255 characters.
+fn docker_repo(s: &str) -> String {
+  let mut output = String::with_capacity(s.len());
+  for component in to_ascii(s, ' ').split('/').map(to_kebab_case) {
+    if component.is_empty() {
+      continue;
+    }
+    let separator = usize::from(!output.is_empty());
+    if output.len() + separator + component.len() > DOCKER_REPO_MAX_LEN {
+      break;
+    }
+    if separator == 1 {
+      output.push('/');
+    }
+    output.push_str(&component);
+  }
+  output
+}
+
+/// `s` with letters, digits, `_` and `.`, within 128 characters, not
+/// starting with `.`.
+fn docker_tag(s: &str) -> String {
+  let tag = hyphenate(s, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+  truncate(tag.trim_start_matches(['.', '-']), DOCKER_TAG_MAX_LEN)
+}
+
+/// `s` transliterated to ASCII, each run of characters other than the
+/// `valid` ones replaced with a hyphen, without leading nor trailing
+/// hyphen.
+fn hyphenate(s: &str, valid: impl Fn(char) -> bool) -> String {
+  let mut output = String::with_capacity(s.len());
+  for c in to_ascii(s, '-').chars() {
+    match c {
+      c if c != '-' && valid(c) => output.push(c),
+      _ if output.is_empty() || output.ends_with('-') => {}
+      _ => output.push('-'),
+    }
+  }
+  if output.ends_with('-') {
+    output.pop();
+  }
+  output
+}
+
+/// The first `max_len` characters of the ASCII `s`, without trailing
+/// hyphen.
+fn truncate(s: &str, max_len: usize) -> String {
+  s[..s.len().min(max_len)].trim_end_matches('-').to_string()
+}
+
+#[cfg(feature = "filter-pypi")]
+define_target_filter! {
+  /// Normalize a Python project name as PEP 503 does, lowercase with the
+  /// runs of `-`, `_` and `.` collapsed to a hyphen:
+  /// `{{ "My_Cool.Package" | pypi_name }}` renders `my-cool-package`.
+  pub struct PypiName;
+  name = "pypi_name",
+  description = "Normalize the string as a PyPI project name (PEP 503).",
+  convert = pypi_name,
+}
+
+#[cfg(feature = "filter-pypi")]
+define_target_filter! {
+  /// Make a Python project name the name of its top-level module:
+  /// `{{ "My-Cool.Package" | py_dist_to_module }}` renders
+  /// `my_cool_package`.
+  pub struct PyDistToModule;
+  name = "py_dist_to_module",
+  description = "Make the PyPI project name a Python module name.",
+  convert = py_dist_to_module,
+}
+
+/// `s` normalized as PEP 503 does: lowercase, the runs of `-`, `_` and `.`
+/// collapsed to a hyphen. So that the name is valid (PEP 508), it is
+/// transliterated to ASCII, the other characters separate the words too and
+/// the name neither starts nor ends with a hyphen.
+fn pypi_name(s: &str) -> String {
+  let mut output = String::with_capacity(s.len());
+  for c in to_ascii(s, '-').chars() {
+    match c {
+      c if c.is_ascii_alphanumeric() => output.push(c.to_ascii_lowercase()),
+      _ if output.is_empty() || output.ends_with('-') => {}
+      _ => output.push('-'),
+    }
+  }
+  if output.ends_with('-') {
+    output.pop();
+  }
+  output
+}
+
+/// The [`pypi_name`] of `s` with underscores, prefixed with one when it
+/// starts with a digit.
+fn py_dist_to_module(s: &str) -> String {
+  let name = pypi_name(s).replace('-', "_");
+  match name.starts_with(|c: char| c.is_ascii_digit()) {
+    true => format!("_{name}"),
+    false => name,
+  }
+}
+
+#[cfg(feature = "filter-maven")]
+#[derive(Debug, FilterParameters)]
+struct MavenArtifactArgs {
+  #[parameter(
+    description = "Join the words without hyphens: `mycoollib`.",
+    arg_type = "bool"
+  )]
+  flat: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `flat`.",
+    arg_type = "bool",
+    mode = "keyword",
+    rename = "flat"
+  )]
+  flat_keyword: Option<Expression>,
+}
+
+/// Make a project name a Maven artifactId, lowercase and hyphenated:
+/// `{{ "My Cool Lib" | maven_artifact }}` renders `my-cool-lib`, or
+/// `mycoollib` with `flat: true`.
+#[cfg(feature = "filter-maven")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "maven_artifact",
+  description = "Make the string a Maven artifactId.",
+  parameters(MavenArtifactArgs),
+  parsed(MavenArtifactFilter)
+)]
+pub struct MavenArtifact;
+
+#[cfg(feature = "filter-maven")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "maven_artifact"]
+struct MavenArtifactFilter {
+  #[parameters]
+  args: MavenArtifactArgs,
+}
+
+#[cfg(feature = "filter-maven")]
+impl Filter for MavenArtifactFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let flat = positional_or_keyword("flat", args.flat, args.flat_keyword)?.unwrap_or(false);
+    evaluate("maven_artifact", input, runtime, |s| {
+      let artifact = to_kebab_case(&to_ascii(s, ' '));
+      Ok(match flat {
+        true => artifact.replace('-', ""),
+        false => artifact,
+      })
+    })
+  }
+}
+
+#[cfg(feature = "filter-maven")]
+define_target_filter! {
+  /// Make a reverse-DNS name a Maven groupId, each dot-separated segment a
+  /// Java package name: `{{ "com.Acme-Corp.2fa.int" | maven_group }}`
+  /// renders `com.acme_corp._2fa.int_`.
+  pub struct MavenGroup;
+  name = "maven_group",
+  description = "Make the string a Maven groupId of valid Java package names.",
+  convert = maven_group,
+}
+
+/// The dot-separated segments of `s` made Java package names as the Java
+/// Language Specification (6.1) suggests: transliterated to ASCII,
+/// lowercase, the other characters replaced with underscores, an
+/// underscore before a leading digit and after a keyword. The empty
+/// segments are dropped.
+fn maven_group(s: &str) -> String {
+  let mut output = String::with_capacity(s.len());
+  for segment in to_ascii(s, '_').split('.') {
+    let segment = segment.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    if segment.is_empty() {
+      continue;
+    }
+    if !output.is_empty() {
+      output.push('.');
+    }
+    if segment.starts_with(|c: char| c.is_ascii_digit()) {
+      output.push('_');
+    }
+    let start = output.len();
+    output.extend(segment.chars().map(|c| match c.is_ascii_alphanumeric() {
+      true => c.to_ascii_lowercase(),
+      false => '_',
+    }));
+    if Reserved::Java.is_reserved(&output[start..]) {
+      output.push('_');
+    }
+  }
+  output
+}
+
+#[cfg(feature = "filter-helm")]
+#[derive(Debug, FilterParameters)]
+struct HelmNameArgs {
+  #[parameter(
+    description = "The maximum length. Defaults to 53, leaving room for the suffixes of the release resources.",
+    arg_type = "integer"
+  )]
+  max_length: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `max_length`.",
+    arg_type = "integer",
+    mode = "keyword",
+    rename = "max_length"
+  )]
+  max_length_keyword: Option<Expression>,
+  #[parameter(
+    description = "How a longer name is shortened: \"cut\" (the default) at the maximum length, \"words\" after the last whole word that fits, \"hash\" after a whole word too, followed by a hash of the name.",
+    arg_type = "str"
+  )]
+  truncate: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `truncate`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "truncate"
+  )]
+  truncate_keyword: Option<Expression>,
+}
+
+/// Make a string a Helm release or chart name, lowercase letters, digits
+/// and hyphens (RFC 1123) of at most 53 characters:
+/// `{{ "Payments API (EU)" | helm_name }}` renders `payments-api-eu`.
+/// `max_length:` changes the limit and `truncate:` how the longer names are
+/// shortened: `"cut"`, `"words"` or `"hash"`, which keeps distinct names
+/// distinct.
+#[cfg(feature = "filter-helm")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "helm_name",
+  description = "Make the string a valid Helm release or chart name.",
+  parameters(HelmNameArgs),
+  parsed(HelmNameFilter)
+)]
+pub struct HelmName;
+
+#[cfg(feature = "filter-helm")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "helm_name"]
+struct HelmNameFilter {
+  #[parameters]
+  args: HelmNameArgs,
+}
+
+#[cfg(feature = "filter-helm")]
+impl Filter for HelmNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let max_length = positional_or_keyword("max_length", args.max_length, args.max_length_keyword)?;
+    let max_length = usize::try_from(max_length.unwrap_or(53))
+      .map_err(|_| invalid_argument("max_length", "Positive number expected"))?;
+    let truncate = positional_or_keyword("truncate", args.truncate, args.truncate_keyword)?
+      .unwrap_or_else(|| "cut".into());
+    evaluate("helm_name", input, runtime, |s| {
+      let name = to_kebab_case(&to_ascii(s, ' '));
+      shorten_name(&name, max_length, &truncate)
+    })
+  }
+}
+
+/// The kebab case ASCII `name` shortened to `max_length` the way named by
+/// the `truncate:` argument.
+#[cfg(any(feature = "filter-helm", feature = "filter-gcp"))]
+fn shorten_name(name: &str, max_length: usize, truncate: &str) -> Result<String> {
+  if name.len() <= max_length {
+    return Ok(name.to_string());
+  }
+  let end = match truncate {
+    "cut" => max_length,
+    "words" => name[..=max_length].rfind('-').unwrap_or(max_length),
+    "hash" => return Ok(shorten_unique(name, max_length)),
+    other => return Err(unknown_value("truncate", other, &["cut", "words", "hash"])),
+  };
+  Ok(name[..end].trim_end_matches('-').to_string())
+}
+
+#[cfg(feature = "filter-aws")]
+define_target_filter! {
+  /// Make a string an AWS Lambda function name, at most 64 letters, digits,
+  /// hyphens and underscores: `{{ "Resize images (prod)" | lambda_name }}`
+  /// renders `Resize-images-prod`.
+  pub struct LambdaName;
+  name = "lambda_name",
+  description = "Make the string a valid AWS Lambda function name.",
+  convert = lambda_name,
+}
+
+#[cfg(feature = "filter-aws")]
+define_target_filter! {
+  /// Make a string an AWS IAM user, group or role name, at most 64 letters,
+  /// digits and `+=,.@_-`: `{{ "CI deploy: prod" | iam_name }}` renders
+  /// `CI-deploy-prod`.
+  pub struct IamName;
+  name = "iam_name",
+  description = "Make the string a valid AWS IAM user, group or role name.",
+  convert = iam_name,
+}
+
+#[cfg(feature = "filter-aws")]
+define_target_filter! {
+  /// Make a string an AWS CloudFormation logical ID, alphanumeric in
+  /// UpperCamelCase: `{{ "orders-table (v2)" | cfn_logical_id }}` renders
+  /// `OrdersTableV2`. An input without any letter or digit, even once
+  /// transliterated, fails the render.
+  pub struct CfnLogicalId;
+  name = "cfn_logical_id",
+  description = "Make the string a valid AWS CloudFormation logical ID.",
+  try_convert = cfn_logical_id,
+}
+
+/// The maximum length of the AWS Lambda function names and IAM names.
+const AWS_NAME_MAX_LEN: usize = 64;
+
+/// The maximum length of a CloudFormation logical ID.
+const CFN_LOGICAL_ID_MAX_LEN: usize = 255;
+
+fn lambda_name(s: &str) -> String {
+  let name = hyphenate(s, |c| c.is_ascii_alphanumeric() || c == '_');
+  truncate(&name, AWS_NAME_MAX_LEN)
+}
+
+fn iam_name(s: &str) -> String {
+  let name = hyphenate(s, |c| c.is_ascii_alphanumeric() || "+=,.@_".contains(c));
+  truncate(&name, AWS_NAME_MAX_LEN)
+}
+
+/// `s` transliterated to ASCII in UpperCamelCase, only letters and digits.
+fn cfn_logical_id(s: &str) -> Result<String> {
+  let mut id = to_upper_camel_case(&to_ascii(s, ' '));
+  id.truncate(CFN_LOGICAL_ID_MAX_LEN);
+  match id.is_empty() {
+    true => Err(empty_name(s)),
+    false => Ok(id),
+  }
+}
+
+/// The error of an input leaving an empty name, which its target rejects.
+fn empty_name(input: &str) -> Error {
+  Error::with_msg("No valid character in input").context("input", input.to_string())
+}
+
+#[cfg(feature = "filter-gcp")]
+#[derive(Debug, FilterParameters)]
+struct GcpNameArgs {
+  #[parameter(
+    description = "The maximum length, e.g. 63 for instances (the default) or 30 for service accounts.",
+    arg_type = "integer"
+  )]
+  max_length: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `max_length`.",
+    arg_type = "integer",
+    mode = "keyword",
+    rename = "max_length"
+  )]
+  max_length_keyword: Option<Expression>,
+  #[parameter(
+    description = "How a longer name is shortened: \"cut\" (the default) at the maximum length, \"words\" after the last whole word that fits, \"hash\" after a whole word too, followed by a hash of the name.",
+    arg_type = "str"
+  )]
+  truncate: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `truncate`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "truncate"
+  )]
+  truncate_keyword: Option<Expression>,
+}
+
+/// Make a string a Google Cloud resource name, a lowercase letter then
+/// lowercase letters, digits and hyphens, not ending with a hyphen:
+/// `{{ "2024 Web Frontend" | gcp_name: 12 }}` renders `web-frontend`. The
+/// maximum length defaults to 63; `truncate:` shortens the longer names as
+/// `helm_name` does, `"hash"` keeping distinct names distinct. An input
+/// without any letter, even once transliterated, fails the render.
+#[cfg(feature = "filter-gcp")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "gcp_name",
+  description = "Make the string a valid Google Cloud resource name.",
+  parameters(GcpNameArgs),
+  parsed(GcpNameFilter)
+)]
+pub struct GcpName;
+
+#[cfg(feature = "filter-gcp")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "gcp_name"]
+struct GcpNameFilter {
+  #[parameters]
+  args: GcpNameArgs,
+}
+
+#[cfg(feature = "filter-gcp")]
+impl Filter for GcpNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let max_length = positional_or_keyword("max_length", args.max_length, args.max_length_keyword)?;
+    let max_length = usize::try_from(max_length.unwrap_or(63))
+      .map_err(|_| invalid_argument("max_length", "Positive number expected"))?;
+    let truncate = positional_or_keyword("truncate", args.truncate, args.truncate_keyword)?
+      .unwrap_or_else(|| "cut".into());
+    evaluate("gcp_name", input, runtime, |s| {
+      // The name starts with a letter: the leading digits are dropped.
+      let name = to_kebab_case(&to_ascii(s, ' '));
+      let name = name.trim_start_matches(|c: char| !c.is_ascii_lowercase());
+      let name = shorten_name(name, max_length, &truncate)?;
+      match name.is_empty() {
+        true => Err(empty_name(s)),
+        false => Ok(name),
+      }
+    })
+  }
+}
+
+#[cfg(feature = "filter-safe-filename")]
+#[derive(Debug, FilterParameters)]
+struct SafeFilenameArgs {
+  #[parameter(
+    description = "The case filter converting the name before its extension, e.g. \"kebabcase\".",
+    arg_type = "str"
+  )]
+  case: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `case`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "case"
+  )]
+  case_keyword: Option<Expression>,
+  #[parameter(
+    description = "The maximum length in bytes. Defaults to 255, the limit of most file systems.",
+    arg_type = "integer"
+  )]
+  max_length: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `max_length`.",
+    arg_type = "integer",
+    mode = "keyword",
+    rename = "max_length"
+  )]
+  max_length_keyword: Option<Expression>,
+}
+
+/// Make a string a file name valid on Windows, macOS and Linux: without
+/// `<>:"/\|?*`, control and invisible characters (zero-width characters,
+/// bidi controls), trailing dots and spaces, and Windows device names (`CON`,
+/// `NUL`, `COM1`, ...) which get a `_` suffix, also before several
+/// extensions: `con_.tar.gz`. A name left empty becomes `_`.
+/// `{{ "Q3: Sales/Marketing?.pdf" | safe_filename }}` renders
+/// `Q3 SalesMarketing.pdf`. `case:` converts the name before the extension
+/// and `max_length:` changes the limit of 255 bytes, keeping the extension:
+/// `{{ "Q3: Sales Report.PDF" | safe_filename: case: "kebabcase" }}`
+/// renders `q3-sales-report.PDF`.
+#[cfg(feature = "filter-safe-filename")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "safe_filename",
+  description = "Make the string a file name valid on Windows, macOS and Linux.",
+  parameters(SafeFilenameArgs),
+  parsed(SafeFilenameFilter)
+)]
+pub struct SafeFilename;
+
+#[cfg(feature = "filter-safe-filename")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "safe_filename"]
+struct SafeFilenameFilter {
+  #[parameters]
+  args: SafeFilenameArgs,
+}
+
+#[cfg(feature = "filter-safe-filename")]
+impl Filter for SafeFilenameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let case = positional_or_keyword("case", args.case, args.case_keyword)?
+      .map(|case| case_argument("case", &case))
+      .transpose()?;
+    let max_length = positional_or_keyword("max_length", args.max_length, args.max_length_keyword)?;
+    let max_length = usize::try_from(max_length.unwrap_or(255))
+      .map_err(|_| invalid_argument("max_length", "Positive number expected"))?;
+    let options = runtime_options(runtime)?;
+    evaluate("safe_filename", input, runtime, |s| {
+      let name: String = s
+        .chars()
+        .filter(|&c| !c.is_control() && !is_invisible(c) && !"<>:\"/\\|?*".contains(c))
+        .collect();
+      let name = name.trim_start_matches(' ');
+      let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension))
+          if !stem.trim_end_matches(['.', ' ']).is_empty()
+            && !extension.is_empty()
+            && !extension.contains(' ') =>
+        {
+          (stem, Some(extension))
+        }
+        _ => (name, None),
+      };
+      let mut stem = match case {
+        Some(case) => conversion::convert_prepared(case, stem, &options),
+        None => stem.to_string(),
+      };
+      let extension = extension.map(|extension| format!(".{extension}"));
+      let extension = extension
+        .as_deref()
+        .filter(|extension| extension.len() < max_length)
+        .unwrap_or("");
+      truncate_chars(&mut stem, max_length - extension.len());
+      if extension.is_empty() {
+        stem.truncate(stem.trim_end_matches(['.', ' ']).len());
+      }
+      if stem.is_empty() {
+        stem.push('_');
+      }
+      let mut name = stem + extension;
+      if Reserved::Windows.is_reserved(&name) {
+        let mut end = name.find('.').unwrap_or(name.len());
+        if name.len() == max_length {
+          end -= name[..end].chars().next_back().map_or(0, char::len_utf8);
+          name.remove(end);
+        }
+        name.insert(end, '_');
+      }
+      Ok(name)
+    })
+  }
+}
+
+/// Truncate `s` to at most `max_len` bytes, at a character boundary, without
+/// trailing dots and spaces.
+fn truncate_chars(s: &mut String, max_len: usize) {
+  let mut end = s.len().min(max_len);
+  while !s.is_char_boundary(end) {
+    end -= 1;
+  }
+  s.truncate(s[..end].trim_end_matches(['.', ' ']).len());
+}
+
+/// Make a string a kebab-case slug, unique within the render: a repeated
+/// slug gets a `-2`, `-3`, ... suffix, as the static site generators
+/// disambiguate the duplicate headings. `{{ "Usage" | unique_slug }}`
+/// renders `usage` the first time, then `usage-2`.
+#[cfg(feature = "filter-unique-slug")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "unique_slug",
+  description = "Make the string a kebab-case slug, with a numeric suffix when already emitted in the render.",
+  parsed(UniqueSlugFilter)
+)]
+pub struct UniqueSlug;
+
+#[cfg(feature = "filter-unique-slug")]
+#[derive(Debug, Default, Display_filter)]
+#[name = "unique_slug"]
+struct UniqueSlugFilter;
+
+/// The register holding the slugs emitted by `unique_slug` in the render,
+/// with the last suffix used for each of them.
+#[derive(Debug, Default)]
+struct EmittedSlugs(HashMap<String, usize>);
+
+#[cfg(feature = "filter-unique-slug")]
+impl Filter for UniqueSlugFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let options = runtime_options(runtime)?;
+    evaluate("unique_slug", input, runtime, |s| {
+      let slug = conversion::convert(HeckCase::Kebab, s, &options);
+      let mut emitted = runtime.registers().get_mut::<EmittedSlugs>();
+      Ok(unique(&mut emitted.0, slug, 2))
+    })
+  }
+}
+
+/// `slug`, or `slug` with the first `-n` suffix not in `emitted`, counting
+/// from `first`, now recorded in `emitted`.
+fn unique(emitted: &mut HashMap<String, usize>, slug: String, first: usize) -> String {
+  let Some(last) = emitted.get(&slug).copied() else {
+    emitted.insert(slug.clone(), first - 1);
+    return slug;
+  };
+  let (n, unique) = (last + 1..)
+    .map(|n| (n, format!("{slug}-{n}")))
+    .find(|(_, unique)| !emitted.contains_key(unique))
+    .expect("a free suffix");
+  emitted.insert(slug, n);
+  emitted.insert(unique.clone(), first - 1);
+  unique
+}
+
+#[cfg(feature = "filter-anchor-mdbook")]
+#[derive(Debug, FilterParameters)]
+struct AnchorMdbookArgs {
+  #[parameter(
+    description = "Suffix the anchors already emitted in the render with `-1`, `-2`, ..., as mdBook does within a chapter. Defaults to true.",
+    arg_type = "bool"
+  )]
+  unique: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `unique`.",
+    arg_type = "bool",
+    mode = "keyword",
+    rename = "unique"
+  )]
+  unique_keyword: Option<Expression>,
+}
+
+/// Make a heading the anchor mdBook gives it: `{{ "Hello, World!" |
+/// anchor_mdbook }}` renders `hello-world`. As in mdBook, the HTML tags and
+/// entities are dropped, only the ASCII letters are lowercased and the
+/// other letters are kept, and a repeated anchor gets a `-1`, `-2`, ...
+/// suffix, unless `unique: false`.
+#[cfg(feature = "filter-anchor-mdbook")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "anchor_mdbook",
+  description = "Make the string the anchor mdBook gives to a heading.",
+  parameters(AnchorMdbookArgs),
+  parsed(AnchorMdbookFilter)
+)]
+pub struct AnchorMdbook;
+
+#[cfg(feature = "filter-anchor-mdbook")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "anchor_mdbook"]
+struct AnchorMdbookFilter {
+  #[parameters]
+  args: AnchorMdbookArgs,
+}
+
+/// The register counting the anchors emitted by `anchor_mdbook` in the
+/// render.
+#[derive(Debug, Default)]
+struct MdbookAnchors(HashMap<String, usize>);
+
+#[cfg(feature = "filter-anchor-mdbook")]
+impl Filter for AnchorMdbookFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let unique = positional_or_keyword("unique", args.unique, args.unique_keyword)?.unwrap_or(true);
+    evaluate("anchor_mdbook", input, runtime, |s| {
+      let anchor = mdbook_anchor(s);
+      if !unique {
+        return Ok(anchor);
+      }
+      let mut anchors = runtime.registers().get_mut::<MdbookAnchors>();
+      let count = anchors.0.entry(anchor.clone()).or_insert(0);
+      *count += 1;
+      Ok(match *count {
+        1 => anchor,
+        count => format!("{anchor}-{}", count - 1),
+      })
+    })
+  }
+}
+
+/// The HTML entities mdBook drops from the headings.
+const MDBOOK_ENTITIES: &[&str] = &["&lt;", "&gt;", "&amp;", "&#39;", "&quot;"];
+
+/// The anchor of the heading `s` as mdBook's `id_from_content` computes it.
+fn mdbook_anchor(s: &str) -> String {
+  let mut content = String::with_capacity(s.len());
+  let mut rest = s;
+  while let Some(start) = rest.find('<') {
+    content.push_str(&rest[..start]);
+    rest = match rest[start..].find('>') {
+      Some(end) => &rest[start + end + 1..],
+      None => {
+        content.push_str(&rest[start..]);
+        ""
+      }
+    };
+  }
+  content.push_str(rest);
+  for entity in MDBOOK_ENTITIES {
+    content = content.replace(entity, "");
+  }
+  content
+    .trim()
+    .trim_start_matches('#')
+    .trim()
+    .chars()
+    .filter_map(|c| match c {
+      c if c.is_alphanumeric() || c == '_' || c == '-' => Some(c.to_ascii_lowercase()),
+      c if c.is_whitespace() => Some('-'),
+      _ => None,
+    })
+    .collect()
+}
+
+#[cfg(feature = "filter-anchor-docusaurus")]
+#[derive(Debug, FilterParameters)]
+struct AnchorDocusaurusArgs {
+  #[parameter(
+    description = "Suffix the anchors already emitted in the render with `-1`, `-2`, ..., as Docusaurus does within a page. Defaults to true.",
+    arg_type = "bool"
+  )]
+  unique: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `unique`.",
+    arg_type = "bool",
+    mode = "keyword",
+    rename = "unique"
+  )]
+  unique_keyword: Option<Expression>,
+}
+
+/// Make a heading the anchor Docusaurus gives it, with the algorithm of
+/// github-slugger: `{{ "What's new? 🎉" | anchor_docusaurus }}` renders
+/// `whats-new-`. The string is lowercased, the punctuation and the emoji
+/// are dropped and each space becomes a hyphen. An explicit id,
+/// `## Intro {#custom-id}`, is kept as is. A repeated anchor gets a `-1`,
+/// `-2`, ... suffix, unless `unique: false`.
+#[cfg(feature = "filter-anchor-docusaurus")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "anchor_docusaurus",
+  description = "Make the string the anchor Docusaurus (github-slugger) gives to a heading.",
+  parameters(AnchorDocusaurusArgs),
+  parsed(AnchorDocusaurusFilter)
+)]
+pub struct AnchorDocusaurus;
+
+#[cfg(feature = "filter-anchor-docusaurus")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "anchor_docusaurus"]
+struct AnchorDocusaurusFilter {
+  #[parameters]
+  args: AnchorDocusaurusArgs,
+}
+
+/// The register holding the anchors emitted by `anchor_docusaurus` in the
+/// render, with the last suffix used for each of them.
+#[derive(Debug, Default)]
+struct DocusaurusAnchors(HashMap<String, usize>);
+
+#[cfg(feature = "filter-anchor-docusaurus")]
+impl Filter for AnchorDocusaurusFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let unique_anchor =
+      positional_or_keyword("unique", args.unique, args.unique_keyword)?.unwrap_or(true);
+    evaluate("anchor_docusaurus", input, runtime, |s| {
+      if let Some(id) = explicit_id(s) {
+        return Ok(id.to_string());
+      }
+      let anchor = github_slug(s);
+      if !unique_anchor {
+        return Ok(anchor);
+      }
+      let mut anchors = runtime.registers().get_mut::<DocusaurusAnchors>();
+      Ok(unique(&mut anchors.0, anchor, 1))
+    })
+  }
+}
+
+/// The id of a heading ending with `{#id}`.
+fn explicit_id(s: &str) -> Option<&str> {
+  let id = s.trim_end().strip_suffix('}')?;
+  let start = id.rfind("{#")?;
+  Some(&id[start + 2..]).filter(|id| !id.is_empty() && !id.contains(char::is_whitespace))
+}
+
+/// The slug of `s` as github-slugger computes it: lowercase, keeping the
+/// letters, digits, combining marks, connector punctuation and hyphens,
+/// each space replaced with a hyphen.
+fn github_slug(s: &str) -> String {
+  s.to_lowercase()
+    .chars()
+    .filter_map(|c| match c {
+      ' ' => Some('-'),
+      c if c.is_alphanumeric() || c == '-' || is_mark(c) || is_connector(c) => Some(c),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Whether `c` is a combining mark of the usual blocks.
+fn is_mark(c: char) -> bool {
+  matches!(
+    c,
+    '\u{0300}'..='\u{036f}'
+      | '\u{0483}'..='\u{0489}'
+      | '\u{0591}'..='\u{05bd}'
+      | '\u{064b}'..='\u{065f}'
+      | '\u{0900}'..='\u{0903}'
+      | '\u{093a}'..='\u{094f}'
+      | '\u{1ab0}'..='\u{1aff}'
+      | '\u{1dc0}'..='\u{1dff}'
+      | '\u{20d0}'..='\u{20ff}'
+      | '\u{3099}'..='\u{309a}'
+      | '\u{fe00}'..='\u{fe0f}'
+      | '\u{fe20}'..='\u{fe2f}'
+  )
+}
+
+/// Whether `c` is a connector punctuation (`_`, `‿`, ...).
+fn is_connector(c: char) -> bool {
+  matches!(
+    c,
+    '_' | '\u{203f}' | '\u{2040}' | '\u{2054}' | '\u{fe33}' | '\u{fe34}' | '\u{fe4d}'
+      ..='\u{fe4f}' | '\u{ff3f}'
+  )
+}
+
+#[cfg(feature = "filter-rst-label")]
+define_target_filter! {
+  /// Make a section title a Sphinx reference label, lowercase ASCII letters,
+  /// digits, `_`, `.` and hyphens, starting with a letter:
+  /// `{{ "2. Installing on Windows (x64)" | rst_label }}` renders
+  /// `installing-on-windows-x64`.
+  pub struct RstLabel;
+  name = "rst_label",
+  description = "Make the string a valid Sphinx reference label.",
+  convert = rst_label,
+}
+
+fn rst_label(s: &str) -> String {
+  let mut label = hyphenate(&s.to_lowercase(), |c| {
+    c.is_ascii_alphanumeric() || "_.".contains(c)
+  });
+  label.make_ascii_lowercase();
+  let start = label
+    .find(|c: char| c.is_ascii_lowercase())
+    .unwrap_or(label.len());
+  label.split_off(start)
+}
+
+#[cfg(feature = "filter-latex")]
+define_target_filter! {
+  /// Make a string a LaTeX `\label{...}` key, lowercase ASCII letters,
+  /// digits, `:` and hyphens: `{{ "sec:Results & Discussion" | latex_label }}`
+  /// renders `sec:results-discussion`.
+  pub struct LatexLabel;
+  name = "latex_label",
+  description = "Make the string a safe LaTeX label key.",
+  convert = latex_label,
+}
+
+#[cfg(feature = "filter-latex")]
+define_target_filter! {
+  /// Make a string a LaTeX command name, ASCII letters only in lowerCamelCase,
+  /// the digits spelled out: `{{ "project name 2" | latex_command }}` renders
+  /// `projectNameTwo`, for `\newcommand{\projectNameTwo}`.
+  pub struct LatexCommand;
+  name = "latex_command",
+  description = "Make the string a LaTeX command name of letters only.",
+  convert = latex_command,
+}
+
+fn latex_label(s: &str) -> String {
+  let label = hyphenate(s, |c| c.is_ascii_alphanumeric() || c == ':');
+  label
+    .to_ascii_lowercase()
+    .replace(":-", ":")
+    .replace("-:", ":")
+}
+
+/// The English names of the digits.
+const DIGIT_NAMES: [&str; 10] = [
+  "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn latex_command(s: &str) -> String {
+  let mut words = String::with_capacity(s.len());
+  for c in to_ascii(s, ' ').chars() {
+    match c.to_digit(10) {
+      Some(digit) => {
+        words.push(' ');
+        words.push_str(DIGIT_NAMES[digit as usize]);
+        words.push(' ');
+      }
+      None => words.push(c),
+    }
+  }
+  let mut command = to_lower_camel_case(&words);
+  command.retain(|c| c.is_ascii_alphabetic());
+  command
+}
+
+/// The conventions of a language or a format for the qualified names: the
+/// case of the namespace segments, their separator and the case of the
+/// name.
+#[cfg(feature = "filter-in-namespace")]
+const NAMESPACE_STYLES: &[(&str, HeckCase, &str, HeckCase)] = &[
+  ("proto", HeckCase::Snake, ".", HeckCase::UpperCamel),
+  ("java", HeckCase::Snake, ".", HeckCase::UpperCamel),
+  ("python", HeckCase::Snake, ".", HeckCase::UpperCamel),
+  ("rust", HeckCase::Snake, "::", HeckCase::UpperCamel),
+  ("cpp", HeckCase::Snake, "::", HeckCase::UpperCamel),
+  ("csharp", HeckCase::UpperCamel, ".", HeckCase::UpperCamel),
+  ("ruby", HeckCase::UpperCamel, "::", HeckCase::UpperCamel),
+  ("php", HeckCase::UpperCamel, "\\", HeckCase::UpperCamel),
+];
+
+#[cfg(feature = "filter-in-namespace")]
+#[derive(Debug, FilterParameters)]
+struct InNamespaceArgs {
+  #[parameter(
+    description = "The namespace, as an array of segments or a string separated by `.`, `::`, `/` or `\\`.",
+    arg_type = "any"
+  )]
+  namespace: Expression,
+  #[parameter(
+    description = "The target conventions: \"proto\", \"java\", \"python\", \"rust\", \"cpp\", \"csharp\", \"ruby\" or \"php\". Without it, the segments and the name are joined with `.` as written.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  style: Option<Expression>,
+}
+
+/// Qualify a name with a namespace, with the separator and the cases of a
+/// target: `{{ "OrderCreated" | in_namespace: "billing.events", style:
+/// "proto" }}` renders `billing.events.OrderCreated`, and
+/// `{{ "order created" | in_namespace: "Billing::Events", style: "rust" }}`
+/// `billing::events::OrderCreated`. The namespace is an array of segments
+/// or a string separated by `.`, `::`, `/` or `\`; without `style:`, the
+/// segments and the name are joined with `.` as written.
+#[cfg(feature = "filter-in-namespace")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "in_namespace",
+  description = "Qualify the string with a namespace, in the separator and the cases of a target.",
+  parameters(InNamespaceArgs),
+  parsed(InNamespaceFilter)
+)]
+pub struct InNamespace;
+
+#[cfg(feature = "filter-in-namespace")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "in_namespace"]
+struct InNamespaceFilter {
+  #[parameters]
+  args: InNamespaceArgs,
+}
+
+#[cfg(feature = "filter-in-namespace")]
+impl Filter for InNamespaceFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let style = match args.style {
+      Some(style) => {
+        let names: Vec<_> = NAMESPACE_STYLES.iter().map(|style| style.0).collect();
+        let style = NAMESPACE_STYLES
+          .iter()
+          .find(|(name, ..)| *name == style.as_str())
+          .ok_or_else(|| unknown_value("style", &style, &names))?;
+        Some(*style)
+      }
+      None => None,
+    };
+    let segments: Vec<String> = match args.namespace.as_array() {
+      Some(array) => array
+        .values()
+        .map(|segment| segment.to_kstr().into_string())
+        .collect(),
+      None => args
+        .namespace
+        .to_kstr()
+        .split(['.', ':', '/', '\\'])
+        .map(str::to_string)
+        .collect(),
+    };
+    let options = runtime_options(runtime)?;
+    evaluate("in_namespace", input, runtime, |s| {
+      let segments = segments.iter().filter(|segment| !segment.trim().is_empty());
+      let parts: Vec<_> = match style {
+        Some((_, segment_case, _, name_case)) => segments
+          .map(|segment| conversion::convert(segment_case, segment, &options))
+          .chain([conversion::convert(name_case, s, &options)])
+          .collect(),
+        None => segments.cloned().chain([s.to_string()]).collect(),
+      };
+      Ok(parts.join(style.map_or(".", |style| style.2)))
+    })
+  }
+}
+
+/// The maximum length of a Kafka topic name.
+#[cfg(feature = "filter-event-name")]
+const KAFKA_TOPIC_MAX_LEN: usize = 249;
+
+#[cfg(feature = "filter-event-name")]
+#[derive(Debug, FilterParameters)]
+struct EventNameArgs {
+  #[parameter(
+    description = "The case of the segments: \"kebab\" (the default) or \"snake\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  case: Option<Expression>,
+}
+
+/// Make a string an event or a Kafka topic name, lowercase segments
+/// separated by dots, of letters, digits and hyphens:
+/// `{{ "Billing.Order Created.v2" | event_name }}` renders
+/// `billing.order-created.v2`. `case: "snake"` separates the words with
+/// underscores instead, which Kafka metrics confuse with dots. The empty
+/// segments are dropped, and the names longer than the 249 characters of
+/// Kafka are shortened with a hash, keeping distinct names distinct. The
+/// characters kept by the `keep_punctuation` option are dropped too.
+#[cfg(feature = "filter-event-name")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "event_name",
+  description = "Make the string a valid event or Kafka topic name.",
+  parameters(EventNameArgs),
+  parsed(EventNameFilter)
+)]
+pub struct EventName;
+
+#[cfg(feature = "filter-event-name")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "event_name"]
+struct EventNameFilter {
+  #[parameters]
+  args: EventNameArgs,
+}
+
+#[cfg(feature = "filter-event-name")]
+impl Filter for EventNameFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let case = match args.case.as_deref() {
+      None | Some("kebab") => HeckCase::Kebab,
+      Some("snake") => HeckCase::Snake,
+      Some(other) => return Err(unknown_value("case", other, &["kebab", "snake"])),
+    };
+    let options = runtime_options(runtime)?;
+    evaluate("event_name", input, runtime, |s| {
+      let segments: Vec<_> = s
+        .split('.')
+        .map(|segment| conversion::convert(case, &to_ascii(segment, ' '), &options))
+        .map(|mut segment| {
+          segment.retain(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+          segment.to_ascii_lowercase()
+        })
+        .filter(|segment| !segment.is_empty())
+        .collect();
+      Ok(shorten_unique(&segments.join("."), KAFKA_TOPIC_MAX_LEN))
+    })
+  }
+}
+
+/// `s` transliterated to ASCII in snake_case, the characters of the
+/// Prometheus metric and label names.
+#[cfg(feature = "filter-prometheus")]
+fn prom_name(s: &str, options: &Options) -> String {
+  let mut name = conversion::convert(HeckCase::Snake, &to_ascii(s, ' '), options);
+  name.retain(|c| c.is_ascii_alphanumeric() || c == '_');
+  name
+}
+
+#[cfg(feature = "filter-prometheus")]
+#[derive(Debug, FilterParameters)]
+struct PromMetricArgs {
+  #[parameter(
+    description = "The base unit suffixed to the name, e.g. \"seconds\" or \"bytes\".",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  unit: Option<Expression>,
+  #[parameter(
+    description = "Suffix the name of a counter with `_total`.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  total: Option<Expression>,
+}
+
+/// Make a string a Prometheus metric name, in snake_case:
+/// `{{ "HTTP request duration" | prom_metric: unit: "seconds" }}` renders
+/// `http_request_duration_seconds`, and `{{ "http requests" | prom_metric:
+/// total: true }}` `http_requests_total`. The unit and `_total` aren't
+/// repeated, the unit coming before `_total`. The colons of the recording
+/// rules are kept; a leading digit takes an underscore before it.
+#[cfg(feature = "filter-prometheus")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "prom_metric",
+  description = "Make the string a valid Prometheus metric name.",
+  parameters(PromMetricArgs),
+  parsed(PromMetricFilter)
+)]
+pub struct PromMetric;
+
+#[cfg(feature = "filter-prometheus")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "prom_metric"]
+struct PromMetricFilter {
+  #[parameters]
+  args: PromMetricArgs,
+}
+
+#[cfg(feature = "filter-prometheus")]
+impl Filter for PromMetricFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let total = args.total.unwrap_or(false);
+    let options = runtime_options(runtime)?;
+    let unit = args.unit.map(|unit| prom_name(&unit, &options));
+    evaluate("prom_metric", input, runtime, |s| {
+      let parts: Vec<_> = s.split(':').map(|part| prom_name(part, &options)).collect();
+      let mut name = parts.join(":");
+      if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+      }
+      let counter = match name.strip_suffix("_total") {
+        Some(stem) => {
+          name.truncate(stem.len());
+          true
+        }
+        None => total,
+      };
+      if let Some(unit) = unit.as_deref().filter(|unit| !unit.is_empty()) {
+        if name != unit && !name.ends_with(&format!("_{unit}")) {
+          name.push('_');
+          name.push_str(unit);
+        }
+      }
+      if counter {
+        name.push_str("_total");
+      }
+      Ok(name)
+    })
+  }
+}
+
+#[cfg(feature = "filter-prometheus")]
+define_target_filter! {
+  /// Make a string a Prometheus label name, in snake_case:
+  /// `{{ "Status Code" | prom_label }}` renders `status_code`. The leading
+  /// underscores, whose `__` prefix Prometheus reserves, are dropped; a
+  /// leading digit takes an underscore before it.
+  pub struct PromLabel;
+  name = "prom_label",
+  description = "Make the string a valid Prometheus label name.",
+  convert = prom_label,
+}
+
+#[cfg(feature = "filter-prometheus")]
+fn prom_label(s: &str) -> String {
+  let name = prom_name(s, &Options::default());
+  match name.trim_start_matches('_') {
+    label if label.starts_with(|c: char| c.is_ascii_digit()) => format!("_{label}"),
+    label => label.to_string(),
+  }
+}
+
+#[cfg(feature = "filter-canonical-header")]
+define_target_filter! {
+  /// Canonicalize an HTTP header name as Go's `CanonicalMIMEHeaderKey`
+  /// does, the first letter and the letters after a hyphen in uppercase,
+  /// the others in lowercase: `{{ "content-TYPE" | canonical_header }}`
+  /// renders `Content-Type`. A name with a space or another character
+  /// invalid in a header is left as is, as Go does: convert it with
+  /// `traincase` first.
+  ///
+  /// Unlike Go, the headers spelled otherwise by their specification keep
+  /// that spelling, whatever the case of the input: `Content-ID`,
+  /// `Content-MD5`, `DNT`, `ETag`, `Sec-WebSocket-Accept`,
+  /// `Sec-WebSocket-Extensions`, `Sec-WebSocket-Key`,
+  /// `Sec-WebSocket-Protocol`, `Sec-WebSocket-Version`, `TE`,
+  /// `WWW-Authenticate`, `X-ATT-DeviceId`, `X-DNS-Prefetch-Control`,
+  /// `X-UA-Compatible` and `X-XSS-Protection`. `etag` gives `ETag` where Go
+  /// gives `Etag`.
+  pub struct CanonicalHeader;
+  name = "canonical_header",
+  description = "Canonicalize the string as an HTTP header name.",
+  convert = canonical_header,
+}
+
+/// The header names not in the canonical form of Go, in their usual
+/// spelling. The doc of [`CanonicalHeader`] lists them.
+#[cfg(feature = "filter-canonical-header")]
+const HEADER_SPELLINGS: &[&str] = &[
+  "Content-ID",
+  "Content-MD5",
+  "DNT",
+  "ETag",
+  "Sec-WebSocket-Accept",
+  "Sec-WebSocket-Extensions",
+  "Sec-WebSocket-Key",
+  "Sec-WebSocket-Protocol",
+  "Sec-WebSocket-Version",
+  "TE",
+  "WWW-Authenticate",
+  "X-ATT-DeviceId",
+  "X-DNS-Prefetch-Control",
+  "X-UA-Compatible",
+  "X-XSS-Protection",
+];
+
+#[cfg(feature = "filter-canonical-header")]
+fn canonical_header(s: &str) -> String {
+  let is_token = |b: u8| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b);
+  if s.is_empty() || !s.bytes().all(is_token) {
+    return s.to_string();
+  }
+  if let Some(spelling) = HEADER_SPELLINGS
+    .iter()
+    .find(|name| name.eq_ignore_ascii_case(s))
+  {
+    return spelling.to_string();
+  }
+  let mut upper = true;
+  s.chars()
+    .map(|c| {
+      let c = match upper {
+        true => c.to_ascii_uppercase(),
+        false => c.to_ascii_lowercase(),
+      };
+      upper = c == '-';
+      c
+    })
+    .collect()
+}
+
+/// The English words left out of the path of the i18n keys.
+#[cfg(feature = "filter-i18n-key")]
+const I18N_STOP_WORDS: &[&str] = &[
+  "a", "an", "and", "are", "as", "at", "be", "been", "by", "can", "could", "for", "from", "had",
+  "has", "have", "her", "his", "i", "in", "is", "it", "its", "me", "my", "of", "on", "or", "our",
+  "please", "that", "the", "their", "this", "to", "us", "was", "we", "were", "will", "with", "you",
+  "your",
+];
+
+#[cfg(feature = "filter-i18n-key")]
+#[derive(Debug, FilterParameters)]
+struct I18nKeyArgs {
+  #[parameter(
+    description = "The namespace of the key, its segments separated by dots.",
+    arg_type = "str",
+    mode = "keyword"
+  )]
+  prefix: Option<Expression>,
+  #[parameter(
+    description = "The maximum length of the key. Defaults to 64.",
+    arg_type = "integer",
+    mode = "keyword"
+  )]
+  max_length: Option<Expression>,
+  #[parameter(
+    description = "End the key with a hash of the sentence, so that the sentences of the same words stay distinct.",
+    arg_type = "bool",
+    mode = "keyword"
+  )]
+  hash: Option<Expression>,
+}
+
+/// Make an English sentence the key of its translations, lowercase and
+/// dotted: the first two significant words, then the whole sentence in
+/// snake_case. `{{ "Your order has shipped!" | i18n_key }}` renders
+/// `order.shipped.your_order_has_shipped`, and with `prefix: "checkout"`
+/// `checkout.order.shipped.your_order_has_shipped`. The keys longer than
+/// `max_length:`, 64 by default, are shortened with a hash, keeping
+/// distinct keys distinct; `hash: true` always ends the key with a hash of
+/// the sentence. The `keep_punctuation` option is ignored: the keys only
+/// hold lowercase letters, digits, underscores and dots.
+#[cfg(feature = "filter-i18n-key")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "i18n_key",
+  description = "Make an English sentence a dotted translation key.",
+  parameters(I18nKeyArgs),
+  parsed(I18nKeyFilter)
+)]
+pub struct I18nKey;
+
+#[cfg(feature = "filter-i18n-key")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "i18n_key"]
+struct I18nKeyFilter {
+  #[parameters]
+  args: I18nKeyArgs,
+}
+
+#[cfg(feature = "filter-i18n-key")]
+impl Filter for I18nKeyFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    let args = self.args.evaluate(runtime)?;
+    let max_length = usize::try_from(args.max_length.unwrap_or(64))
+      .map_err(|_| invalid_argument("max_length", "Positive number expected"))?;
+    let hash = args.hash.unwrap_or(false);
+    let options = Options {
+      keep_punctuation: String::new(),
+      ..runtime_options(runtime)?
+    };
+    let snake = |s: &str| conversion::convert(HeckCase::Snake, &to_ascii(s, ' '), &options);
+    let mut segments: Vec<_> = args
+      .prefix
+      .as_deref()
+      .unwrap_or("")
+      .split('.')
+      .map(snake)
+      .filter(|segment| !segment.is_empty())
+      .collect();
+    evaluate("i18n_key", input, runtime, |s| {
+      let sentence = snake(s).to_ascii_lowercase();
+      segments.extend(
+        sentence
+          .split('_')
+          .filter(|word| !word.is_empty() && !I18N_STOP_WORDS.contains(word))
+          .take(2)
+          .map(str::to_string),
+      );
+      segments.push(sentence);
+      segments.retain(|segment| !segment.is_empty());
+      let key = segments.join(".");
+      Ok(match hash {
+        true => {
+          let hash = format!("{:08x}", stable_hash(s) as u32);
+          let key = shorten(
+            &key,
+            max_length.saturating_sub(hash.len() + 1),
+            Strategy::Truncate,
+          );
+          match key.is_empty() {
+            true => hash[..max_length.min(hash.len())].to_string(),
+            false => format!("{key}_{hash}"),
+          }
+        }
+        false => shorten_unique(&key, max_length),
+      })
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  #[cfg(feature = "filter-email-local")]
+  fn email_local() {
+    assert_eq!(
+      liquid_core::call_filter!(EmailLocal, "María-José O'Neill").unwrap(),
+      liquid_core::value!("maria.jose.oneill")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(EmailLocal, "  Dr. Jürgen  Groß (Ops) ").unwrap(),
+      liquid_core::value!("dr.jurgen.gross.ops")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(EmailLocal, "Иван").unwrap(),
+      liquid_core::value!("")
+    );
+    let long = "a".repeat(40) + " " + &"b".repeat(30);
+    assert_eq!(
+      liquid_core::call_filter!(EmailLocal, long).unwrap(),
+      liquid_core::value!("a".repeat(40))
+    );
+    assert!(EmailLocal.keyword_parameters().is_empty());
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(EmailLocal)
+      .build()
+      .unwrap();
+    for template in [
+      "{{ name | email_local: words: 1 }}",
+      "{{ name | email_local: limit: 10 }}",
+      "{{ name | email_local: each_line: true }}",
+      "{{ name | email_local: 1 }}",
+    ] {
+      assert!(parser.parse(template).is_err(), "{template}");
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "filter-git-ref")]
+  fn git_ref() {
+    assert_eq!(
+      liquid_core::call_filter!(GitRef, "feature/PROJ-42 Fix the login!").unwrap(),
+      liquid_core::value!("feature/proj-42-fix-the-login")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(GitRef, "/.hidden//a..b~c^d:e?f*g[h@{i}/x.lock/").unwrap(),
+      liquid_core::value!("hidden/a-b-c-d-e-f-g-h-i/x-lock")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(GitRef, "@").unwrap(),
+      liquid_core::value!("")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-docker")]
+  fn docker() {
+    assert_eq!(
+      liquid_core::call_filter!(DockerRepo, "ACME Corp//Web_App (Beta)/").unwrap(),
+      liquid_core::value!("acme-corp/web-app-beta")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(DockerRepo, "Société/Café").unwrap(),
+      liquid_core::value!("societe/cafe")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(DockerTag, "feature/Login Fix v1.2").unwrap(),
+      liquid_core::value!("feature-Login-Fix-v1.2")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(DockerTag, "..-été_2024!").unwrap(),
+      liquid_core::value!("ete_2024")
+    );
+    let long = "x".repeat(200);
+    assert_eq!(
+      liquid_core::call_filter!(DockerTag, long).unwrap(),
+      liquid_core::value!("x".repeat(128))
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-pypi")]
+  fn pypi() {
+    assert_eq!(
+      liquid_core::call_filter!(PypiName, "My_Cool..Package").unwrap(),
+      liquid_core::value!("my-cool-package")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(PypiName, " -Zoë's Tools_ ").unwrap(),
+      liquid_core::value!("zoe-s-tools")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(PyDistToModule, "My-Cool.Package").unwrap(),
+      liquid_core::value!("my_cool_package")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(PyDistToModule, "3d-tools").unwrap(),
+      liquid_core::value!("_3d_tools")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-maven")]
+  fn maven() {
+    assert_eq!(
+      liquid_core::call_filter!(MavenArtifact, "My Cool Lib").unwrap(),
+      liquid_core::value!("my-cool-lib")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(MavenArtifact)
+      .build()
+      .unwrap()
+      .parse("{{ 'Café Utils' | maven_artifact: flat: true }}")
+      .unwrap();
+    assert_eq!(template.render(&liquid::object!({})).unwrap(), "cafeutils");
+    assert_eq!(
+      liquid_core::call_filter!(MavenGroup, "com.Acme-Corp.2fa.int").unwrap(),
+      liquid_core::value!("com.acme_corp._2fa.int_")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(MavenGroup, ".org..Société Générale. ").unwrap(),
+      liquid_core::value!("org.societe_generale")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-helm")]
+  fn helm_name() {
+    assert_eq!(
+      liquid_core::call_filter!(HelmName, "Payments API (EU)").unwrap(),
+      liquid_core::value!("payments-api-eu")
+    );
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(HelmName)
+      .build()
+      .unwrap();
+    let render = |args: &str| {
+      parser
+        .parse(&format!(
+          "{{{{ 'Customer Billing Service Worker' | helm_name: {args} }}}}"
+        ))?
+        .render(&liquid::object!({}))
+    };
+    assert_eq!(render("max_length: 20").unwrap(), "customer-billing-ser");
+    assert_eq!(
+      render("max_length: 20, truncate: 'words'").unwrap(),
+      "customer-billing"
+    );
+    let hashed = render("max_length: 20, truncate: 'hash'").unwrap();
+    assert_eq!(hashed.len(), 17);
+    assert!(hashed.starts_with("customer-"));
+    let hashed = render("max_length: 26, truncate: 'hash'").unwrap();
+    assert!(hashed.starts_with("customer-billing-"), "{hashed}");
+    assert_eq!(render("max_length: 6, truncate: 'hash'").unwrap().len(), 6);
+    assert!(render("truncate: 'middle'").is_ok());
+    assert!(render("max_length: 3, truncate: 'middle'").is_err());
+    assert!(render("max_length: -1").is_err());
+    assert_eq!(render("20, 'words'").unwrap(), "customer-billing");
+    assert_eq!(render("20, truncate: 'words'").unwrap(), "customer-billing");
+    assert!(render("20, max_length: 20").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-aws")]
+  fn aws() {
+    assert_eq!(
+      liquid_core::call_filter!(LambdaName, "Resize images (prod)").unwrap(),
+      liquid_core::value!("Resize-images-prod")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LambdaName, "a".repeat(70)).unwrap(),
+      liquid_core::value!("a".repeat(64))
+    );
+    assert_eq!(
+      liquid_core::call_filter!(IamName, "CI deploy: prod/eu+us@team").unwrap(),
+      liquid_core::value!("CI-deploy-prod-eu+us@team")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(CfnLogicalId, "orders-table (v2) für Zoë").unwrap(),
+      liquid_core::value!("OrdersTableV2FurZoe")
+    );
+    for name in ["!?-", "日本語", " "] {
+      assert!(
+        liquid_core::call_filter!(CfnLogicalId, name).is_err(),
+        "{name:?}"
+      );
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "filter-gcp")]
+  fn gcp_name() {
+    assert_eq!(
+      liquid_core::call_filter!(GcpName, "2024 Web Frontend").unwrap(),
+      liquid_core::value!("web-frontend")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(GcpName, "Billing Export Bucket", 15).unwrap(),
+      liquid_core::value!("billing-export")
+    );
+    for name in ["42", "!?-", "日本語", ""] {
+      assert!(
+        liquid_core::call_filter!(GcpName, name).is_err(),
+        "{name:?}"
+      );
+    }
+    assert!(liquid_core::call_filter!(GcpName, "x", 0).is_err());
+    assert!(liquid_core::call_filter!(GcpName, "x", -1).is_err());
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(GcpName)
+      .build()
+      .unwrap()
+      .parse("{{ 'Billing Export Bucket' | gcp_name: max_length: 15 }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "billing-export"
+    );
+    let short = |name: &str| {
+      liquid_core::call_filter!(GcpName, name, 20, "hash")
+        .unwrap()
+        .into_scalar()
+        .unwrap()
+        .into_string()
+    };
+    let export = short("Billing Export Bucket Europe");
+    assert!(export.starts_with("billing-"), "{export}");
+    assert!(export.len() <= 20);
+    assert_ne!(export, short("Billing Export Bucket Asia"));
+    assert!(liquid_core::call_filter!(GcpName, "Billing Export", 3, "middle").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-safe-filename")]
+  fn safe_filename() {
+    assert_eq!(
+      liquid_core::call_filter!(SafeFilename, "Q3: Sales/Marketing?.pdf").unwrap(),
+      liquid_core::value!("Q3 SalesMarketing.pdf")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SafeFilename, " notes... ").unwrap(),
+      liquid_core::value!("notes")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SafeFilename, "con.txt").unwrap(),
+      liquid_core::value!("con_.txt")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SafeFilename, "LPT1").unwrap(),
+      liquid_core::value!("LPT1_")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(SafeFilename, "Console.log").unwrap(),
+      liquid_core::value!("Console.log")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SafeFilename)
+      .build()
+      .unwrap()
+      .parse(concat!(
+        "{{ 'Q3: Sales Report.PDF' | safe_filename: case: 'kebabcase' }} ",
+        "{{ 'Überschrift sehr lang.md' | safe_filename: max_length: 12 }} ",
+        "{{ 'Conference.md' | safe_filename: max_length: 6 }}"
+      ))
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "q3-sales-report.PDF Überschr.md Co_.md"
+    );
+    assert!(liquid_core::call_filter!(SafeFilename, "x", -1).is_err());
+
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SafeFilename)
+      .build()
+      .unwrap()
+      .parse("{{ name | safe_filename }}")
+      .unwrap();
+    let name = "invoice\u{202E}fdp.exe\u{200B}";
+    assert_eq!(
+      template.render(&liquid::object!({ "name": name })).unwrap(),
+      "invoicefdp.exe"
+    );
+    for (name, output) in [
+      ("???", "_"),
+      ("...", "_"),
+      ("\u{200B}", "_"),
+      ("con.tar.gz", "con_.tar.gz"),
+      ("nul.", "nul_"),
+    ] {
+      assert_eq!(
+        template.render(&liquid::object!({ "name": name })).unwrap(),
+        output,
+        "{name:?}"
+      );
+    }
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(SafeFilename)
+      .build()
+      .unwrap()
+      .parse("{{ '!!!.pdf' | safe_filename: case: 'snakecase' }}")
+      .unwrap();
+    assert_eq!(template.render(&liquid::object!({})).unwrap(), "_.pdf");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-unique-slug")]
+  fn unique_slug() {
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(UniqueSlug)
+      .build()
+      .unwrap()
+      .parse(concat!(
+        "{% for title in titles %}{{ title | unique_slug }} {% endfor %}",
+        "{{ nil | unique_slug }}"
+      ))
+      .unwrap();
+    let globals = liquid::object!({
+      "titles": ["Usage", "Usage 2", "usage", "Usage", "Install"],
+    });
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "usage usage-2 usage-3 usage-4 install "
+    );
+    assert_eq!(
+      template.render(&globals).unwrap(),
+      "usage usage-2 usage-3 usage-4 install ",
+      "the slugs are unique within a render"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-anchor-mdbook")]
+  fn anchor_mdbook() {
+    assert_eq!(
+      liquid_core::call_filter!(AnchorMdbook, "## Hello, World!").unwrap(),
+      liquid_core::value!("hello-world")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(AnchorMdbook, "The <code>Vec&lt;T&gt;</code> type").unwrap(),
+      liquid_core::value!("the-vect-type")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(AnchorMdbook, "Über  Ñandú_2 — ok").unwrap(),
+      liquid_core::value!("Über--Ñandú_2--ok")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(AnchorMdbook)
+      .build()
+      .unwrap()
+      .parse(concat!(
+        "{{ 'Usage' | anchor_mdbook }} {{ 'Usage' | anchor_mdbook }} ",
+        "{{ 'usage' | anchor_mdbook }} {{ 'Usage' | anchor_mdbook: unique: false }}"
+      ))
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "usage usage-1 usage-2 usage"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-anchor-docusaurus")]
+  fn anchor_docusaurus() {
+    assert_eq!(
+      liquid_core::call_filter!(AnchorDocusaurus, "What's new? 🎉").unwrap(),
+      liquid_core::value!("whats-new-")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(AnchorDocusaurus, "Über  C++ & Rust_2").unwrap(),
+      liquid_core::value!("über--c--rust_2")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(AnchorDocusaurus, "Intro {#getting-started}").unwrap(),
+      liquid_core::value!("getting-started")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(AnchorDocusaurus)
+      .build()
+      .unwrap()
+      .parse(concat!(
+        "{{ 'Usage 1' | anchor_docusaurus }} {{ 'Usage' | anchor_docusaurus }} ",
+        "{{ 'Usage' | anchor_docusaurus }} {{ 'Usage' | anchor_docusaurus }} ",
+        "{{ 'Usage' | anchor_docusaurus: unique: false }}"
+      ))
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "usage-1 usage usage-2 usage-3 usage"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-rst-label")]
+  fn rst_label() {
+    assert_eq!(
+      liquid_core::call_filter!(RstLabel, "2. Installing on Windows (x64)").unwrap(),
+      liquid_core::value!("installing-on-windows-x64")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(RstLabel, "Ärger mit config.yaml: __init__").unwrap(),
+      liquid_core::value!("arger-mit-config.yaml-__init__")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(RstLabel, "42").unwrap(),
+      liquid_core::value!("")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-latex")]
+  fn latex() {
+    assert_eq!(
+      liquid_core::call_filter!(LatexLabel, "sec:Results & Discussion").unwrap(),
+      liquid_core::value!("sec:results-discussion")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LatexLabel, "fig: {Über} 100%").unwrap(),
+      liquid_core::value!("fig:uber-100")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LatexCommand, "project name 2").unwrap(),
+      liquid_core::value!("projectNameTwo")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(LatexCommand, "Version 1.10 (beta)").unwrap(),
+      liquid_core::value!("versionOneOneZeroBeta")
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-in-namespace")]
+  fn in_namespace() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(InNamespace)
+      .build()
+      .unwrap();
+    let render = |template: &str| {
+      parser
+        .parse(template)?
+        .render(&liquid::object!({ "ns": ["Billing", "Order Events"] }))
+    };
+    assert_eq!(
+      render("{{ 'OrderCreated' | in_namespace: 'billing.events', style: 'proto' }}").unwrap(),
+      "billing.events.OrderCreated"
+    );
+    assert_eq!(
+      render("{{ 'order created' | in_namespace: 'Billing::Events', style: 'rust' }}").unwrap(),
+      "billing::events::OrderCreated"
+    );
+    assert_eq!(
+      render("{{ 'order_created' | in_namespace: ns, style: 'php' }}").unwrap(),
+      "Billing\\OrderEvents\\OrderCreated"
+    );
+    assert_eq!(
+      render("{{ 'order_created' | in_namespace: ns, style: 'csharp' }}").unwrap(),
+      "Billing.OrderEvents.OrderCreated"
+    );
+    assert_eq!(
+      render("{{ 'Order' | in_namespace: 'billing/v1' }}").unwrap(),
+      "billing.v1.Order"
+    );
+    assert_eq!(
+      render("{{ 'Order' | in_namespace: '', style: 'java' }}").unwrap(),
+      "Order"
+    );
+    assert!(render("{{ 'Order' | in_namespace: 'billing', style: 'cobol' }}").is_err());
+    assert!(render("{{ 'Order' | in_namespace }}").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-event-name")]
+  fn event_name() {
+    assert_eq!(
+      liquid_core::call_filter!(EventName, "Billing.Order Created.v2").unwrap(),
+      liquid_core::value!("billing.order-created.v2")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(EventName, "billing..OrderCreated!").unwrap(),
+      liquid_core::value!("billing.order-created")
+    );
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(EventName)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'Billing.Order Créée' | event_name: case: 'snake' }}").unwrap(),
+      "billing.order_creee"
+    );
+    assert!(render("{{ 'billing' | event_name: case: 'camel' }}").is_err());
+    let template = parser
+      .parse("{{ 'C++ Events.Orders @Home' | event_name }}")
+      .unwrap();
+    let globals = liquid::object!({ "heck_config": { "keep_punctuation": "+@" } });
+    assert_eq!(template.render(&globals).unwrap(), "c-events.orders-home");
+    let long = liquid_core::call_filter!(EventName, "segment.".repeat(40)).unwrap();
+    let long = long.to_kstr();
+    assert!(long.len() <= 249);
+    assert!(long.starts_with("segment.segment."), "{long}");
+  }
+
+  #[test]
+  #[cfg(feature = "filter-prometheus")]
+  fn prometheus() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(PromMetric)
+      .filter(PromLabel)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'HTTP request duration' | prom_metric: unit: 'seconds' }}").unwrap(),
+      "http_request_duration_seconds"
+    );
+    assert_eq!(
+      render("{{ 'http requests' | prom_metric: total: true }}").unwrap(),
+      "http_requests_total"
+    );
+    assert_eq!(
+      render("{{ 'bytesSent_total' | prom_metric: unit: 'bytes' }}").unwrap(),
+      "bytes_sent_bytes_total"
+    );
+    assert_eq!(
+      render("{{ 'response size bytes' | prom_metric: unit: 'bytes', total: true }}").unwrap(),
+      "response_size_bytes_total"
+    );
+    assert_eq!(
+      render("{{ 'job:HTTP Requests:rate5m' | prom_metric }}").unwrap(),
+      "job:http_requests:rate5m"
+    );
+    assert_eq!(
+      render("{{ '5xx errors' | prom_metric }}").unwrap(),
+      "_5xx_errors"
+    );
+    assert_eq!(
+      render("{{ 'Status Code' | prom_label }} {{ '__name' | prom_label }}").unwrap(),
+      "status_code name"
+    );
+    assert_eq!(
+      render("{{ '2nd-zone' | prom_label }}").unwrap(),
+      "_2nd_zone"
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "filter-canonical-header")]
+  fn canonical_header() {
+    for (name, canonical) in [
+      ("content-TYPE", "Content-Type"),
+      ("x-request-id", "X-Request-Id"),
+      ("etag", "ETag"),
+      ("www-authenticate", "WWW-Authenticate"),
+      ("sec-websocket-key", "Sec-WebSocket-Key"),
+      ("dnt", "DNT"),
+      ("accept_encoding", "Accept_encoding"),
+      ("content type", "content type"),
+      ("", ""),
+    ] {
+      assert_eq!(
+        liquid_core::call_filter!(CanonicalHeader, name).unwrap(),
+        liquid_core::value!(canonical)
+      );
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "filter-i18n-key")]
+  fn i18n_key() {
+    let parser = liquid::ParserBuilder::with_stdlib()
+      .filter(I18nKey)
+      .build()
+      .unwrap();
+    let render = |template: &str| parser.parse(template)?.render(&liquid::object!({}));
+    assert_eq!(
+      render("{{ 'Your order has shipped!' | i18n_key }}").unwrap(),
+      "order.shipped.your_order_has_shipped"
+    );
+    assert_eq!(
+      render("{{ 'Your order has shipped!' | i18n_key: prefix: 'Checkout.Emails' }}").unwrap(),
+      "checkout.emails.order.shipped.your_order_has_shipped"
+    );
+    assert_eq!(
+      render("{{ 'Welcome' | i18n_key }}").unwrap(),
+      "welcome.welcome"
+    );
+    let hashed = render("{{ 'Your order has shipped!' | i18n_key: hash: true }}").unwrap();
+    assert!(
+      hashed.starts_with("order.shipped.your_order_has_shipped_"),
+      "{hashed}"
+    );
+    assert_ne!(
+      hashed,
+      render("{{ 'Your order has shipped.' | i18n_key: hash: true }}").unwrap()
+    );
+    let long = render(
+      "{{ 'We could not process your payment because the card issuer declined it' | i18n_key: max_length: 40 }}",
+    )
+    .unwrap();
+    assert!(long.len() <= 40, "{long}");
+    assert!(long.starts_with("not.process."), "{long}");
+    assert!(render("{{ 'x' | i18n_key: max_length: -1 }}").is_err());
+    let template = parser
+      .parse("{{ 'C++ is #1!' | i18n_key: prefix: 'Docs+' }}")
+      .unwrap();
+    let globals = liquid::object!({ "heck_config": { "keep_punctuation": "+#!" } });
+    assert_eq!(template.render(&globals).unwrap(), "docs.c.1.c_is_1");
+  }
+}
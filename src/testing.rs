@@ -0,0 +1,63 @@
+//! Test helpers for crates wrapping or configuring the filters. Only
+//! available with the `testing` feature.
+use crate::filters::evaluate_filter;
+use liquid_core::{ParseFilter, ValueView};
+
+/// Assert that `filter` converts each input of `cases` to its output.
+///
+/// Mismatches are reported with a diff.
+///
+/// ```
+/// use liquid_heck::{testing::assert_conversions, SnakeCase};
+///
+/// assert_conversions(&SnakeCase, &[("HelloWorld", "hello_world"), ("a b", "a_b")]);
+/// ```
+#[track_caller]
+pub fn assert_conversions(filter: &dyn ParseFilter, cases: &[(&str, &str)]) {
+  let name = filter.reflection().name();
+  for (input, expected) in cases {
+    let output = evaluate_filter(filter, input)
+      .unwrap_or_else(|error| panic!("{name}: {input:?} failed: {error}"));
+    pretty_assertions::assert_eq!(output.to_kstr().as_str(), *expected, "{name}: {input:?}");
+  }
+}
+
+/// Assert that a filter converts inputs to the expected outputs.
+///
+/// Mismatches are reported with a diff.
+///
+/// ```
+/// use liquid_heck::{assert_filter, KebabCase};
+///
+/// assert_filter!(KebabCase, "HelloWorld" => "hello-world");
+/// assert_filter!(KebabCase, [
+///     "hello world" => "hello-world",
+///     "hello_world" => "hello-world",
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! assert_filter {
+  ($filter:expr, [$($input:expr => $expected:expr),* $(,)?]) => {
+    $crate::testing::assert_conversions(&$filter, &[$(($input, $expected)),*])
+  };
+  ($filter:expr, $input:expr => $expected:expr $(,)?) => {
+    $crate::testing::assert_conversions(&$filter, &[($input, $expected)])
+  };
+}
+
+#[cfg(all(test, feature = "filter-snake"))]
+mod tests {
+  use crate::SnakeCase;
+
+  #[test]
+  fn assert_filter() {
+    assert_filter!(SnakeCase, "HelloWorld21" => "hello_world21");
+    assert_filter!(SnakeCase, ["a b" => "a_b", "C" => "c"]);
+  }
+
+  #[test]
+  #[should_panic(expected = "snakecase: \"AB\"")]
+  fn assert_filter_mismatch() {
+    assert_filter!(SnakeCase, "AB" => "a_b");
+  }
+}
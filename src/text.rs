@@ -0,0 +1,441 @@
+//! Filters converting the identifiers embedded in a longer text, leaving the
+//! rest of the text as written.
+use crate::args::{invalid_argument, positional_or_keyword};
+use crate::conversion;
+use crate::filters::{check_input_length, nil_output, runtime_options};
+use crate::objects::case_argument;
+use liquid_core::{
+  Display_filter, Expression, Filter, FilterParameters, FilterReflection, FromFilterParameters,
+  ParseFilter, Result, Runtime, Value, ValueView,
+};
+
+/// Whether `name` can be the name of a format string placeholder: a
+/// non-empty run of letters, digits, `_` and `-`, with `.` between the
+/// fields of a nested name.
+fn is_placeholder_name(name: &str) -> bool {
+  name.split('.').all(|field| {
+    !field.is_empty()
+      && field
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+  })
+}
+
+/// Rename with `rename` the named placeholders of the format string `s`,
+/// `{name}`, `{name:spec}` or `{name!conversion}`, each field of a nested
+/// `{user.name}` on its own. `{{` and `}}` are the escaped braces, and the
+/// braces not enclosing a name are kept as written.
+pub(crate) fn rename_placeholders(s: &str, mut rename: impl FnMut(&str) -> String) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut rest = s;
+  while let Some(start) = rest.find(['{', '}']) {
+    output.push_str(&rest[..start]);
+    rest = &rest[start..];
+    if rest.starts_with("{{") || rest.starts_with("}}") {
+      output.push_str(&rest[..2]);
+      rest = &rest[2..];
+      continue;
+    }
+    let placeholder = rest[1..].find('}').map(|end| &rest[1..end + 1]);
+    let name = placeholder.map(|inner| &inner[..inner.find([':', '!']).unwrap_or(inner.len())]);
+    match (placeholder, name) {
+      (Some(inner), Some(name)) if rest.starts_with('{') && is_placeholder_name(name) => {
+        output.push('{');
+        for (i, field) in name.split('.').enumerate() {
+          if i > 0 {
+            output.push('.');
+          }
+          output.push_str(&rename(field));
+        }
+        output.push_str(&inner[name.len()..]);
+        output.push('}');
+        rest = &rest[inner.len() + 2..];
+      }
+      _ => {
+        output.push_str(&rest[..1]);
+        rest = &rest[1..];
+      }
+    }
+  }
+  output.push_str(rest);
+  output
+}
+
+/// Convert with `convert` the substrings of `s` enclosed by `open` and
+/// `close`, keeping the delimiters. An `open` without its `close` is kept as
+/// written with the rest of the string.
+pub(crate) fn convert_within(
+  s: &str,
+  open: &str,
+  close: &str,
+  mut convert: impl FnMut(&str) -> String,
+) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut rest = s;
+  while let Some(start) = rest.find(open) {
+    let inner = &rest[start + open.len()..];
+    let Some(end) = inner.find(close) else {
+      break;
+    };
+    output.push_str(&rest[..start + open.len()]);
+    output.push_str(&convert(&inner[..end]));
+    output.push_str(close);
+    rest = &inner[end + close.len()..];
+  }
+  output.push_str(rest);
+  output
+}
+
+/// The length of the opening fence of a Markdown code block starting
+/// `line`, three or more backticks or tildes.
+fn fence_len(line: &str) -> Option<usize> {
+  let marker = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+  let len = line.len() - line.trim_start_matches(marker).len();
+  (len >= 3).then_some(len)
+}
+
+/// The length of the Markdown block markers starting `line`: the
+/// indentation, the heading `#`s, the blockquote `>`s and the list bullets or
+/// numbers, with the spaces following them.
+fn block_markers_len(line: &str) -> usize {
+  let mut len = 0;
+  loop {
+    let rest = &line[len..];
+    let body = rest.trim_start();
+    let marker = match body.chars().next() {
+      Some('>') => 1,
+      Some('#') => body.len() - body.trim_start_matches('#').len(),
+      Some('-' | '*' | '+') => 1,
+      Some('0'..='9') => {
+        let digits = body.len() - body.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        match body[digits..].starts_with(['.', ')']) {
+          true => digits + 1,
+          false => return len + rest.len() - body.len(),
+        }
+      }
+      _ => return len + rest.len() - body.len(),
+    };
+    let after = &body[marker..];
+    if !(after.is_empty() || after.starts_with([' ', '\t']) || body.starts_with('>')) {
+      return len + rest.len() - body.len();
+    }
+    len += rest.len() - after.trim_start().len();
+    if len == line.len() {
+      return len;
+    }
+  }
+}
+
+/// The length of the inline Markdown markup starting `s`, kept as written:
+/// a code span, the destination of a link, an autolink or HTML tag, or one
+/// of the `[`, `]`, `*` and `|` markup characters.
+fn markup_len(s: &str) -> usize {
+  match s.as_bytes()[0] {
+    b'`' => {
+      let ticks = s.len() - s.trim_start_matches('`').len();
+      s[ticks..]
+        .find(&s[..ticks])
+        .map_or(ticks, |end| end + 2 * ticks)
+    }
+    b']' if s[1..].starts_with('(') => s.find(')').map_or(1, |end| end + 1),
+    b'<' => s.find('>').map_or(1, |end| end + 1),
+    _ => 1,
+  }
+}
+
+/// Convert with `convert` the prose of the Markdown document `s`, each line
+/// on its own: the fenced code blocks, the code spans, the link destinations,
+/// the link reference definitions and the markup are kept as written, and
+/// the text between them converted with its surrounding spaces kept.
+pub(crate) fn convert_markdown(s: &str, mut convert: impl FnMut(&str) -> String) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut fence: Option<&str> = None;
+  for line in s.split_inclusive('\n') {
+    let content = line.trim_end_matches(['\n', '\r']);
+    let trimmed = content.trim_start();
+    match fence {
+      Some(open) => {
+        if trimmed.starts_with(open) {
+          fence = None;
+        }
+        output.push_str(line);
+        continue;
+      }
+      None => {
+        if let Some(len) = fence_len(trimmed) {
+          fence = Some(&trimmed[..len]);
+          output.push_str(line);
+          continue;
+        }
+      }
+    }
+    if trimmed.starts_with('[') && trimmed.contains("]:") {
+      output.push_str(line);
+      continue;
+    }
+    let markers = block_markers_len(content);
+    output.push_str(&content[..markers]);
+    let mut rest = &content[markers..];
+    while !rest.is_empty() {
+      let text = rest
+        .find(['`', '<', '[', ']', '*', '|'])
+        .unwrap_or(rest.len());
+      let (prose, markup) = rest.split_at(text);
+      let words = prose.trim();
+      if words.chars().any(char::is_alphanumeric) {
+        let start = prose.len() - prose.trim_start().len();
+        output.push_str(&prose[..start]);
+        output.push_str(&convert(words));
+        output.push_str(&prose[start + words.len()..]);
+      } else {
+        output.push_str(prose);
+      }
+      let len = if markup.is_empty() {
+        0
+      } else {
+        markup_len(markup)
+      };
+      output.push_str(&markup[..len]);
+      rest = &markup[len..];
+    }
+    output.push_str(&line[content.len()..]);
+  }
+  output
+}
+
+#[cfg(feature = "filter-rename-placeholders")]
+#[derive(Debug, FilterParameters)]
+struct RenamePlaceholdersArgs {
+  #[parameter(
+    description = "The case of the placeholder names, e.g. \"snake\" or \"lowercamelcase\".",
+    arg_type = "str"
+  )]
+  case: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `case`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "case"
+  )]
+  case_keyword: Option<Expression>,
+}
+
+/// Convert the names of the placeholders of a format string, leaving the
+/// surrounding text alone:
+/// `{{ "Hello {user_name}, your {order_id}" | rename_placeholders: "camel" }}`
+/// renders `Hello {userName}, your {orderId}`. The format specs
+/// (`{total:.2}`), the positional placeholders (`{0}`, `{}`) and the escaped
+/// braces (`{{`) are kept.
+#[cfg(feature = "filter-rename-placeholders")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "rename_placeholders",
+  description = "Convert the names of the placeholders of a format string to the given case.",
+  parameters(RenamePlaceholdersArgs),
+  parsed(RenamePlaceholdersFilter)
+)]
+pub struct RenamePlaceholders;
+
+#[cfg(feature = "filter-rename-placeholders")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "rename_placeholders"]
+struct RenamePlaceholdersFilter {
+  #[parameters]
+  args: RenamePlaceholdersArgs,
+}
+
+#[cfg(feature = "filter-rename-placeholders")]
+impl Filter for RenamePlaceholdersFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let case = positional_or_keyword("case", args.case, args.case_keyword)?
+      .ok_or_else(|| invalid_argument("case", "Case name expected"))?;
+    let case = case_argument("case", &case)?;
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let options = runtime_options(runtime)?;
+    check_input_length(s, &options)?;
+    let output = rename_placeholders(s, |name| conversion::convert(case, name, &options));
+    #[cfg(feature = "log")]
+    log::debug!(
+      "rename_placeholders: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(feature = "filter-convert-within")]
+#[derive(Debug, FilterParameters)]
+struct ConvertWithinArgs {
+  #[parameter(description = "The opening delimiter, e.g. \"`\".", arg_type = "str")]
+  open: Option<Expression>,
+  #[parameter(description = "The closing delimiter, e.g. \"`\".", arg_type = "str")]
+  close: Option<Expression>,
+  #[parameter(
+    description = "The case of the enclosed substrings, e.g. \"snake\" or \"kebabcase\".",
+    arg_type = "str"
+  )]
+  case: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `open`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "open"
+  )]
+  open_keyword: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `close`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "close"
+  )]
+  close_keyword: Option<Expression>,
+  #[parameter(
+    description = "Same as the positional `case`.",
+    arg_type = "str",
+    mode = "keyword",
+    rename = "case"
+  )]
+  case_keyword: Option<Expression>,
+}
+
+/// Convert only the substrings enclosed by two delimiters, leaving the rest
+/// of the text alone: ``{{ text | convert_within: "`", "`", "snake" }}``
+/// restyles the inline code spans of a sentence. The delimiters are kept, and
+/// an opening delimiter without its closing one is left as written.
+#[cfg(feature = "filter-convert-within")]
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+  name = "convert_within",
+  description = "Convert the substrings enclosed by the delimiters to the given case.",
+  parameters(ConvertWithinArgs),
+  parsed(ConvertWithinFilter)
+)]
+pub struct ConvertWithin;
+
+#[cfg(feature = "filter-convert-within")]
+#[derive(Debug, FromFilterParameters, Display_filter)]
+#[name = "convert_within"]
+struct ConvertWithinFilter {
+  #[parameters]
+  args: ConvertWithinArgs,
+}
+
+#[cfg(feature = "filter-convert-within")]
+impl Filter for ConvertWithinFilter {
+  #[cfg_attr(feature = "tracing", tracing::instrument(
+    skip(input, runtime),
+    fields(input = %crate::instrument::Redacted::Value(input))
+  ))]
+  fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<Value> {
+    if let Some(output) = nil_output(input, runtime)? {
+      return Ok(output);
+    }
+    let args = self.args.evaluate(runtime)?;
+    let open = positional_or_keyword("open", args.open, args.open_keyword)?.unwrap_or_default();
+    let close = positional_or_keyword("close", args.close, args.close_keyword)?.unwrap_or_default();
+    if open.is_empty() || close.is_empty() {
+      return Err(invalid_argument("open", "Non-empty delimiters expected"));
+    }
+    let case = positional_or_keyword("case", args.case, args.case_keyword)?
+      .ok_or_else(|| invalid_argument("case", "Case name expected"))?;
+    let case = case_argument("case", &case)?;
+    let s = input.to_kstr();
+    let s = s.as_str();
+    let options = runtime_options(runtime)?;
+    check_input_length(s, &options)?;
+    let output = convert_within(s, &open, &close, |inner| {
+      conversion::convert(case, inner, &options)
+    });
+    #[cfg(feature = "log")]
+    log::debug!(
+      "convert_within: {} -> {}",
+      crate::instrument::Redacted::Str(s),
+      crate::instrument::Redacted::Str(&output)
+    );
+    Ok(Value::scalar(output))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  #[cfg(feature = "filter-rename-placeholders")]
+  fn rename_placeholders() {
+    assert_eq!(
+      liquid_core::call_filter!(
+        RenamePlaceholders,
+        "Hello {user_name}, your {order_id} is ready",
+        "camel"
+      )
+      .unwrap(),
+      liquid_core::value!("Hello {userName}, your {orderId} is ready")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(
+        RenamePlaceholders,
+        "{{literal}} {0} {} {totalPrice:>10.2} {userName!r} {user.firstName}",
+        "snakecase"
+      )
+      .unwrap(),
+      liquid_core::value!("{{literal}} {0} {} {total_price:>10.2} {user_name!r} {user.first_name}")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(RenamePlaceholders, "${HOME} { not a name } {open", "kebab").unwrap(),
+      liquid_core::value!("${home} { not a name } {open")
+    );
+    assert!(liquid_core::call_filter!(RenamePlaceholders, "{a}").is_err());
+    assert!(liquid_core::call_filter!(RenamePlaceholders, "{a}", "camelot").is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "filter-convert-within")]
+  fn convert_within() {
+    assert_eq!(
+      liquid_core::call_filter!(
+        ConvertWithin,
+        "Set `maxRetries` and `Request Timeout`, then `",
+        "`",
+        "`",
+        "snake"
+      )
+      .unwrap(),
+      liquid_core::value!("Set `max_retries` and `request_timeout`, then `")
+    );
+    assert_eq!(
+      liquid_core::call_filter!(
+        ConvertWithin,
+        "[[User Id]] and [[Order Id]]",
+        "[[",
+        "]]",
+        "kebab"
+      )
+      .unwrap(),
+      liquid_core::value!("[[user-id]] and [[order-id]]")
+    );
+    let template = liquid::ParserBuilder::with_stdlib()
+      .filter(ConvertWithin)
+      .build()
+      .unwrap()
+      .parse("{{ 'a <Foo Bar> b' | convert_within: open: '<', close: '>', case: 'camel' }}")
+      .unwrap();
+    assert_eq!(
+      template.render(&liquid::object!({})).unwrap(),
+      "a <fooBar> b"
+    );
+    assert!(liquid_core::call_filter!(ConvertWithin, "a", "", "`", "snake").is_err());
+    assert!(liquid_core::call_filter!(ConvertWithin, "a", "`", "`").is_err());
+  }
+}
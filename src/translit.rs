@@ -0,0 +1,216 @@
+//! Transliteration of the accented Latin letters to ASCII.
+#[cfg(any(feature = "diacritics", feature = "homoglyphs"))]
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(feature = "diacritics")]
+use unicode_normalization::UnicodeNormalization;
+
+/// Push the ASCII spelling of `c` to `output`, keeping its case: `é` is
+/// `e`, `Æ` is `AE`, `ß` is `ss`. Returns `false`, pushing nothing, when
+/// `c` has no ASCII spelling.
+pub(crate) fn push_ascii(output: &mut String, c: char) -> bool {
+  if c.is_ascii() {
+    output.push(c);
+    return true;
+  }
+  let Some(ascii) = ascii_lowercase(c) else {
+    return false;
+  };
+  if c.is_uppercase() {
+    output.extend(ascii.chars().map(|c| c.to_ascii_uppercase()));
+  } else {
+    output.push_str(ascii);
+  }
+  true
+}
+
+/// `s` transliterated to ASCII by [`push_ascii`], the characters without
+/// ASCII spelling replaced with `replacement`.
+pub(crate) fn to_ascii(s: &str, replacement: char) -> String {
+  let mut output = String::with_capacity(s.len());
+  for c in s.chars() {
+    if !push_ascii(&mut output, c) {
+      output.push(replacement);
+    }
+  }
+  output
+}
+
+/// `s` without the diacritics of its letters: `é` is `e`, `Ñ` is `N`. Unlike
+/// [`to_ascii`], the letters without canonical decomposition, like `ø`, `ß`
+/// or `Ж`, are kept. The combining marks of the decomposed string are
+/// dropped, then the rest recomposed.
+#[cfg(feature = "diacritics")]
+pub(crate) fn strip_diacritics(s: &str) -> Cow<'_, str> {
+  if s.is_ascii() {
+    return Cow::Borrowed(s);
+  }
+  Cow::Owned(s.nfd().filter(|&c| !is_diacritic(c)).nfc().collect())
+}
+
+/// Is `c` one of the combining diacritical marks? The vowel signs of the
+/// other scripts, also combining marks, are kept.
+#[cfg(feature = "diacritics")]
+fn is_diacritic(c: char) -> bool {
+  matches!(
+    c,
+    '\u{300}'..='\u{36F}'
+      | '\u{1AB0}'..='\u{1AFF}'
+      | '\u{1DC0}'..='\u{1DFF}'
+      | '\u{20D0}'..='\u{20FF}'
+      | '\u{FE20}'..='\u{FE2F}'
+  )
+}
+
+/// `s` with its confusable characters replaced by the ASCII letter or digit
+/// they look like: the Cyrillic `а` and the Greek `ο` become the Latin `a`
+/// and `o`, the fullwidth `Ｐ` a `P`. Every confusable is replaced, so a
+/// text in Cyrillic or Greek becomes a mix of scripts.
+#[cfg(feature = "homoglyphs")]
+pub(crate) fn normalize_homoglyphs(s: &str) -> Cow<'_, str> {
+  if !s.contains(|c| homoglyph(c).is_some()) {
+    return Cow::Borrowed(s);
+  }
+  Cow::Owned(s.chars().map(|c| homoglyph(c).unwrap_or(c)).collect())
+}
+
+/// The ASCII character `c` is confusable with.
+#[cfg(feature = "homoglyphs")]
+fn homoglyph(c: char) -> Option<char> {
+  Some(match c {
+    // Fullwidth ASCII.
+    '\u{FF01}'..='\u{FF5E}' => char::from_u32(u32::from(c) - 0xFEE0)?,
+    // Cyrillic.
+    'А' => 'A',
+    'В' => 'B',
+    'Е' => 'E',
+    'К' => 'K',
+    'М' => 'M',
+    'Н' => 'H',
+    'О' => 'O',
+    'Р' => 'P',
+    'С' => 'C',
+    'Т' => 'T',
+    'У' | 'Ү' => 'Y',
+    'Х' => 'X',
+    'Ѕ' => 'S',
+    'І' | 'Ӏ' => 'I',
+    'Ј' => 'J',
+    'Ԛ' => 'Q',
+    'Ԝ' => 'W',
+    'а' => 'a',
+    'е' => 'e',
+    'о' => 'o',
+    'р' => 'p',
+    'с' => 'c',
+    'у' => 'y',
+    'х' => 'x',
+    'ѕ' => 's',
+    'і' => 'i',
+    'ј' => 'j',
+    'ԁ' => 'd',
+    'һ' => 'h',
+    'ԛ' => 'q',
+    'ԝ' => 'w',
+    'ӏ' => 'l',
+    // Greek.
+    'Α' => 'A',
+    'Β' => 'B',
+    'Ε' => 'E',
+    'Ζ' => 'Z',
+    'Η' => 'H',
+    'Ι' => 'I',
+    'Κ' => 'K',
+    'Μ' => 'M',
+    'Ν' => 'N',
+    'Ο' => 'O',
+    'Ρ' => 'P',
+    'Τ' => 'T',
+    'Υ' => 'Y',
+    'Χ' => 'X',
+    'ο' => 'o',
+    'ν' => 'v',
+    'ι' => 'i',
+    'α' => 'a',
+    'ρ' => 'p',
+    // Latin and letterlike lookalikes.
+    'ı' => 'i',
+    'ℓ' => 'l',
+    'ⅰ' => 'i',
+    'ⅼ' => 'l',
+    _ => return None,
+  })
+}
+
+/// The ASCII spelling of the lowercase of `c`.
+fn ascii_lowercase(c: char) -> Option<&'static str> {
+  Some(match c {
+    'À'..='Å' | 'à'..='å' | 'Ā'..='ą' => "a",
+    'Æ' | 'æ' => "ae",
+    'Ç' | 'ç' | 'Ć'..='č' => "c",
+    'Ð' | 'ð' | 'Ď'..='đ' => "d",
+    'È'..='Ë' | 'è'..='ë' | 'Ē'..='ě' => "e",
+    'Ĝ'..='ģ' => "g",
+    'Ĥ'..='ħ' => "h",
+    'Ì'..='Ï' | 'ì'..='ï' | 'Ĩ'..='ı' => "i",
+    'Ĳ' | 'ĳ' => "ij",
+    'Ĵ' | 'ĵ' => "j",
+    'Ķ'..='ĸ' => "k",
+    'Ĺ'..='ł' => "l",
+    'Ñ' | 'ñ' | 'Ń'..='ň' | 'Ŋ' | 'ŋ' => "n",
+    'ŉ' => "n",
+    'Ò'..='Ö' | 'Ø' | 'ò'..='ö' | 'ø' | 'Ō'..='ő' => "o",
+    'Œ' | 'œ' => "oe",
+    'Ŕ'..='ř' => "r",
+    'Ś'..='š' | 'Ș' | 'ș' | 'ſ' => "s",
+    'ß' | 'ẞ' => "ss",
+    'Ţ'..='ŧ' | 'Ț' | 'ț' => "t",
+    'Þ' | 'þ' => "th",
+    'Ù'..='Ü' | 'ù'..='ü' | 'Ũ'..='ų' => "u",
+    'Ŵ' | 'ŵ' => "w",
+    'Ý' | 'ý' | 'ÿ' | 'Ŷ'..='Ÿ' => "y",
+    'Ź'..='ž' => "z",
+    _ => return None,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn ascii() {
+    let mut output = String::new();
+    for c in "María-José Œuvre Łódź STRAẞE ÿ".chars() {
+      assert!(push_ascii(&mut output, c), "{c}");
+    }
+    assert_eq!(output, "Maria-Jose OEuvre Lodz STRASSE y");
+    assert!(!push_ascii(&mut output, 'Ж'));
+    assert_eq!(to_ascii("Жan", '?'), "?an");
+  }
+
+  #[test]
+  #[cfg(feature = "diacritics")]
+  fn diacritics() {
+    assert_eq!(strip_diacritics("plain"), Cow::Borrowed("plain"));
+    assert_eq!(strip_diacritics("Crème Brûlée Ñandú"), "Creme Brulee Nandu");
+    // Combining marks typed apart, and the letters without decomposition.
+    assert_eq!(
+      strip_diacritics("Cafe\u{301} Øresund Straße"),
+      "Cafe Øresund Straße"
+    );
+    // Hangul syllables and Devanagari vowel signs survive the round trip.
+    assert_eq!(strip_diacritics("한국 हिन्दी"), "한국 हिन्दी");
+  }
+
+  #[test]
+  #[cfg(feature = "homoglyphs")]
+  fn homoglyphs() {
+    assert_eq!(normalize_homoglyphs("paypal"), Cow::Borrowed("paypal"));
+    // The `а` are Cyrillic, the `ο` Greek.
+    assert_eq!(normalize_homoglyphs("pаypаl.cοm"), "paypal.com");
+    assert_eq!(normalize_homoglyphs("Ｐａｙ２"), "Pay2");
+    assert_eq!(normalize_homoglyphs("Жук ΑΒΓ"), "Жyк ABΓ");
+  }
+}
@@ -0,0 +1,66 @@
+//! [wasm-bindgen](https://crates.io/crates/wasm-bindgen) bindings. Only
+//! available with the `wasm` feature.
+//!
+//! The bindings are thin wrappers around [`crate::conversion`] and the
+//! Liquid filters, so a web page gets exactly the same output as the
+//! server side.
+use crate::conversion;
+use wasm_bindgen::prelude::*;
+
+/// Convert the string to UpperCamelCase.
+#[wasm_bindgen(js_name = toUpperCamelCase)]
+pub fn to_upper_camel_case(s: &str) -> String {
+  conversion::to_upper_camel_case(s)
+}
+
+/// Convert the string to lowerCamelCase.
+#[wasm_bindgen(js_name = toLowerCamelCase)]
+pub fn to_lower_camel_case(s: &str) -> String {
+  conversion::to_lower_camel_case(s)
+}
+
+/// Convert the string to snake_case.
+#[wasm_bindgen(js_name = toSnakeCase)]
+pub fn to_snake_case(s: &str) -> String {
+  conversion::to_snake_case(s)
+}
+
+/// Convert the string to kebab-case.
+#[wasm_bindgen(js_name = toKebabCase)]
+pub fn to_kebab_case(s: &str) -> String {
+  conversion::to_kebab_case(s)
+}
+
+/// Convert the string to SHOUTY_SNAKE_CASE.
+#[wasm_bindgen(js_name = toShoutySnakeCase)]
+pub fn to_shouty_snake_case(s: &str) -> String {
+  conversion::to_shouty_snake_case(s)
+}
+
+/// Convert the string to Title Case.
+#[wasm_bindgen(js_name = toTitleCase)]
+pub fn to_title_case(s: &str) -> String {
+  conversion::to_title_case(s)
+}
+
+/// Convert the string to SHOUTY-KEBAB-CASE.
+#[wasm_bindgen(js_name = toShoutyKebabCase)]
+pub fn to_shouty_kebab_case(s: &str) -> String {
+  conversion::to_shouty_kebab_case(s)
+}
+
+/// Convert the string to Train-Case.
+#[wasm_bindgen(js_name = toTrainCase)]
+pub fn to_train_case(s: &str) -> String {
+  conversion::to_train_case(s)
+}
+
+/// Render `template` with the Liquid stdlib and all the heck filters.
+///
+/// `globals` is a JSON object holding the template variables.
+#[wasm_bindgen(js_name = renderTemplate)]
+pub fn render_template(template: &str, globals: &str) -> Result<String, JsError> {
+  let globals: liquid::Object = serde_json::from_str(globals)?;
+  let template = crate::parser_with_heck()?.parse(template)?;
+  Ok(template.render(&globals)?)
+}
@@ -0,0 +1,550 @@
+//! Word segmentation.
+//!
+//! A port of the [Heck](https://crates.io/crates/heck) word splitting, so
+//! the conversions can honor [`Options`]. With the default options the
+//! words are exactly the ones Heck finds.
+use crate::spelling::unit_len;
+use crate::{CjkStyle, Options, UnitStyle};
+use alloc::string::String;
+use core::ops::Range;
+
+/// Iterator over the words of a string.
+pub(crate) struct Words<'a> {
+  /// The text after the current chunk.
+  rest: &'a str,
+  separator: fn(char) -> bool,
+  /// The punctuation kept in the words.
+  kept: String,
+  /// The current run of alphanumeric characters.
+  chunk: &'a str,
+  digit_boundary: bool,
+  script_boundary: bool,
+  cjk: CjkStyle,
+  version_strings: bool,
+  opaque_tokens: bool,
+  amounts: bool,
+}
+
+impl<'a> Words<'a> {
+  pub(crate) fn new(s: &'a str, options: &Options) -> Self {
+    Words {
+      rest: s,
+      separator: separator(options),
+      kept: options.keep_punctuation.clone(),
+      chunk: "",
+      digit_boundary: options.digit_boundary,
+      script_boundary: options.script_boundary,
+      cjk: options.cjk,
+      version_strings: options.version_strings,
+      opaque_tokens: options.opaque_tokens,
+      amounts: options.units == UnitStyle::Keep,
+    }
+  }
+}
+
+impl Words<'_> {
+  fn is_separator(&self, c: char) -> bool {
+    (self.separator)(c) && !self.kept.contains(c)
+  }
+}
+
+impl<'a> Iterator for Words<'a> {
+  type Item = &'a str;
+
+  fn next(&mut self) -> Option<&'a str> {
+    if self.chunk.is_empty() {
+      let start = self.rest.find(|c| !self.is_separator(c))?;
+      self.rest = &self.rest[start..];
+      // A version string, an opaque token or an amount is a word, separators
+      // included.
+      let version = version_len(self.rest).filter(|_| self.version_strings);
+      let token = version
+        .or_else(|| opaque_len(self.rest).filter(|_| self.opaque_tokens))
+        .or_else(|| self.amounts.then(|| quantity_len(self.rest)).flatten());
+      if let Some(len) = token {
+        let (word, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        return Some(word);
+      }
+      let end = self
+        .rest
+        .find(|c| self.is_separator(c))
+        .unwrap_or(self.rest.len());
+      (self.chunk, self.rest) = self.rest.split_at(end);
+    }
+    let len = match self.cjk {
+      CjkStyle::Letters => self.first_word_len(self.chunk),
+      cjk => {
+        let cjk_char = |c| is_cjk(c, cjk == CjkStyle::Preserve);
+        if self.chunk.starts_with(cjk_char) {
+          // A CJK run is a word.
+          self
+            .chunk
+            .find(|c| !cjk_char(c))
+            .unwrap_or(self.chunk.len())
+        } else {
+          let end = self.chunk.find(cjk_char).unwrap_or(self.chunk.len());
+          self.first_word_len(&self.chunk[..end])
+        }
+      }
+    };
+    let (word, rest) = self.chunk.split_at(len);
+    self.chunk = rest;
+    Some(word)
+  }
+}
+
+/// The words of `input`, split as the conversions split them with `options`
+/// (`digit_boundary`, `script_boundary`, `ascii_mode`, `cjk`).
+///
+/// ```
+/// use liquid_heck::{segment, Options};
+///
+/// let words: Vec<&str> = segment("XMLHttpRequest_v2", &Options::default()).collect();
+/// assert_eq!(words, ["XML", "Http", "Request", "v2"]);
+/// ```
+pub fn segment<'a>(input: &'a str, options: &Options) -> impl Iterator<Item = &'a str> + 'a {
+  Words::new(input, options)
+}
+
+/// The byte ranges of the words of `s`.
+pub(crate) fn word_spans<'a>(
+  s: &'a str,
+  options: &Options,
+) -> impl Iterator<Item = Range<usize>> + 'a {
+  Words::new(s, options).map(move |word| {
+    let start = word.as_ptr() as usize - s.as_ptr() as usize;
+    start..start + word.len()
+  })
+}
+
+/// Rebuild `s` with `f` pushing each word, keeping the text between the words
+/// as is.
+pub(crate) fn map_words(s: &str, options: &Options, mut f: impl FnMut(&mut String, &str)) -> String {
+  let mut output = String::with_capacity(s.len());
+  let mut end = 0;
+  for span in word_spans(s, options) {
+    output.push_str(&s[end..span.start]);
+    f(&mut output, &s[span.clone()]);
+    end = span.end;
+  }
+  output.push_str(&s[end..]);
+  output
+}
+
+/// Split `s` after its first `n` words. The text up to the next word stays
+/// in the tail.
+pub(crate) fn split_after_words<'a>(s: &'a str, n: usize, options: &Options) -> (&'a str, &'a str) {
+  let end = match n.checked_sub(1) {
+    Some(last) => word_spans(s, options)
+      .nth(last)
+      .map_or(s.len(), |span| span.end),
+    None => 0,
+  };
+  s.split_at(end)
+}
+
+/// Does `c` separate the words, as [`Words`] splits them with `options`?
+pub(crate) fn is_word_separator(c: char, options: &Options) -> bool {
+  separator(options)(c) && !options.keep_punctuation.contains(c)
+}
+
+/// Is `c` punctuation the conversions drop with `options`: a separator
+/// other than the whitespace, `_` and `-`?
+pub(crate) fn is_dropped_punctuation(c: char, options: &Options) -> bool {
+  is_word_separator(c, options) && !c.is_whitespace() && !matches!(c, '_' | '-')
+}
+
+/// The characters separating the words with `options`.
+fn separator(options: &Options) -> fn(char) -> bool {
+  let ascii = options.ascii_mode || !cfg!(feature = "heck05");
+  match (options.cjk, ascii) {
+    (CjkStyle::Letters, false) => is_separator,
+    (CjkStyle::Letters, true) => is_ascii_separator,
+    (CjkStyle::Word, false) => |c| is_separator(c) && !is_cjk(c, false),
+    (CjkStyle::Word, true) => |c| is_ascii_separator(c) && !is_cjk(c, false),
+    (CjkStyle::Preserve, false) => |c| is_separator(c) && !is_cjk(c, true),
+    (CjkStyle::Preserve, true) => |c| is_ascii_separator(c) && !is_cjk(c, true),
+  }
+}
+
+fn is_separator(c: char) -> bool {
+  !c.is_alphanumeric()
+}
+
+fn is_ascii_separator(c: char) -> bool {
+  !c.is_ascii_alphanumeric()
+}
+
+/// Is `c` a Chinese, Japanese or Korean character? With `punctuation`, the
+/// CJK punctuation and the fullwidth forms are too.
+pub(crate) fn is_cjk(c: char, punctuation: bool) -> bool {
+  matches!(c,
+    '\u{1100}'..='\u{11FF}' // Hangul Jamo
+    | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+    | '\u{3130}'..='\u{318F}' // Hangul Compatibility Jamo
+    | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+    | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+    | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+    | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    | '\u{20000}'..='\u{2FA1F}' // Supplementary Ideographic Plane
+  ) || punctuation
+    && matches!(c,
+      '\u{3000}'..='\u{303F}' // CJK Symbols and Punctuation
+      | '\u{FF00}'..='\u{FFEF}' // Halfwidth and Fullwidth Forms
+    )
+}
+
+/// The case of the last cased character of the current word.
+#[derive(Clone, Copy, PartialEq)]
+enum WordMode {
+  /// No cased character yet.
+  Boundary,
+  Lowercase,
+  Uppercase,
+}
+
+/// Byte length of the amount starting `s`, with the unit abbreviation
+/// joined to it, like `19.99` or `500ml`. The amount must end the word.
+fn quantity_len(s: &str) -> Option<usize> {
+  let len = amount_len(s)?;
+  match s[len..].chars().next() {
+    Some(c) if c.is_alphabetic() => unit_len(&s[len..]).map(|(unit, _, _)| len + unit),
+    Some(c) if c.is_alphanumeric() => None,
+    _ => Some(len),
+  }
+}
+
+/// Byte length of the amount starting `s`, like `19.99` or `1,299.50`:
+/// digits with dots or commas between them.
+pub(crate) fn amount_len(s: &str) -> Option<usize> {
+  let digits = |s: &str| s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+  let mut len = Some(digits(s)).filter(|&len| len > 0)?;
+  while s[len..].starts_with(['.', ',']) {
+    match digits(&s[len + 1..]) {
+      0 => break,
+      n => len += 1 + n,
+    }
+  }
+  Some(len)
+}
+
+/// Byte length of the version string starting `s`, like `1.2`, `v1.2.3` or
+/// `1.0.0-rc.1+build.5`: digits with dots, an optional pre-release and
+/// build metadata.
+pub(crate) fn version_len(s: &str) -> Option<usize> {
+  let bytes = s.as_bytes();
+  let digits = |i: usize| bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+  let identifiers = |i: usize| {
+    bytes[i..]
+      .iter()
+      .take_while(|b| b.is_ascii_alphanumeric() || **b == b'-' || **b == b'.')
+      .count()
+  };
+  let mut i = usize::from(matches!(bytes.first(), Some(b'v' | b'V')));
+  let mut numbers = 0;
+  loop {
+    let n = digits(i);
+    if n == 0 {
+      return None;
+    }
+    i += n;
+    numbers += 1;
+    if bytes.get(i) != Some(&b'.') || !bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+      break;
+    }
+    i += 1;
+  }
+  if numbers < 2 {
+    return None;
+  }
+  for mark in [b'-', b'+'] {
+    if bytes.get(i) == Some(&mark) && bytes.get(i + 1).is_some_and(u8::is_ascii_alphanumeric) {
+      i += 1 + identifiers(i + 1);
+      // A trailing dot or hyphen ends the sentence, not the version.
+      while matches!(bytes[i - 1], b'.' | b'-') {
+        i -= 1;
+      }
+    }
+  }
+  // The version must end the word.
+  match s[i..].chars().next() {
+    Some(c) if c.is_alphanumeric() => None,
+    _ => Some(i),
+  }
+}
+
+/// Byte length of the opaque token starting `s`: a UUID, a hexadecimal
+/// hash (a Git SHA) of at least 7 digits, or a base64 string of at least 20
+/// characters that does not read like words.
+pub(crate) fn opaque_len(s: &str) -> Option<usize> {
+  let bytes = s.as_bytes();
+  let ends_word = |i: usize| !s[i..].starts_with(char::is_alphanumeric);
+  let is_uuid = bytes.len() >= 36
+    && bytes[..36].iter().enumerate().all(|(i, b)| match i {
+      8 | 13 | 18 | 23 => *b == b'-',
+      _ => b.is_ascii_hexdigit(),
+    });
+  if is_uuid && ends_word(36) {
+    return Some(36);
+  }
+  let base64 = |b: &u8| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/');
+  let mut len = bytes.iter().take_while(|b| base64(b)).count();
+  let token = &bytes[..len];
+  len += bytes[len..]
+    .iter()
+    .take(2)
+    .take_while(|b| **b == b'=')
+    .count();
+  if !ends_word(len) {
+    return None;
+  }
+  let has = |f: fn(&u8) -> bool| token.iter().any(f);
+  if len >= 7 && token.iter().all(u8::is_ascii_hexdigit) {
+    return (has(u8::is_ascii_digit) && has(u8::is_ascii_alphabetic)).then_some(len);
+  }
+  // Words change between lowercase, uppercase and digits every few
+  // characters at most, random strings about every other character.
+  let class = |b: &u8| (b.is_ascii_lowercase(), b.is_ascii_uppercase());
+  let changes = token
+    .windows(2)
+    .filter(|w| class(&w[0]) != class(&w[1]))
+    .count();
+  let is_base64 = len >= 20
+    && has(u8::is_ascii_lowercase)
+    && has(u8::is_ascii_uppercase)
+    && (has(u8::is_ascii_digit) || has(|b| matches!(b, b'+' | b'/')))
+    && changes * 3 >= len;
+  is_base64.then_some(len)
+}
+
+/// The writing system of the letters, as far as the words are concerned.
+#[derive(Clone, Copy, PartialEq)]
+enum Script {
+  Latin,
+  Greek,
+  Cyrillic,
+  Other,
+}
+
+impl Script {
+  /// The script of `c`, `None` when it is not a letter.
+  fn of(c: char) -> Option<Script> {
+    if !c.is_alphabetic() {
+      return None;
+    }
+    Some(match c {
+      'A'..='Z' | 'a'..='z' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' => Script::Latin,
+      '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Script::Greek,
+      '\u{0400}'..='\u{052F}' => Script::Cyrillic,
+      _ => Script::Other,
+    })
+  }
+}
+
+impl Words<'_> {
+  /// Byte length of the first word of `chunk`, a run of alphanumeric
+  /// characters.
+  fn first_word_len(&self, chunk: &str) -> usize {
+    first_word_len(chunk, self.digit_boundary, self.script_boundary)
+  }
+}
+
+/// Byte length of the first word of `chunk`, a run of alphanumeric characters.
+fn first_word_len(chunk: &str, digit_boundary: bool, script_boundary: bool) -> usize {
+  let mut char_indices = chunk.char_indices().peekable();
+  let mut mode = WordMode::Boundary;
+
+  while let Some((i, c)) = char_indices.next() {
+    let Some(&(next_i, next)) = char_indices.peek() else {
+      break;
+    };
+    let next_mode = if c.is_lowercase() {
+      WordMode::Lowercase
+    } else if c.is_uppercase() {
+      WordMode::Uppercase
+    } else {
+      mode
+    };
+
+    // Word boundary after if current is not uppercase and next is uppercase
+    if next_mode == WordMode::Lowercase && next.is_uppercase() {
+      return next_i;
+    }
+    // Word boundary before if current and previous are uppercase and next is lowercase
+    if mode == WordMode::Uppercase && c.is_uppercase() && next.is_lowercase() {
+      return i;
+    }
+    if digit_boundary && c.is_numeric() != next.is_numeric() {
+      return next_i;
+    }
+    if script_boundary {
+      if let (Some(script), Some(next_script)) = (Script::of(c), Script::of(next)) {
+        if script != next_script {
+          return next_i;
+        }
+      }
+    }
+    mode = next_mode;
+  }
+
+  chunk.len()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use alloc::vec::Vec;
+  use pretty_assertions::assert_eq;
+
+  fn words<'a>(s: &'a str, options: &Options) -> Vec<&'a str> {
+    Words::new(s, options).collect()
+  }
+
+  #[test]
+  fn segmentation() {
+    let default = Options::default();
+    assert_eq!(
+      words("XMLHttpRequest", &default),
+      ["XML", "Http", "Request"]
+    );
+    assert_eq!(
+      words("  hello_world--21 ", &default),
+      ["hello", "world", "21"]
+    );
+    assert_eq!(words("HelloWorld21", &default), ["Hello", "World21"]);
+    let digits = Options {
+      digit_boundary: true,
+      ..Options::default()
+    };
+    assert_eq!(words("HelloWorld21", &digits), ["Hello", "World", "21"]);
+    assert_eq!(words("HTTP2Server", &digits), ["HTTP", "2", "Server"]);
+    let ascii = Options {
+      ascii_mode: true,
+      ..Options::default()
+    };
+    assert_eq!(words("caféAuLait", &ascii), ["caf", "Au", "Lait"]);
+  }
+
+  #[test]
+  fn split_words() {
+    let default = Options::default();
+    assert_eq!(
+      split_after_words("the quickFox jumps", 2, &default),
+      ("the quick", "Fox jumps")
+    );
+    assert_eq!(split_after_words("the fox", 0, &default), ("", "the fox"));
+    assert_eq!(split_after_words("the fox", 5, &default), ("the fox", ""));
+  }
+
+  #[test]
+  fn mapped_words() {
+    let upper = |output: &mut String, word: &str| output.push_str(&word.to_uppercase());
+    assert_eq!(
+      map_words("  userId--url_2 ", &Options::default(), upper),
+      "  USERID--URL_2 "
+    );
+  }
+
+  #[test]
+  fn segmented() {
+    let digits = Options::builder().digit_boundary(true).build();
+    assert_eq!(
+      segment("user_id2 HTTPServer", &digits).collect::<Vec<_>>(),
+      ["user", "id", "2", "HTTP", "Server"]
+    );
+    assert_eq!(segment(" -_ ", &Options::default()).next(), None);
+  }
+
+  #[test]
+  fn cjk() {
+    let word = Options::builder().cjk(CjkStyle::Word).build();
+    assert_eq!(words("hello世界World", &word), ["hello", "世界", "World"]);
+    assert_eq!(words("東京Tower、日本", &word), ["東京", "Tower", "日本"]);
+    let preserve = Options::builder().cjk(CjkStyle::Preserve).build();
+    assert_eq!(
+      words("userName東京、日本 ＡＢ", &preserve),
+      ["user", "Name", "東京、日本", "ＡＢ"]
+    );
+    let ascii = Options {
+      ascii_mode: true,
+      ..word
+    };
+    assert_eq!(words("東京Tower", &ascii), ["東京", "Tower"]);
+  }
+
+  #[test]
+  #[cfg(feature = "heck05")]
+  fn scripts() {
+    let scripts = Options::builder().script_boundary(true).build();
+    assert_eq!(
+      words("получитьuserId", &scripts),
+      ["получить", "user", "Id"]
+    );
+    assert_eq!(words("αβγabc2где", &scripts), ["αβγ", "abc2где"]);
+    assert_eq!(
+      words("получитьuserId", &Options::default()),
+      ["получитьuser", "Id"]
+    );
+  }
+
+  #[test]
+  fn kept_punctuation() {
+    let kept = Options::builder().keep_punctuation("'+").build();
+    assert_eq!(words("C++ Guide", &kept), ["C++", "Guide"]);
+    assert_eq!(words("don't panic!", &kept), ["don't", "panic"]);
+    assert_eq!(words("C++ Guide", &Options::default()), ["C", "Guide"]);
+    assert!(is_dropped_punctuation('!', &kept));
+    assert!(!is_dropped_punctuation('+', &kept));
+    assert!(!is_dropped_punctuation('_', &kept));
+  }
+
+  #[test]
+  fn version_strings() {
+    for (s, len) in [
+      ("1.2", Some(3)),
+      ("v1.2.3-rc.1 notes", Some(11)),
+      ("1.0.0-alpha+build.5.", Some(19)),
+      ("V10.4-", Some(5)),
+      ("1.2a", None),
+      ("v2", None),
+      ("1.", None),
+    ] {
+      assert_eq!(version_len(s), len, "{s}");
+    }
+    let versions = Options::builder().version_strings(true).build();
+    assert_eq!(
+      words("release v1.2.3-rc.1 notes_2.0", &versions),
+      ["release", "v1.2.3-rc.1", "notes", "2.0"]
+    );
+  }
+
+  #[test]
+  fn opaque_tokens() {
+    for (s, len) in [
+      ("123e4567-e89b-12d3-a456-426614174000 id", Some(36)),
+      ("9fceb02d0ae598e95dc970b74767f19372d61af8", Some(40)),
+      ("a1b2c3d", Some(7)),
+      ("deadbeef", None),
+      ("a1b2c3", None),
+      ("dGhpcyBpcyBhIHRlc3Q9Cg==", Some(24)),
+      ("userAccount2024ProfileV2", None),
+      ("a1b2c3dX", None),
+    ] {
+      assert_eq!(opaque_len(s), len, "{s}");
+    }
+    let opaque = Options::builder().opaque_tokens(true).build();
+    assert_eq!(
+      words(
+        "commit 9fceb02d0ae5 on 123e4567-e89b-12d3-a456-426614174000",
+        &opaque
+      ),
+      [
+        "commit",
+        "9fceb02d0ae5",
+        "on",
+        "123e4567-e89b-12d3-a456-426614174000"
+      ]
+    );
+  }
+}